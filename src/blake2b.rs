@@ -0,0 +1,31 @@
+use crate::keccak256::{digest_reader, Hash};
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use std::io::{self, Read};
+
+/// Blake2b with 32-byte output
+type Blake2b256 = Blake2b<U32>;
+
+pub fn blake2b_256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+    Hash(Blake2b256::digest(data).into())
+}
+
+/// Hashes data read in chunks, eg. message too big to fit in memory
+pub fn blake2b_256_hash_reader<R: Read>(reader: R) -> io::Result<Hash> {
+    digest_reader::<Blake2b256, R>(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake2b_256_hash() {
+        let hash = blake2b_256_hash("abc");
+        assert_eq!(
+            hex::encode(hash.0),
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        );
+        assert!(blake2b_256_hash_reader("abc".as_bytes()).unwrap() == hash);
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+use crate::bls12381::serde_utils::{deserialize_bytes, serialize_bytes};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Public key and the message it signed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestPair {
+    pub public_key: Bls12381G1PublicKey,
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub message: Vec<u8>,
+}
+
+/// (public key, message) pairs and their aggregate signature, ie. real data
+/// to be verified with aggregate_verify_bls12381_v1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateManifest {
+    pub pairs: Vec<ManifestPair>,
+    pub signature: Bls12381G2Signature,
+}
+
+impl AggregateManifest {
+    pub fn from_json(json: &str) -> Result<Self, AggregateManifestError> {
+        serde_json::from_str(json).map_err(|err| AggregateManifestError::Json(err.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, AggregateManifestError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| AggregateManifestError::Json(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AggregateManifestError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| AggregateManifestError::Io(err.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AggregateManifestError> {
+        std::fs::write(path, self.to_json()?)
+            .map_err(|err| AggregateManifestError::Io(err.to_string()))
+    }
+
+    /// Pairs in the form expected by aggregate_verify_bls12381_v1
+    pub fn pub_keys_and_msgs(&self) -> Vec<(Bls12381G1PublicKey, &[u8])> {
+        self.pairs
+            .iter()
+            .map(|pair| (pair.public_key, pair.message.as_slice()))
+            .collect()
+    }
+
+    pub fn verify(&self) -> bool {
+        aggregate_verify_bls12381_v1(&self.pub_keys_and_msgs(), &self.signature)
+    }
+}
+
+/// Returns manifest of given number of pairs, each key signing distinct message
+pub fn get_aggregate_manifest_test_data(cnt: u32, msg_size: usize) -> AggregateManifest {
+    let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(cnt, cnt, msg_size);
+    AggregateManifest {
+        pairs: pks
+            .into_iter()
+            .zip(msgs)
+            .map(|(public_key, message)| ManifestPair {
+                public_key,
+                message,
+            })
+            .collect(),
+        signature: Bls12381G2Signature::aggregate(&sigs, true).unwrap(),
+    }
+}
+
+/// Represents an error when loading or saving aggregate manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregateManifestError {
+    Io(String),
+    Json(String),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for AggregateManifestError {}
+
+impl fmt::Display for AggregateManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_manifest_json_roundtrip() {
+        let manifest = get_aggregate_manifest_test_data(3, 64);
+        assert!(manifest.verify());
+
+        let json = manifest.to_json().unwrap();
+        assert_eq!(AggregateManifest::from_json(&json), Ok(manifest.clone()));
+
+        // Aggregate verification does not depend on the order of the pairs, swap messages
+        let mut manifest = manifest;
+        let (first, rest) = manifest.pairs.split_at_mut(1);
+        std::mem::swap(&mut first[0].message, &mut rest[0].message);
+        assert!(!manifest.verify());
+
+        assert!(matches!(
+            AggregateManifest::from_json(r#"{"pairs": []}"#),
+            Err(AggregateManifestError::Json(_))
+        ));
+    }
+}
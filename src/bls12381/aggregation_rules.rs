@@ -0,0 +1,134 @@
+use super::*;
+
+/// Compressed G2 point at infinity (compression and infinity flags set)
+pub const G2_POINT_AT_INFINITY: [u8; Bls12381G2Signature::LENGTH] = {
+    let mut bytes = [0u8; Bls12381G2Signature::LENGTH];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+/// Rules of public key aggregation and fast aggregate verification
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AggregationRules {
+    /// BLS signature draft rules (as in Bls12381G1PublicKey::aggregate and
+    /// fast_aggregate_verify_bls12381_v1): every key is validated (KeyValidate)
+    /// when aggregated, no keys never verify
+    #[default]
+    Ietf,
+    /// Ethereum consensus-spec rules (eth_aggregate_pubkeys, eth_fast_aggregate_verify):
+    /// every key is validated (KeyValidate) before aggregation, no keys verify
+    /// only the point at infinity signature
+    /// see: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/bls.md
+    Eth,
+}
+
+/// Aggregates public keys following given rules, no keys are rejected by both of them
+pub fn aggregate_public_keys_with_rules(
+    public_keys: &[Bls12381G1PublicKey],
+    rules: AggregationRules,
+) -> Result<Bls12381G1PublicKey, BlsError> {
+    match rules {
+        AggregationRules::Ietf => Bls12381G1PublicKey::aggregate(public_keys),
+        AggregationRules::Eth => {
+            for public_key in public_keys {
+                blst::min_pk::PublicKey::key_validate(&public_key.0)?;
+            }
+            Bls12381G1PublicKey::aggregate_prevalidated(public_keys)
+        }
+    }
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification of one message signed
+/// with multiple keys following given rules.
+/// Proofs of possession of the keys are not checked, as in the spec test vectors.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn fast_aggregate_verify_with_rules_bls12381_v1(
+    message: &[u8],
+    public_keys: &[Bls12381G1PublicKey],
+    signature: &Bls12381G2Signature,
+    rules: AggregationRules,
+) -> bool {
+    match rules {
+        AggregationRules::Ietf => {
+            fast_aggregate_verify_bls12381_v1_unchecked(message, public_keys, signature)
+        }
+        AggregationRules::Eth => {
+            // Sync committee signature of no participants
+            if public_keys.is_empty() {
+                return signature.0 == G2_POINT_AT_INFINITY;
+            }
+            match aggregate_public_keys_with_rules(public_keys, rules) {
+                Ok(agg_pk) => verify_bls12381_v1(message, &agg_pk, signature),
+                Err(_) => false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_agree_on_valid_input() {
+        let (_sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(4, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        for rules in [AggregationRules::Ietf, AggregationRules::Eth] {
+            assert_eq!(
+                aggregate_public_keys_with_rules(&pks, rules),
+                Bls12381G1PublicKey::aggregate(&pks)
+            );
+            assert!(fast_aggregate_verify_with_rules_bls12381_v1(
+                &msg, &pks, &agg_sig, rules
+            ));
+            assert!(!fast_aggregate_verify_with_rules_bls12381_v1(
+                &msg[1..],
+                &pks,
+                &agg_sig,
+                rules
+            ));
+            assert_eq!(
+                aggregate_public_keys_with_rules(&[], rules),
+                Err(BlsError::NoPublicKeysGiven)
+            );
+        }
+    }
+
+    #[test]
+    fn rules_disagree() {
+        let (_sks, _pks, msg, _sigs) = get_fast_aggregate_verify_test_data(2, 32);
+        let infinity_sig = Bls12381G2Signature(G2_POINT_AT_INFINITY);
+
+        // No participants with the point at infinity signature
+        assert!(!fast_aggregate_verify_with_rules_bls12381_v1(
+            &msg,
+            &[],
+            &infinity_sig,
+            AggregationRules::Ietf
+        ));
+        assert!(fast_aggregate_verify_with_rules_bls12381_v1(
+            &msg,
+            &[],
+            &infinity_sig,
+            AggregationRules::Eth
+        ));
+    }
+
+    #[test]
+    fn rules_reject_infinity_key_at_any_position() {
+        let (_sks, pks, _msg, _sigs) = get_fast_aggregate_verify_test_data(2, 32);
+        let mut infinity_pk = [0u8; Bls12381G1PublicKey::LENGTH];
+        infinity_pk[0] = 0xc0;
+
+        for pos in 0..=pks.len() {
+            let mut keys = pks.clone();
+            keys.insert(pos, Bls12381G1PublicKey(infinity_pk));
+            for rules in [AggregationRules::Ietf, AggregationRules::Eth] {
+                assert_eq!(
+                    aggregate_public_keys_with_rules(&keys, rules),
+                    Err(BlsError::PointAtInfinity)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use super::*;
+use crate::bls12381::serde_utils::{deserialize_bytes, serialize_bytes};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+#[cfg(feature = "ssz")]
+use ssz::{Decode, Encode};
+use std::fmt;
+use std::path::Path;
+
+/// Signature of one message by one or more public keys, eg. aggregate attestation
+/// exported from a consensus node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestationRecord {
+    pub pubkeys: Vec<Bls12381G1PublicKey>,
+    /// Signing root of the attestation data
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_signing_root"
+    )]
+    pub message: [u8; 32],
+    pub signature: Bls12381G2Signature,
+}
+
+impl AttestationRecord {
+    /// Keys of the dumped attestations belong to validators, whose proofs of possession
+    /// were verified on deposit
+    pub fn verify(&self) -> bool {
+        fast_aggregate_verify_bls12381_v1_unchecked(&self.message, &self.pubkeys, &self.signature)
+    }
+}
+
+fn deserialize_signing_root<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; 32], D::Error> {
+    deserialize_bytes(deserializer)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| D::Error::custom(BlsError::InvalidLength(bytes.len())))
+}
+
+/// Records given as JSON array or SSZ list (List[AttestationRecord]),
+/// format is selected by file extension (.ssz or .json)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttestationDump {
+    pub records: Vec<AttestationRecord>,
+}
+
+impl AttestationDump {
+    pub fn from_json(json: &str) -> Result<Self, AttestationDumpError> {
+        let records = serde_json::from_str(json)
+            .map_err(|err| AttestationDumpError::Json(err.to_string()))?;
+        Ok(Self { records })
+    }
+
+    pub fn to_json(&self) -> Result<String, AttestationDumpError> {
+        serde_json::to_string_pretty(&self.records)
+            .map_err(|err| AttestationDumpError::Json(err.to_string()))
+    }
+
+    #[cfg(feature = "ssz")]
+    pub fn from_ssz(bytes: &[u8]) -> Result<Self, AttestationDumpError> {
+        let records = Vec::<AttestationRecord>::from_ssz_bytes(bytes)
+            .map_err(|err| AttestationDumpError::Ssz(format!("{:?}", err)))?;
+        Ok(Self { records })
+    }
+
+    #[cfg(feature = "ssz")]
+    pub fn to_ssz(&self) -> Vec<u8> {
+        self.records.as_ssz_bytes()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AttestationDumpError> {
+        if is_ssz_file(path) {
+            #[cfg(feature = "ssz")]
+            return Self::from_ssz(
+                &std::fs::read(path).map_err(|err| AttestationDumpError::Io(err.to_string()))?,
+            );
+            #[cfg(not(feature = "ssz"))]
+            return Err(AttestationDumpError::Ssz(
+                "ssz feature is disabled".to_string(),
+            ));
+        }
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| AttestationDumpError::Io(err.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AttestationDumpError> {
+        if is_ssz_file(path) {
+            #[cfg(feature = "ssz")]
+            return std::fs::write(path, self.to_ssz())
+                .map_err(|err| AttestationDumpError::Io(err.to_string()));
+            #[cfg(not(feature = "ssz"))]
+            return Err(AttestationDumpError::Ssz(
+                "ssz feature is disabled".to_string(),
+            ));
+        }
+        std::fs::write(path, self.to_json()?)
+            .map_err(|err| AttestationDumpError::Io(err.to_string()))
+    }
+}
+
+fn is_ssz_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "ssz")
+}
+
+// Container of the variable-size list of public keys, Bytes32 message and Bytes96 signature
+#[cfg(feature = "ssz")]
+impl Encode for AttestationRecord {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        ssz::BYTES_PER_LENGTH_OFFSET
+            + 32
+            + Bls12381G2Signature::LENGTH
+            + self.pubkeys.ssz_bytes_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let offset = ssz::BYTES_PER_LENGTH_OFFSET + 32 + Bls12381G2Signature::LENGTH;
+        let mut encoder = ssz::SszEncoder::container(buf, offset);
+        encoder.append(&self.pubkeys);
+        encoder.append(&self.message);
+        encoder.append(&self.signature);
+        encoder.finalize();
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl Decode for AttestationRecord {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        let mut builder = ssz::SszDecoderBuilder::new(bytes);
+        builder.register_type::<Vec<Bls12381G1PublicKey>>()?;
+        builder.register_type::<[u8; 32]>()?;
+        builder.register_type::<Bls12381G2Signature>()?;
+        let mut decoder = builder.build()?;
+        Ok(Self {
+            pubkeys: decoder.decode_next()?,
+            message: decoder.decode_next()?,
+            signature: decoder.decode_next()?,
+        })
+    }
+}
+
+/// Returns records of given numbers of public keys (eg. attestation aggregates of committees)
+pub fn get_attestation_dump_test_data(pubkey_cnts: &[u32]) -> AttestationDump {
+    let records = pubkey_cnts
+        .iter()
+        .enumerate()
+        .map(|(i, cnt)| {
+            let (sks, pubkeys, _msg, _sigs) = get_fast_aggregate_verify_test_data(*cnt, 32);
+            // Distinct messages, as signing roots of distinct attestation data
+            let message = [i as u8; 32];
+            let sigs: Vec<Bls12381G2Signature> =
+                sks.iter().map(|sk| sk.sign_v1(&message)).collect();
+            AttestationRecord {
+                pubkeys,
+                message,
+                signature: Bls12381G2Signature::aggregate(&sigs, true).unwrap(),
+            }
+        })
+        .collect();
+    AttestationDump { records }
+}
+
+/// Represents an error when loading or saving attestation dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationDumpError {
+    Io(String),
+    Json(String),
+    Ssz(String),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for AttestationDumpError {}
+
+impl fmt::Display for AttestationDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestation_dump_json_roundtrip() {
+        let dump = get_attestation_dump_test_data(&[1, 3]);
+        assert!(dump.records.iter().all(AttestationRecord::verify));
+
+        let json = dump.to_json().unwrap();
+        assert_eq!(AttestationDump::from_json(&json), Ok(dump.clone()));
+
+        // Consensus node exports hex with 0x prefix
+        let record = &dump.records[0];
+        let json = format!(
+            r#"[{{"pubkeys": ["0x{}"], "message": "0x{}", "signature": "0x{}"}}]"#,
+            hex::encode(record.pubkeys[0].0),
+            hex::encode(record.message),
+            hex::encode(record.signature.0)
+        );
+        assert_eq!(
+            AttestationDump::from_json(&json).unwrap().records,
+            vec![record.clone()]
+        );
+
+        let mut record = record.clone();
+        record.message[1] ^= 1;
+        assert!(!record.verify());
+    }
+
+    #[cfg(feature = "ssz")]
+    #[test]
+    fn attestation_dump_ssz_roundtrip() {
+        let dump = get_attestation_dump_test_data(&[2, 1, 4]);
+        let bytes = dump.to_ssz();
+        assert_eq!(AttestationDump::from_ssz(&bytes), Ok(dump));
+        assert!(AttestationDump::from_ssz(&bytes[1..]).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use std::fmt;
+
+/// Fixed set of public keys (eg. validators of the consensus round) signing messages
+/// as multi-signatures. Participating members are given as a bitmap, where bit `i`
+/// (least significant bit first within each byte) is set if member `i` signed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Committee {
+    public_keys: Vec<Bls12381G1PublicKey>,
+}
+
+impl Committee {
+    /// Creates committee from given public keys. Keys are validated once here,
+    /// so they do not need to be validated again when aggregating participants.
+    pub fn new(public_keys: Vec<Bls12381G1PublicKey>) -> Result<Self, CommitteeError> {
+        if public_keys.is_empty() {
+            return Err(CommitteeError::NoPublicKeysGiven);
+        }
+        for (idx, pk) in public_keys.iter().enumerate() {
+            if g1_affine_from_bytes(&pk.0).is_none() {
+                return Err(CommitteeError::InvalidPublicKey(idx));
+            }
+        }
+        Ok(Self { public_keys })
+    }
+
+    pub fn len(&self) -> usize {
+        self.public_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.public_keys.is_empty()
+    }
+
+    pub fn public_keys(&self) -> &[Bls12381G1PublicKey] {
+        &self.public_keys
+    }
+
+    /// Expected length of the participation bitmap in bytes
+    pub fn bitmap_length(&self) -> usize {
+        self.public_keys.len().div_ceil(8)
+    }
+
+    /// Returns public keys of the members marked in the bitmap
+    pub fn participants(&self, bitmap: &[u8]) -> Result<Vec<Bls12381G1PublicKey>, CommitteeError> {
+        if bitmap.len() != self.bitmap_length() {
+            return Err(CommitteeError::InvalidBitmapLength(bitmap.len()));
+        }
+        // Bits above committee size must not be set
+        if !self.public_keys.len().is_multiple_of(8)
+            && bitmap[bitmap.len() - 1] >> (self.public_keys.len() % 8) != 0
+        {
+            return Err(CommitteeError::InvalidBitmapLength(bitmap.len()));
+        }
+
+        Ok(self
+            .public_keys
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| bitmap[idx / 8] & (1 << (idx % 8)) != 0)
+            .map(|(_, pk)| *pk)
+            .collect())
+    }
+
+    /// Aggregates public keys of the members marked in the bitmap
+    pub fn aggregate_public_key(
+        &self,
+        bitmap: &[u8],
+    ) -> Result<Bls12381G1PublicKey, CommitteeError> {
+        let participants = self.participants(bitmap)?;
+        if participants.is_empty() {
+            return Err(CommitteeError::NoParticipants);
+        }
+        Bls12381G1PublicKey::aggregate_prevalidated(&participants).map_err(CommitteeError::BlsError)
+    }
+
+    /// Verifies multi-signature of the message by the members marked in the bitmap.
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub fn verify_bls12381_v1(
+        &self,
+        message: &[u8],
+        bitmap: &[u8],
+        signature: &Bls12381G2Signature,
+    ) -> bool {
+        match self.aggregate_public_key(bitmap) {
+            Ok(agg_pk) => verify_bls12381_v1(message, &agg_pk, signature),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Creates participation bitmap of the committee of given size with given members set
+pub fn participation_bitmap(committee_size: usize, participants: &[usize]) -> Vec<u8> {
+    let mut bitmap = vec![0u8; committee_size.div_ceil(8)];
+    for idx in participants {
+        bitmap[idx / 8] |= 1 << (idx % 8);
+    }
+    bitmap
+}
+
+/// Represents an error when creating committee or aggregating its participants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitteeError {
+    NoPublicKeysGiven,
+    // Public key at given index is not valid
+    InvalidPublicKey(usize),
+    // Bitmap length does not match committee size or bits above committee size are set
+    InvalidBitmapLength(usize),
+    NoParticipants,
+    BlsError(BlsError),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for CommitteeError {}
+
+impl fmt::Display for CommitteeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Generates committee of given size, where given percentage of members
+/// (evenly spread over the committee, rounded up so that any non-zero
+/// percentage gives at least one member) signed the message.
+pub fn get_committee_test_data(
+    committee_size: u32,
+    participation_percent: u32,
+    msg_size: usize,
+) -> (Committee, Vec<u8>, Vec<u8>, Bls12381G2Signature) {
+    let (sks, pks, msg, _sigs) = get_fast_aggregate_verify_test_data(committee_size, msg_size);
+    let committee = Committee::new(pks).unwrap();
+
+    let participants: Vec<usize> = (0..committee_size)
+        .filter(|i| {
+            ((i + 1) * participation_percent).div_ceil(100)
+                > (i * participation_percent).div_ceil(100)
+        })
+        .map(|i| i as usize)
+        .collect();
+    let bitmap = participation_bitmap(committee_size as usize, &participants);
+
+    let sigs: Vec<Bls12381G2Signature> = participants
+        .iter()
+        .map(|idx| sks[*idx].sign_v1(&msg))
+        .collect();
+    let sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+    (committee, bitmap, msg, sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committee_sign_and_verify() {
+        for percent in [1, 5, 50, 67, 100] {
+            let (committee, bitmap, msg, sig) = get_committee_test_data(20, percent, 32);
+            assert!(committee.verify_bls12381_v1(&msg, &bitmap, &sig));
+            assert!(!committee.verify_bls12381_v1(&[0u8; 32], &bitmap, &sig));
+        }
+    }
+
+    #[test]
+    fn committee_bitmap_must_match_signers() {
+        let (committee, bitmap, msg, sig) = get_committee_test_data(20, 50, 32);
+        assert_eq!(committee.participants(&bitmap).unwrap().len(), 10);
+
+        // Extra member marked
+        let mut extra = bitmap.clone();
+        let unset = (0..20)
+            .find(|i| bitmap[i / 8] & (1 << (i % 8)) == 0)
+            .unwrap();
+        extra[unset / 8] |= 1 << (unset % 8);
+        assert!(!committee.verify_bls12381_v1(&msg, &extra, &sig));
+
+        // Bits above committee size
+        let mut overflow = bitmap.clone();
+        overflow[2] |= 0x80;
+        assert_eq!(
+            committee.aggregate_public_key(&overflow),
+            Err(CommitteeError::InvalidBitmapLength(3))
+        );
+        assert_eq!(
+            committee.aggregate_public_key(&bitmap[..2]),
+            Err(CommitteeError::InvalidBitmapLength(2))
+        );
+        assert_eq!(
+            committee.aggregate_public_key(&[0u8; 3]),
+            Err(CommitteeError::NoParticipants)
+        );
+    }
+
+    #[test]
+    fn committee_rejects_invalid_keys() {
+        assert_eq!(
+            Committee::new(vec![]),
+            Err(CommitteeError::NoPublicKeysGiven)
+        );
+
+        let (_sks, mut pks, _msg, _sigs) = get_fast_aggregate_verify_test_data(4, 32);
+        pks[2] = Bls12381G1PublicKey([0xff; 48]);
+        assert_eq!(
+            Committee::new(pks),
+            Err(CommitteeError::InvalidPublicKey(2))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::*;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use digest09::generic_array::GenericArray;
+use digest09::{consts, BlockInput, FixedOutputDirty, Reset, Update};
+use sha2::Digest;
+
+/// SHA-256 implementing the digest 0.9 traits required by bls12_381 ExpandMsgXmd,
+/// delegating to sha2::Sha256
+#[derive(Clone, Default)]
+struct Sha256(sha2::Sha256);
+
+impl Update for Sha256 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        Digest::update(&mut self.0, data);
+    }
+}
+
+impl BlockInput for Sha256 {
+    type BlockSize = consts::U64;
+}
+
+impl FixedOutputDirty for Sha256 {
+    type OutputSize = consts::U32;
+
+    fn finalize_into_dirty(&mut self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        out.copy_from_slice(&self.0.finalize_reset());
+    }
+}
+
+impl Reset for Sha256 {
+    fn reset(&mut self) {
+        Digest::reset(&mut self.0);
+    }
+}
+
+/// Performs BLS12-381 G2 signature verification with the zkcrypto bls12_381 implementation,
+/// so that results of verify_bls12381_v1 (blst) can be cross-checked.
+/// Public key shall be in the G1 subgroup and not infinity, signature shall be in the G2 subgroup.
+pub fn reference_verify_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+) -> bool {
+    // Decompression checks that the point is in the subgroup
+    let pk = G1Affine::from_compressed(&public_key.0);
+    let sig = G2Affine::from_compressed(&signature.0);
+    if bool::from(pk.is_none() | sig.is_none()) {
+        return false;
+    }
+    let (pk, sig) = (pk.unwrap(), sig.unwrap());
+    if bool::from(pk.is_identity()) {
+        return false;
+    }
+
+    let msg_point = <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+        message,
+        BLS12381_CIPHERSITE_V1,
+    );
+    pairing(&pk, &G2Affine::from(msg_point)) == pairing(&G1Affine::generator(), &sig)
+}
+
+/// Performs BLS12-381 G1 signature verification (minimal signature size variant)
+/// with the zkcrypto bls12_381 implementation
+pub fn reference_verify_min_sig_bls12381_v1(
+    message: &[u8],
+    public_key: &min_sig::Bls12381G2PublicKey,
+    signature: &min_sig::Bls12381G1Signature,
+) -> bool {
+    let pk = G2Affine::from_compressed(&public_key.0);
+    let sig = G1Affine::from_compressed(&signature.0);
+    if bool::from(pk.is_none() | sig.is_none()) {
+        return false;
+    }
+    let (pk, sig) = (pk.unwrap(), sig.unwrap());
+    if bool::from(pk.is_identity()) {
+        return false;
+    }
+
+    let msg_point = <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+        message,
+        BLS12381G1_CIPHERSITE_V1,
+    );
+    pairing(&G1Affine::from(msg_point), &pk) == pairing(&sig, &G2Affine::generator())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn implementations_agree<V: Bls12381Variant>() {
+        let (_sks, pks, msgs, sigs) = V::get_aggregate_verify_test_data(4, 4, 32);
+        for ((pk, msg), sig) in pks.iter().zip(msgs.iter()).zip(sigs.iter()) {
+            assert!(V::reference_verify(msg, pk, sig));
+        }
+        assert!(!V::reference_verify(&msgs[1], &pks[0], &sigs[0]));
+
+        for case in NegativeTestCase::ALL {
+            let (pk, msg, sig) = get_negative_verify_test_data::<V>(case, 32);
+            assert_eq!(
+                V::reference_verify(&msg, &pk, &sig),
+                V::verify(&msg, &pk, &sig),
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn implementations_agree_min_pk() {
+        implementations_agree::<MinPk>();
+    }
+
+    #[test]
+    fn implementations_agree_min_sig() {
+        implementations_agree::<MinSig>();
+    }
+}
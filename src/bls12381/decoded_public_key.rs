@@ -0,0 +1,88 @@
+use super::*;
+use blst::min_pk::PublicKey;
+
+/// BLS12-381 G1 public key decoded and validated (on curve, not infinity, in the G1 subgroup)
+/// once, so that repeated verifications against the same key skip decompression
+/// and the subgroup check done by verify_bls12381_v1.
+pub struct DecodedG1PublicKey {
+    public_key: Bls12381G1PublicKey,
+    decoded: PublicKey,
+}
+
+impl DecodedG1PublicKey {
+    pub fn new(public_key: &Bls12381G1PublicKey) -> Result<Self, BlsError> {
+        let decoded = PublicKey::from_bytes(&public_key.0)?;
+        decoded.validate()?;
+        Ok(Self {
+            public_key: *public_key,
+            decoded,
+        })
+    }
+
+    pub fn public_key(&self) -> &Bls12381G1PublicKey {
+        &self.public_key
+    }
+
+    /// Performs BLS12-381 G2 signature verification, same as verify_bls12381_v1
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub fn verify(&self, message: &[u8], signature: &Bls12381G2Signature) -> bool {
+        match blst::min_pk::Signature::from_bytes(&signature.0) {
+            Ok(sig) => {
+                sig.verify(
+                    true,
+                    message,
+                    BLS12381_CIPHERSITE_V1,
+                    &[],
+                    &self.decoded,
+                    false,
+                ) == blst::BLST_ERROR::BLST_SUCCESS
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl TryFrom<&Bls12381G1PublicKey> for DecodedG1PublicKey {
+    type Error = BlsError;
+
+    fn try_from(public_key: &Bls12381G1PublicKey) -> Result<Self, Self::Error> {
+        Self::new(public_key)
+    }
+}
+
+pub fn get_decoded_verify_test_data(
+    msg_cnt: u32,
+    msg_size: usize,
+) -> (Bls12381G1PublicKey, Vec<Vec<u8>>, Vec<Bls12381G2Signature>) {
+    let (sks, pks, msgs, _sigs) = get_aggregate_verify_test_data(msg_cnt, msg_cnt, msg_size);
+
+    // All messages signed with the first key
+    let sigs: Vec<Bls12381G2Signature> = msgs.iter().map(|msg| sks[0].sign_v1(msg)).collect();
+
+    (pks[0], msgs, sigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoded_public_key_verify() {
+        let (pk, msgs, sigs) = get_decoded_verify_test_data(2, 32);
+        let decoded_pk = DecodedG1PublicKey::new(&pk).unwrap();
+        assert_eq!(decoded_pk.public_key(), &pk);
+
+        for (msg, sig) in msgs.iter().zip(sigs.iter()) {
+            assert!(decoded_pk.verify(msg, sig));
+            assert_eq!(
+                decoded_pk.verify(msg, sig),
+                verify_bls12381_v1(msg, &pk, sig)
+            );
+        }
+        assert!(!decoded_pk.verify(&msgs[1], &sigs[0]));
+
+        // Keys failing validation are rejected when decoded
+        assert!(DecodedG1PublicKey::new(&Bls12381G1PublicKey([0u8; 48])).is_err());
+        assert!(DecodedG1PublicKey::new(&Bls12381G1PublicKey(g1_non_subgroup_point(0))).is_err());
+    }
+}
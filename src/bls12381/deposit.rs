@@ -0,0 +1,124 @@
+use super::*;
+use sha2::{Digest, Sha256};
+
+/// Domain type of the deposit signatures
+/// see: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#domain-types
+pub const DOMAIN_DEPOSIT: [u8; 4] = [0x03, 0x00, 0x00, 0x00];
+
+/// Genesis fork version of the mainnet
+pub const MAINNET_GENESIS_FORK_VERSION: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+
+/// Amount of the full validator deposit (32 ETH) in Gwei
+pub const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+
+/// Deposit data without the signature, ie. the signed object
+/// see: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#depositmessage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositMessage {
+    pub public_key: Bls12381G1PublicKey,
+    pub withdrawal_credentials: [u8; 32],
+    /// Amount in Gwei
+    pub amount: u64,
+}
+
+impl DepositMessage {
+    /// SSZ hash tree root of the container (3 fields padded to 4 chunks)
+    pub fn hash_tree_root(&self) -> [u8; 32] {
+        // Bytes48 spans 2 chunks
+        let mut public_key_chunks = [0u8; 64];
+        public_key_chunks[..Bls12381G1PublicKey::LENGTH].copy_from_slice(&self.public_key.0);
+        let public_key_root = sha256(&public_key_chunks);
+
+        let mut amount_chunk = [0u8; 32];
+        amount_chunk[..8].copy_from_slice(&self.amount.to_le_bytes());
+
+        hash_pair(
+            &hash_pair(&public_key_root, &self.withdrawal_credentials),
+            &hash_pair(&amount_chunk, &[0u8; 32]),
+        )
+    }
+
+    /// Root of the signing data (object root and deposit domain), ie. the message which is signed
+    pub fn signing_root(&self, fork_version: [u8; 4]) -> [u8; 32] {
+        hash_pair(
+            &self.hash_tree_root(),
+            &compute_deposit_domain(fork_version),
+        )
+    }
+
+    pub fn sign(&self, sk: &Bls12381G1PrivateKey, fork_version: [u8; 4]) -> Bls12381G2Signature {
+        sk.sign_v1(&self.signing_root(fork_version))
+    }
+
+    /// Verifies deposit signature of the public key of the message
+    pub fn verify(&self, signature: &Bls12381G2Signature, fork_version: [u8; 4]) -> bool {
+        verify_bls12381_v1(
+            &self.signing_root(fork_version),
+            &self.public_key,
+            signature,
+        )
+    }
+}
+
+/// Deposit domain of given fork version. Deposits are valid across forks,
+/// so genesis validators root is always zero.
+/// see: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#compute_domain
+pub fn compute_deposit_domain(fork_version: [u8; 4]) -> [u8; 32] {
+    // ForkData root: current_version chunk and genesis_validators_root
+    let mut version_chunk = [0u8; 32];
+    version_chunk[..4].copy_from_slice(&fork_version);
+    let fork_data_root = hash_pair(&version_chunk, &[0u8; 32]);
+
+    let mut domain = [0u8; 32];
+    domain[..4].copy_from_slice(&DOMAIN_DEPOSIT);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mainnet_deposit_domain() {
+        assert_eq!(
+            hex::encode(compute_deposit_domain(MAINNET_GENESIS_FORK_VERSION)),
+            "03000000f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9"
+        );
+    }
+
+    #[test]
+    fn deposit_sign_and_verify() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let mut withdrawal_credentials = [0u8; 32];
+        withdrawal_credentials[0] = 0x01;
+        let deposit = DepositMessage {
+            public_key: sk.public_key(),
+            withdrawal_credentials,
+            amount: MAX_EFFECTIVE_BALANCE,
+        };
+
+        let signature = deposit.sign(&sk, MAINNET_GENESIS_FORK_VERSION);
+        assert!(deposit.verify(&signature, MAINNET_GENESIS_FORK_VERSION));
+        // Signature of another network
+        assert!(!deposit.verify(&signature, [0x00, 0x00, 0x10, 0x20]));
+
+        let other = DepositMessage {
+            amount: 1_000_000_000,
+            ..deposit.clone()
+        };
+        assert_ne!(other.hash_tree_root(), deposit.hash_tree_root());
+        assert!(!other.verify(&signature, MAINNET_GENESIS_FORK_VERSION));
+    }
+}
@@ -0,0 +1,220 @@
+use super::*;
+use blst::blst_fr;
+
+/// Contribution of a single dealer to the Pedersen (joint-Feldman) distributed key generation.
+/// Dealer shares its random secret with polynomial of degree `threshold - 1`, publishes
+/// commitments to the coefficients (as G1 points) and sends share `j` privately to participant `j`.
+/// Resulting group private key is the sum of dealers' secrets and is never known to anyone.
+pub struct DkgDealing {
+    pub dealer: u32,
+    /// Commitments to the polynomial coefficients, ie. their public keys
+    pub commitments: Vec<Bls12381G1PublicKey>,
+    /// Secret shares of participants 1..=participants
+    pub shares: Vec<Bls12381G1KeyShare>,
+}
+
+impl DkgDealing {
+    pub fn generate(
+        dealer: u32,
+        threshold: usize,
+        participants: usize,
+    ) -> Result<Self, ThresholdError> {
+        if threshold == 0 || threshold > participants || participants > u32::MAX as usize {
+            return Err(ThresholdError::InvalidThreshold(threshold, participants));
+        }
+
+        let coefficients = random_polynomial(random_fr(), threshold);
+        let commitments = coefficients
+            .iter()
+            .map(|coefficient| Ok(fr_to_private_key(coefficient)?.public_key()))
+            .collect::<Result<Vec<_>, ThresholdError>>()?;
+        let shares = (1..=participants as u32)
+            .map(|index| {
+                Ok(Bls12381G1KeyShare {
+                    index,
+                    private_key: fr_to_private_key(&evaluate_polynomial(&coefficients, index))?,
+                })
+            })
+            .collect::<Result<Vec<_>, ThresholdError>>()?;
+
+        Ok(Self {
+            dealer,
+            commitments,
+            shares,
+        })
+    }
+
+    /// Share to be sent to participant of given index
+    pub fn share(&self, index: u32) -> Option<&Bls12381G1KeyShare> {
+        self.shares.iter().find(|share| share.index == index)
+    }
+
+    /// Checks received share against dealer's commitments:
+    /// share * G1 == sum(C_k * index^k)
+    pub fn verify_share(&self, share: &Bls12381G1KeyShare) -> bool {
+        match evaluate_commitments(&self.commitments, share.index) {
+            Some(expected) => expected == share.public_key(),
+            None => false,
+        }
+    }
+}
+
+/// Evaluates committed polynomial at given index in the exponent, giving
+/// public key of the share of given index.
+pub fn evaluate_commitments(
+    commitments: &[Bls12381G1PublicKey],
+    index: u32,
+) -> Option<Bls12381G1PublicKey> {
+    if commitments.is_empty() || index == 0 {
+        return None;
+    }
+    let scalar = index.to_le_bytes();
+
+    // Horner's method, projective point at infinity
+    let mut result = blst::blst_p1::default();
+    for commitment in commitments.iter().rev() {
+        let commitment = g1_affine_from_bytes(&commitment.0)?;
+        unsafe {
+            let mut rp = blst::blst_p1::default();
+            blst::blst_p1_mult(&mut rp, &result, scalar.as_ptr(), 32);
+            blst::blst_p1_add_or_double_affine(&mut result, &rp, &commitment);
+        }
+    }
+
+    let mut bytes = [0u8; Bls12381G1PublicKey::LENGTH];
+    unsafe {
+        blst::blst_p1_compress(bytes.as_mut_ptr(), &result);
+    }
+    Some(Bls12381G1PublicKey(bytes))
+}
+
+/// Sums commitments of all dealings coefficient-wise. First of them is the group public key,
+/// the rest allows computing public key of any group key share (see evaluate_commitments).
+pub fn aggregate_dkg_commitments(
+    dealings: &[DkgDealing],
+) -> Result<Vec<Bls12381G1PublicKey>, ThresholdError> {
+    let threshold = match dealings.first() {
+        Some(dealing) => dealing.commitments.len(),
+        None => return Err(ThresholdError::NotEnoughShares(0)),
+    };
+    if let Some(dealing) = dealings
+        .iter()
+        .find(|dealing| dealing.commitments.len() != threshold)
+    {
+        return Err(ThresholdError::InvalidDealing(dealing.dealer));
+    }
+
+    (0..threshold)
+        .map(|k| {
+            let commitments: Vec<Bls12381G1PublicKey> = dealings
+                .iter()
+                .map(|dealing| dealing.commitments[k])
+                .collect();
+            Bls12381G1PublicKey::aggregate(&commitments).map_err(ThresholdError::BlsError)
+        })
+        .collect()
+}
+
+/// Computes group key share of participant of given index, ie. sum of shares
+/// received from all dealers.
+pub fn aggregate_dkg_key_share(
+    index: u32,
+    dealings: &[DkgDealing],
+) -> Result<Bls12381G1KeyShare, ThresholdError> {
+    if dealings.is_empty() {
+        return Err(ThresholdError::NotEnoughShares(0));
+    }
+    let mut key = blst_fr::default();
+    for dealing in dealings {
+        let share = dealing
+            .share(index)
+            .ok_or(ThresholdError::InvalidDealing(dealing.dealer))?;
+        key = fr_add(&key, &fr_from_private_key(&share.private_key));
+    }
+    Ok(Bls12381G1KeyShare {
+        index,
+        private_key: fr_to_private_key(&key)?,
+    })
+}
+
+/// Simulates DKG among given number of participants, each of them acting as a dealer.
+/// Returns group public key and group key shares of all participants.
+pub fn simulate_dkg(
+    threshold: usize,
+    participants: usize,
+) -> Result<(Bls12381G1PublicKey, Vec<Bls12381G1KeyShare>), ThresholdError> {
+    let dealings = (1..=participants as u32)
+        .map(|dealer| DkgDealing::generate(dealer, threshold, participants))
+        .collect::<Result<Vec<_>, ThresholdError>>()?;
+
+    for dealing in &dealings {
+        if !dealing
+            .shares
+            .iter()
+            .all(|share| dealing.verify_share(share))
+        {
+            return Err(ThresholdError::InvalidDealing(dealing.dealer));
+        }
+    }
+
+    let group_public_key = aggregate_dkg_commitments(&dealings)?[0];
+    let key_shares = (1..=participants as u32)
+        .map(|index| aggregate_dkg_key_share(index, &dealings))
+        .collect::<Result<Vec<_>, ThresholdError>>()?;
+    Ok((group_public_key, key_shares))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dkg_sign_and_verify() {
+        let (group_pk, key_shares) = simulate_dkg(3, 5).unwrap();
+        assert_eq!(key_shares.len(), 5);
+
+        let msg = b"message";
+        let sig_shares: Vec<Bls12381G2SignatureShare> = key_shares[1..]
+            .iter()
+            .map(|share| share.sign_v1(msg))
+            .collect();
+        let sig = combine_signature_shares(&sig_shares, 3).unwrap();
+        assert!(verify_bls12381_v1(msg, &group_pk, &sig));
+
+        let group_sk = reconstruct_private_key(&key_shares, 3).unwrap();
+        assert_eq!(group_sk.public_key(), group_pk);
+    }
+
+    #[test]
+    fn dkg_commitments() {
+        let dealings: Vec<DkgDealing> = (1..=4)
+            .map(|dealer| DkgDealing::generate(dealer, 2, 4).unwrap())
+            .collect();
+        let commitments = aggregate_dkg_commitments(&dealings).unwrap();
+        for index in 1..=4 {
+            let key_share = aggregate_dkg_key_share(index, &dealings).unwrap();
+            assert_eq!(
+                evaluate_commitments(&commitments, index),
+                Some(key_share.public_key())
+            );
+        }
+
+        // Share not matching dealer's commitments
+        let dealing = &dealings[0];
+        let share = Bls12381G1KeyShare {
+            index: 1,
+            private_key: Bls12381G1PrivateKey::from_bytes(
+                &dealing.shares[1].private_key.to_bytes(),
+            )
+            .unwrap(),
+        };
+        assert!(dealing.verify_share(&dealing.shares[0]));
+        assert!(!dealing.verify_share(&share));
+        assert!(!dealing.verify_share(&Bls12381G1KeyShare { index: 0, ..share }));
+
+        assert_eq!(
+            DkgDealing::generate(1, 5, 4).err(),
+            Some(ThresholdError::InvalidThreshold(5, 4))
+        );
+    }
+}
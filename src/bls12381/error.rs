@@ -0,0 +1,49 @@
+use blst::BLST_ERROR;
+use std::fmt;
+use std::string::String;
+
+/// Represents an error when parsing, validating or aggregating BLS keys and signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlsError {
+    InvalidHex(String),
+    InvalidLength(usize),
+    NoPublicKeysGiven,
+    NoSignatureGiven,
+    // Bytes are not a valid encoding of the point on the curve
+    InvalidPoint,
+    // Point is not in the expected subgroup
+    NotInSubgroup,
+    // Public key is the point at infinity
+    PointAtInfinity,
+    // Private key is zero
+    ZeroKey,
+    // Private key is not lower than the curve order
+    InvalidPrivateKey,
+    // Other error returned by underlying BLS library
+    Blst(String),
+}
+
+impl From<BLST_ERROR> for BlsError {
+    fn from(error: BLST_ERROR) -> Self {
+        match error {
+            BLST_ERROR::BLST_BAD_ENCODING | BLST_ERROR::BLST_POINT_NOT_ON_CURVE => {
+                Self::InvalidPoint
+            }
+            BLST_ERROR::BLST_POINT_NOT_IN_GROUP => Self::NotInSubgroup,
+            BLST_ERROR::BLST_PK_IS_INFINITY => Self::PointAtInfinity,
+            _ => {
+                let err_msg = format!("{:?}", error);
+                Self::Blst(err_msg)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for BlsError {}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -0,0 +1,256 @@
+use super::*;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Fixtures replayed instead of generating test data
+static REPLAYED_FIXTURES: OnceCell<TestFixtures> = OnceCell::new();
+
+/// Fixtures recorded from the generated test data
+static RECORDED_FIXTURES: OnceCell<Mutex<TestFixtures>> = OnceCell::new();
+
+/// Generated keys, messages and signatures, which can be saved and replayed later
+/// (or on another machine) to guarantee identical inputs.
+/// Public keys are derived from the private keys when replaying.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestFixtures {
+    pub variant: String,
+    pub sets: Vec<TestFixtureSet>,
+}
+
+/// Result of a single generator call, bytes are hex encoded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestFixtureSet {
+    pub kind: TestDataKind,
+    pub cnt: u32,
+    pub msg_cnt: u32,
+    pub msg_size: usize,
+    pub private_keys: Vec<String>,
+    pub messages: Vec<String>,
+    pub signatures: Vec<String>,
+}
+
+impl TestFixtures {
+    pub fn new<V: Bls12381Variant>() -> Self {
+        Self {
+            variant: V::NAME.to_string(),
+            sets: vec![],
+        }
+    }
+
+    pub fn find(
+        &self,
+        kind: TestDataKind,
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+    ) -> Option<&TestFixtureSet> {
+        self.sets.iter().find(|set| {
+            set.kind == kind && set.cnt == cnt && set.msg_cnt == msg_cnt && set.msg_size == msg_size
+        })
+    }
+
+    /// Adds the set, unless set of the same parameters is already there
+    pub fn add(&mut self, set: TestFixtureSet) {
+        if self
+            .find(set.kind, set.cnt, set.msg_cnt, set.msg_size)
+            .is_none()
+        {
+            self.sets.push(set);
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, FixturesError> {
+        serde_json::from_str(json).map_err(|err| FixturesError::Json(err.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, FixturesError> {
+        serde_json::to_string_pretty(self).map_err(|err| FixturesError::Json(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, FixturesError> {
+        let json =
+            std::fs::read_to_string(path).map_err(|err| FixturesError::Io(err.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), FixturesError> {
+        std::fs::write(path, self.to_json()?).map_err(|err| FixturesError::Io(err.to_string()))
+    }
+}
+
+impl TestFixtureSet {
+    pub fn from_test_data<V: Bls12381Variant>(
+        kind: TestDataKind,
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+        data: &TestData<V, Vec<Vec<u8>>>,
+    ) -> Self {
+        let (sks, _pks, msgs, sigs) = data;
+        Self {
+            kind,
+            cnt,
+            msg_cnt,
+            msg_size,
+            private_keys: sks
+                .iter()
+                .map(|sk| hex::encode(V::private_key_to_bytes(sk)))
+                .collect(),
+            messages: msgs.iter().map(hex::encode).collect(),
+            signatures: sigs
+                .iter()
+                .map(|sig| hex::encode(V::signature_to_bytes(sig)))
+                .collect(),
+        }
+    }
+
+    pub fn to_test_data<V: Bls12381Variant>(
+        &self,
+    ) -> Result<TestData<V, Vec<Vec<u8>>>, FixturesError> {
+        let sks = self
+            .private_keys
+            .iter()
+            .map(|sk| V::private_key_from_bytes(&decode_hex(sk)?).map_err(FixturesError::BlsError))
+            .collect::<Result<Vec<_>, FixturesError>>()?;
+        let msgs = self
+            .messages
+            .iter()
+            .map(|msg| decode_hex(msg))
+            .collect::<Result<Vec<_>, FixturesError>>()?;
+        let sigs = self
+            .signatures
+            .iter()
+            .map(|sig| V::signature_from_bytes(&decode_hex(sig)?).map_err(FixturesError::BlsError))
+            .collect::<Result<Vec<_>, FixturesError>>()?;
+        let pks = sks.iter().map(V::public_key).collect();
+        Ok((sks, pks, msgs, sigs))
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, FixturesError> {
+    hex::decode(s).map_err(|_| FixturesError::InvalidHex(s.to_string()))
+}
+
+/// Sets fixtures replayed by the test data generators, returns false if they were already set
+pub fn set_replayed_fixtures(fixtures: TestFixtures) -> bool {
+    REPLAYED_FIXTURES.set(fixtures).is_ok()
+}
+
+/// Starts recording test data generated by the test data generators
+pub fn record_fixtures<V: Bls12381Variant>() -> bool {
+    RECORDED_FIXTURES
+        .set(Mutex::new(TestFixtures::new::<V>()))
+        .is_ok()
+}
+
+pub fn recorded_fixtures() -> Option<TestFixtures> {
+    RECORDED_FIXTURES
+        .get()
+        .map(|fixtures| fixtures.lock().unwrap().clone())
+}
+
+/// Replays test data from the fixtures if they were set, otherwise generates it
+/// (and records it if recording was started).
+/// Panics if replayed fixtures do not contain the requested data, as inputs would differ.
+pub fn replay_or_generate<V: Bls12381Variant>(
+    kind: TestDataKind,
+    cnt: u32,
+    msg_cnt: u32,
+    msg_size: usize,
+    generate: impl FnOnce() -> TestData<V, Vec<Vec<u8>>>,
+) -> TestData<V, Vec<Vec<u8>>> {
+    if let Some(fixtures) = REPLAYED_FIXTURES.get() {
+        if fixtures.variant != V::NAME {
+            panic!(
+                "{}",
+                FixturesError::VariantMismatch(fixtures.variant.clone())
+            );
+        }
+        return match fixtures.find(kind, cnt, msg_cnt, msg_size) {
+            Some(set) => set.to_test_data::<V>().unwrap(),
+            None => panic!(
+                "{}",
+                FixturesError::NotFound(format!(
+                    "{:?} cnt {} msg_cnt {} msg_size {}",
+                    kind, cnt, msg_cnt, msg_size
+                ))
+            ),
+        };
+    }
+
+    let data = generate();
+    if let Some(fixtures) = RECORDED_FIXTURES.get() {
+        fixtures
+            .lock()
+            .unwrap()
+            .add(TestFixtureSet::from_test_data::<V>(
+                kind, cnt, msg_cnt, msg_size, &data,
+            ));
+    }
+    data
+}
+
+/// Represents an error when loading or replaying fixtures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixturesError {
+    Io(String),
+    Json(String),
+    InvalidHex(String),
+    // Fixtures were generated for another variant
+    VariantMismatch(String),
+    // Fixtures do not contain the requested set
+    NotFound(String),
+    BlsError(BlsError),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for FixturesError {}
+
+impl fmt::Display for FixturesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_round_trip() {
+        let data = MinSig::get_aggregate_verify_test_data(4, 2, 16);
+        let mut fixtures = TestFixtures::new::<MinSig>();
+        let set = TestFixtureSet::from_test_data::<MinSig>(
+            TestDataKind::AggregateVerify,
+            4,
+            2,
+            16,
+            &data,
+        );
+        fixtures.add(set.clone());
+        fixtures.add(set);
+        assert_eq!(fixtures.sets.len(), 1);
+
+        let fixtures = TestFixtures::from_json(&fixtures.to_json().unwrap()).unwrap();
+        assert_eq!(fixtures.variant, "min-sig");
+        assert!(fixtures
+            .find(TestDataKind::FastAggregateVerify, 4, 2, 16)
+            .is_none());
+        let (_sks, pks, msgs, sigs) = fixtures
+            .find(TestDataKind::AggregateVerify, 4, 2, 16)
+            .unwrap()
+            .to_test_data::<MinSig>()
+            .unwrap();
+        assert_eq!((&pks, &msgs, &sigs), (&data.1, &data.2, &data.3));
+
+        let mut set = fixtures.sets[0].clone();
+        set.messages[0] = "xyz".to_string();
+        assert_eq!(
+            set.to_test_data::<MinSig>().err(),
+            Some(FixturesError::InvalidHex("xyz".to_string()))
+        );
+    }
+}
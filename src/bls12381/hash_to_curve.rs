@@ -0,0 +1,289 @@
+use crate::HASH_READ_CHUNK_SIZE;
+use blst::{blst_fp, blst_fp2, blst_p1, blst_p2};
+use sha2::{Digest, Sha256};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::io::{self, Read};
+
+/// Length of the uniform bytes required to hash to G2 (count = 2, m = 2, L = 64)
+/// see: https://datatracker.ietf.org/doc/html/rfc9380#name-hash_to_field-implementatio
+pub const HASH_TO_G2_UNIFORM_BYTES_LENGTH: usize = 256;
+
+/// Length of the uniform bytes required to hash to G1 (count = 2, m = 1, L = 64)
+pub const HASH_TO_G1_UNIFORM_BYTES_LENGTH: usize = 128;
+
+// SHA-256 input block size
+const SHA256_BLOCK_LENGTH: usize = 64;
+
+/// Same parameters as BLS12381_CIPHERSITE_V1 but message is expanded with SHAKE-256
+/// (expand_message_xof) instead of SHA-256 (expand_message_xmd)
+pub const BLS12381G2_XOF_CIPHERSITE_V1: &[u8] = b"BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_POP_";
+
+/// Expands message into `len` uniformly random bytes using SHA-256
+/// see: https://datatracker.ietf.org/doc/html/rfc9380#name-expand_message_xmd
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len];
+    unsafe {
+        blst::blst_expand_message_xmd(
+            out.as_mut_ptr(),
+            out.len(),
+            msg.as_ptr(),
+            msg.len(),
+            dst.as_ptr(),
+            dst.len(),
+        );
+    }
+    out
+}
+
+/// Expands message read in chunks into `len` uniformly random bytes using SHA-256,
+/// so that message size is not limited by memory. Gives the same result as expand_message_xmd.
+pub fn expand_message_xmd_reader<R: Read>(
+    mut reader: R,
+    dst: &[u8],
+    len: usize,
+) -> io::Result<Vec<u8>> {
+    let ell = len.div_ceil(32);
+    assert!(
+        ell <= u8::MAX as usize && len <= u16::MAX as usize,
+        "len {} too big",
+        len
+    );
+    assert!(
+        dst.len() <= u8::MAX as usize,
+        "dst length {} too big",
+        dst.len()
+    );
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut hasher = Sha256::new();
+    Digest::update(&mut hasher, [0u8; SHA256_BLOCK_LENGTH]);
+    let mut buf = vec![0u8; HASH_READ_CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buf)? {
+            0 => break,
+            read => Digest::update(&mut hasher, &buf[..read]),
+        }
+    }
+    Digest::update(&mut hasher, (len as u16).to_be_bytes());
+    Digest::update(&mut hasher, [0u8]);
+    Digest::update(&mut hasher, &dst_prime);
+    let b_0 = hasher.finalize();
+
+    let mut out = vec![0u8; len];
+    expand_message_xmd_blocks(&b_0.into(), &dst_prime, &mut out);
+    Ok(out)
+}
+
+/// Fills `out` with blocks b_1, b_2, ... of expand_message_xmd (last one truncated)
+fn expand_message_xmd_blocks(b_0: &[u8; 32], dst_prime: &[u8], out: &mut [u8]) {
+    let mut b_i = [0u8; 32];
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        // b_1 = H(b_0 || 1 || DST_prime), b_i = H(b_0 xor b_(i-1) || i || DST_prime)
+        let mut xored = [0u8; 32];
+        for (x, (a, b)) in xored.iter_mut().zip(b_0.iter().zip(b_i.iter())) {
+            *x = a ^ b;
+        }
+        let mut hasher = Sha256::new();
+        Digest::update(&mut hasher, xored);
+        Digest::update(&mut hasher, [i as u8 + 1]);
+        Digest::update(&mut hasher, dst_prime);
+        b_i = hasher.finalize().into();
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+}
+
+/// Hashes multiple messages to G2, giving the same points as hash_to_g2 of each of them.
+/// DST_prime and SHA-256 state after the zero pad block are computed once for all
+/// the messages and the uniform bytes buffer is reused.
+pub fn hash_to_g2_batch<M: AsRef<[u8]>>(msgs: &[M], dst: &[u8]) -> Vec<blst_p2> {
+    assert!(
+        dst.len() <= u8::MAX as usize,
+        "dst length {} too big",
+        dst.len()
+    );
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut padded = Sha256::new();
+    Digest::update(&mut padded, [0u8; SHA256_BLOCK_LENGTH]);
+
+    let mut uniform_bytes = [0u8; HASH_TO_G2_UNIFORM_BYTES_LENGTH];
+    msgs.iter()
+        .map(|msg| {
+            let mut hasher = padded.clone();
+            Digest::update(&mut hasher, msg.as_ref());
+            Digest::update(&mut hasher, (uniform_bytes.len() as u16).to_be_bytes());
+            Digest::update(&mut hasher, [0u8]);
+            Digest::update(&mut hasher, &dst_prime);
+            let b_0: [u8; 32] = hasher.finalize().into();
+
+            expand_message_xmd_blocks(&b_0, &dst_prime, &mut uniform_bytes);
+            map_to_g2(&hash_to_field_fp2(&uniform_bytes))
+        })
+        .collect()
+}
+
+/// Expands message into `len` uniformly random bytes using SHAKE-256
+/// see: https://datatracker.ietf.org/doc/html/rfc9380#name-expand_message_xof
+pub fn expand_message_xof(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    assert!(len <= u16::MAX as usize, "len {} too big", len);
+    assert!(
+        dst.len() <= u8::MAX as usize,
+        "dst length {} too big",
+        dst.len()
+    );
+
+    let mut hasher = Shake256::default();
+    hasher.update(msg);
+    hasher.update(&(len as u16).to_be_bytes());
+    hasher.update(dst);
+    hasher.update(&[dst.len() as u8]);
+
+    let mut out = vec![0u8; len];
+    XofReader::read(&mut hasher.finalize_xof(), &mut out);
+    out
+}
+
+/// Hashes message to G2 using expand_message_xof instead of expand_message_xmd
+pub fn hash_to_g2_xof(msg: &[u8], dst: &[u8]) -> blst_p2 {
+    let uniform_bytes = expand_message_xof(msg, dst, HASH_TO_G2_UNIFORM_BYTES_LENGTH);
+    map_to_g2(&hash_to_field_fp2(&uniform_bytes))
+}
+
+/// Hashes message read in chunks to G2, giving the same point as hash_to_g2
+/// (expand_message_xmd is computed incrementally, so message size is not limited by memory)
+pub fn hash_to_g2_reader<R: Read>(reader: R, dst: &[u8]) -> io::Result<blst_p2> {
+    let uniform_bytes = expand_message_xmd_reader(reader, dst, HASH_TO_G2_UNIFORM_BYTES_LENGTH)?;
+    Ok(map_to_g2(&hash_to_field_fp2(&uniform_bytes)))
+}
+
+/// Hashes message read in chunks to G1, giving the same point as hash_to_g1
+pub fn hash_to_g1_reader<R: Read>(reader: R, dst: &[u8]) -> io::Result<blst_p1> {
+    let uniform_bytes = expand_message_xmd_reader(reader, dst, HASH_TO_G1_UNIFORM_BYTES_LENGTH)?;
+    let u0 = fp_from_uniform_bytes(&uniform_bytes[..64]);
+    let u1 = fp_from_uniform_bytes(&uniform_bytes[64..]);
+    let mut q = blst_p1::default();
+    unsafe {
+        blst::blst_map_to_g1(&mut q, &u0, &u1);
+    }
+    Ok(q)
+}
+
+/// Reduces 64-byte big-endian integer modulo p
+fn fp_from_uniform_bytes(bytes: &[u8]) -> blst_fp {
+    // Split into hi (16 bytes) and lo (48 bytes), so that value = hi * 2^384 + lo
+    let mut hi_bytes = [0u8; 48];
+    hi_bytes[32..].copy_from_slice(&bytes[..16]);
+
+    let mut two_383_bytes = [0u8; 48];
+    two_383_bytes[0] = 0x80;
+
+    let mut hi = blst_fp::default();
+    let mut lo = blst_fp::default();
+    let mut two_384 = blst_fp::default();
+    let mut ret = blst_fp::default();
+    unsafe {
+        blst::blst_fp_from_bendian(&mut hi, hi_bytes.as_ptr());
+        blst::blst_fp_from_bendian(&mut lo, bytes[16..].as_ptr());
+        blst::blst_fp_from_bendian(&mut two_384, two_383_bytes.as_ptr());
+        let two_384_ptr: *mut blst_fp = &mut two_384;
+        blst::blst_fp_add(two_384_ptr, two_384_ptr, two_384_ptr);
+
+        blst::blst_fp_mul(&mut ret, &hi, &two_384);
+        let ret_ptr: *mut blst_fp = &mut ret;
+        blst::blst_fp_add(ret_ptr, ret_ptr, &lo);
+    }
+    ret
+}
+
+/// Constructs two Fp2 field elements from uniform bytes
+/// see: https://datatracker.ietf.org/doc/html/rfc9380#name-hash_to_field-implementatio
+pub fn hash_to_field_fp2(uniform_bytes: &[u8]) -> [blst_fp2; 2] {
+    assert_eq!(uniform_bytes.len(), HASH_TO_G2_UNIFORM_BYTES_LENGTH);
+
+    let mut u = [blst_fp2::default(); 2];
+    for (i, chunk) in uniform_bytes.chunks(128).enumerate() {
+        u[i].fp[0] = fp_from_uniform_bytes(&chunk[..64]);
+        u[i].fp[1] = fp_from_uniform_bytes(&chunk[64..]);
+    }
+    u
+}
+
+/// Maps two field elements to G2 (SSWU map of each of them, addition and cofactor clearing)
+pub fn map_to_g2(u: &[blst_fp2; 2]) -> blst_p2 {
+    let mut q = blst_p2::default();
+    unsafe {
+        blst::blst_map_to_g2(&mut q, &u[0], &u[1]);
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls12381::*;
+
+    #[test]
+    fn hash_to_g2_phases_match_hash_to_g2() {
+        let msg = b"abc";
+
+        let uniform_bytes =
+            expand_message_xmd(msg, BLS12381_CIPHERSITE_V1, HASH_TO_G2_UNIFORM_BYTES_LENGTH);
+        let u = hash_to_field_fp2(&uniform_bytes);
+        let q = map_to_g2(&u);
+
+        let expected = hash_to_g2(msg);
+        unsafe {
+            assert!(blst::blst_p2_is_equal(&q, &expected));
+        }
+    }
+
+    #[test]
+    fn hash_to_point_reader_matches_hash_to_point() {
+        for size in [0, 1, 100, 2 * HASH_READ_CHUNK_SIZE + 3] {
+            let msg = vec![(size % u8::MAX as usize) as u8; size];
+
+            assert_eq!(
+                expand_message_xmd_reader(msg.as_slice(), BLS12381_CIPHERSITE_V1, 300).unwrap(),
+                expand_message_xmd(&msg, BLS12381_CIPHERSITE_V1, 300)
+            );
+
+            let q2 = hash_to_g2_reader(msg.as_slice(), BLS12381_CIPHERSITE_V1).unwrap();
+            let q1 = hash_to_g1_reader(msg.as_slice(), BLS12381G1_CIPHERSITE_V1).unwrap();
+            let expected2 = hash_to_g2(&msg);
+            let expected1 = hash_to_g1(&msg);
+            unsafe {
+                assert!(blst::blst_p2_is_equal(&q2, &expected2));
+                assert!(blst::blst_p1_is_equal(&q1, &expected1));
+            }
+        }
+    }
+
+    #[test]
+    fn hash_to_g2_batch_matches_hash_to_g2() {
+        let msgs: Vec<Vec<u8>> = [0, 1, 32, 100]
+            .iter()
+            .map(|size| vec![7u8; *size])
+            .collect();
+
+        let points = hash_to_g2_batch(&msgs, BLS12381_CIPHERSITE_V1);
+        assert_eq!(points.len(), msgs.len());
+        for (q, msg) in points.iter().zip(msgs.iter()) {
+            unsafe {
+                assert!(blst::blst_p2_is_equal(q, &hash_to_g2(msg)));
+            }
+        }
+    }
+
+    #[test]
+    fn expand_message_xof_test_vector() {
+        // RFC 9380, Appendix K.6, expand_message_xof(SHAKE256), msg = "abc"
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
+        let uniform_bytes = expand_message_xof(b"abc", dst, 0x20);
+
+        assert_eq!(
+            hex::encode(uniform_bytes),
+            "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07"
+        );
+    }
+}
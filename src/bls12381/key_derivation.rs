@@ -0,0 +1,239 @@
+use super::*;
+use bip39::Mnemonic;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Minimal seed length required by EIP-2333
+pub const MIN_SEED_LENGTH: usize = 32;
+
+const HKDF_MOD_R_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+// ceil((3 * ceil(log2(r))) / 16)
+const HKDF_MOD_R_L: usize = 48;
+
+/// Derives EIP-2333 master private key from the seed.
+/// More details: https://eips.ethereum.org/EIPS/eip-2333
+pub fn derive_master_private_key(seed: &[u8]) -> Result<Bls12381G1PrivateKey, KeyDerivationError> {
+    if seed.len() < MIN_SEED_LENGTH {
+        return Err(KeyDerivationError::SeedTooShort(seed.len()));
+    }
+    hkdf_mod_r(seed, &[])
+}
+
+/// Converts BIP-39 mnemonic (English wordlist) and passphrase into 64-byte seed.
+/// More details: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<[u8; 64], KeyDerivationError> {
+    let mnemonic = Mnemonic::parse(mnemonic)
+        .map_err(|err| KeyDerivationError::InvalidMnemonic(err.to_string()))?;
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+/// Derives EIP-2333 master private key from BIP-39 mnemonic and passphrase
+pub fn master_private_key_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Bls12381G1PrivateKey, KeyDerivationError> {
+    let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+    derive_master_private_key(&seed)
+}
+
+/// Derives EIP-2333 child private key of given index
+pub fn derive_child_private_key(
+    parent: &Bls12381G1PrivateKey,
+    index: u32,
+) -> Result<Bls12381G1PrivateKey, KeyDerivationError> {
+    let lamport_pk = parent_private_key_to_lamport_public_key(parent, index)?;
+    hkdf_mod_r(&lamport_pk, &[])
+}
+
+/// Derives private key of given path, eg. "m/12381/3600/0/0/0" (EIP-2334),
+/// where "m" stands for given master key.
+pub fn derive_private_key_from_path(
+    master: &Bls12381G1PrivateKey,
+    path: &str,
+) -> Result<Bls12381G1PrivateKey, KeyDerivationError> {
+    let indexes = parse_derivation_path(path)?;
+    let mut key = Bls12381G1PrivateKey::from_bytes(&master.to_bytes())
+        .map_err(KeyDerivationError::BlsError)?;
+    for index in indexes {
+        key = derive_child_private_key(&key, index)?;
+    }
+    Ok(key)
+}
+
+/// Parses derivation path, eg. "m/12381/3600/0/0/0", into child indexes
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, KeyDerivationError> {
+    let mut nodes = path.trim().split('/');
+    if nodes.next() != Some("m") {
+        return Err(KeyDerivationError::InvalidPath(path.to_string()));
+    }
+    nodes
+        .map(|node| {
+            node.parse::<u32>()
+                .map_err(|_| KeyDerivationError::InvalidPath(path.to_string()))
+        })
+        .collect()
+}
+
+fn parent_private_key_to_lamport_public_key(
+    parent: &Bls12381G1PrivateKey,
+    index: u32,
+) -> Result<[u8; 32], KeyDerivationError> {
+    let salt = index.to_be_bytes();
+    let ikm = parent.to_bytes();
+    let not_ikm: Vec<u8> = ikm.iter().map(|b| !b).collect();
+
+    let mut lamport_pk = Sha256::new();
+    for ikm in [&ikm, &not_ikm] {
+        let lamport_sk = ikm_to_lamport_private_key(ikm, &salt)?;
+        for chunk in lamport_sk.chunks(32) {
+            lamport_pk.update(Sha256::digest(chunk));
+        }
+    }
+    Ok(lamport_pk.finalize().into())
+}
+
+// 255 chunks of 32 bytes
+fn ikm_to_lamport_private_key(ikm: &[u8], salt: &[u8]) -> Result<Vec<u8>, KeyDerivationError> {
+    let mut okm = vec![0u8; 255 * 32];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .map_err(|err| KeyDerivationError::Hkdf(err.to_string()))?;
+    Ok(okm)
+}
+
+/// HKDF_mod_r as defined in EIP-2333, ie. KeyGen of the BLS signature draft
+fn hkdf_mod_r(ikm: &[u8], key_info: &[u8]) -> Result<Bls12381G1PrivateKey, KeyDerivationError> {
+    let mut ikm = ikm.to_vec();
+    ikm.push(0);
+    let mut info = key_info.to_vec();
+    info.extend_from_slice(&(HKDF_MOD_R_L as u16).to_be_bytes());
+
+    let mut salt = HKDF_MOD_R_SALT.to_vec();
+    loop {
+        salt = Sha256::digest(&salt).to_vec();
+        let mut okm = [0u8; HKDF_MOD_R_L];
+        Hkdf::<Sha256>::new(Some(&salt), &ikm)
+            .expand(&info, &mut okm)
+            .map_err(|err| KeyDerivationError::Hkdf(err.to_string()))?;
+
+        let mut scalar = blst::blst_scalar::default();
+        // Reduces modulo r, returns false if the result is zero
+        if unsafe { blst::blst_scalar_from_be_bytes(&mut scalar, okm.as_ptr(), okm.len()) } {
+            let mut bytes = [0u8; Bls12381G1PrivateKey::LENGTH];
+            unsafe {
+                blst::blst_bendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+            }
+            return Bls12381G1PrivateKey::from_bytes(&bytes).map_err(KeyDerivationError::BlsError);
+        }
+    }
+}
+
+/// Represents an error when deriving private key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyDerivationError {
+    InvalidMnemonic(String),
+    SeedTooShort(usize),
+    // Path must be of form "m/<index>/<index>/..."
+    InvalidPath(String),
+    Hkdf(String),
+    BlsError(BlsError),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for KeyDerivationError {}
+
+impl fmt::Display for KeyDerivationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // Test case 0 from EIP-2333
+    #[test]
+    fn master_key_from_mnemonic_test_vector() {
+        let seed = mnemonic_to_seed(TEST_MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        // 6083874454709270928345386274498605044986640685124978867557563392430687146096
+        let master_sk = master_private_key_from_mnemonic(TEST_MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(
+            master_sk.to_hex_secret(),
+            "0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070"
+        );
+        assert_eq!(derive_master_private_key(&seed).unwrap(), master_sk);
+    }
+
+    #[test]
+    fn child_key_test_vectors() {
+        // Test case 0 from EIP-2333
+        let master_sk = master_private_key_from_mnemonic(TEST_MNEMONIC, "TREZOR").unwrap();
+        // 20397789859736650942317412262472558107875392172444076792671091975210932703118
+        assert_eq!(
+            derive_child_private_key(&master_sk, 0)
+                .unwrap()
+                .to_hex_secret(),
+            "2d18bd6c14e6d15bf8b5085c9b74f3daae3b03cc2014770a599d8c1539e50f8e"
+        );
+        assert!(
+            derive_private_key_from_path(&master_sk, "m/0").unwrap()
+                == derive_child_private_key(&master_sk, 0).unwrap()
+        );
+        assert!(derive_private_key_from_path(&master_sk, "m").unwrap() == master_sk);
+        assert_eq!(
+            derive_private_key_from_path(&master_sk, "m/12381/3600/0/0/0")
+                .unwrap()
+                .to_hex_secret(),
+            "032e6c3c7359223e127e9479afc521c4342f8903bc29ae01b671bcbcc98be0f6"
+        );
+
+        // Test case 1 from EIP-2333
+        let seed = hex::decode("3141592653589793238462643383279502884197169399375105820974944592")
+            .unwrap();
+        let master_sk = derive_master_private_key(&seed).unwrap();
+        // 25457201688850691947727629385191704516744796114925897962676248250929345014287
+        assert_eq!(
+            derive_child_private_key(&master_sk, 3141592653)
+                .unwrap()
+                .to_hex_secret(),
+            "384843fad5f3d777ea39de3e47a8f999ae91f89e42bffa993d91d9782d152a0f"
+        );
+    }
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(
+            parse_derivation_path("m/12381/3600/0/0/0"),
+            Ok(vec![12381, 3600, 0, 0, 0])
+        );
+        assert_eq!(parse_derivation_path("m"), Ok(vec![]));
+        for path in ["", "12381/0", "m/", "m/-1", "m/4294967296", "m/0'"] {
+            assert_eq!(
+                parse_derivation_path(path),
+                Err(KeyDerivationError::InvalidPath(path.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_mnemonic_and_seed() {
+        // Checksum word does not match
+        assert!(matches!(
+            mnemonic_to_seed(&TEST_MNEMONIC.replace("about", "abandon"), ""),
+            Err(KeyDerivationError::InvalidMnemonic(_))
+        ));
+        assert_eq!(
+            derive_master_private_key(&[1u8; 31]).err(),
+            Some(KeyDerivationError::SeedTooShort(31))
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use super::*;
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::Path;
+use subtle::ConstantTimeEq;
+use unicode_normalization::UnicodeNormalization;
+
+/// EIP-2335 keystore version
+pub const KEYSTORE_VERSION: u32 = 4;
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+/// Key derivation function used to derive the decryption key from the password.
+/// Default parameters are those recommended by EIP-2335.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeystoreKdf {
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl KeystoreKdf {
+    pub fn scrypt() -> Self {
+        Self::Scrypt {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    pub fn pbkdf2() -> Self {
+        Self::Pbkdf2 { c: 262144 }
+    }
+}
+
+impl Default for KeystoreKdf {
+    fn default() -> Self {
+        Self::scrypt()
+    }
+}
+
+/// EIP-2335 encrypted keystore of the BLS12-381 private key.
+/// More details: https://eips.ethereum.org/EIPS/eip-2335
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub crypto: KeystoreCrypto,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub pubkey: String,
+    pub path: String,
+    pub uuid: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub kdf: KeystoreModule<serde_json::Value>,
+    pub checksum: KeystoreModule<ChecksumParams>,
+    pub cipher: KeystoreModule<CipherParams>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreModule<P> {
+    pub function: String,
+    pub params: P,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u32,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Pbkdf2Params {
+    dklen: usize,
+    c: u32,
+    prf: String,
+    salt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecksumParams {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+impl Keystore {
+    /// Encrypts the private key with the password using given key derivation function.
+    /// Salt, IV and UUID are random.
+    pub fn encrypt(
+        private_key: &Bls12381G1PrivateKey,
+        password: &str,
+        kdf: KeystoreKdf,
+        path: &str,
+    ) -> Result<Self, KeystoreError> {
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut uuid = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(&mut uuid);
+
+        let kdf_module = match kdf {
+            KeystoreKdf::Scrypt { log_n, r, p } => KeystoreModule {
+                function: "scrypt".to_string(),
+                params: to_json_value(&ScryptParams {
+                    dklen: 32,
+                    n: 1 << log_n,
+                    r,
+                    p,
+                    salt: hex::encode(salt),
+                })?,
+                message: String::new(),
+            },
+            KeystoreKdf::Pbkdf2 { c } => KeystoreModule {
+                function: "pbkdf2".to_string(),
+                params: to_json_value(&Pbkdf2Params {
+                    dklen: 32,
+                    c,
+                    prf: "hmac-sha256".to_string(),
+                    salt: hex::encode(salt),
+                })?,
+                message: String::new(),
+            },
+        };
+        let decryption_key = derive_key(&kdf_module, password)?;
+
+        let mut cipher_message = private_key.to_bytes();
+        Aes128Ctr::new(decryption_key[..16].into(), iv[..].into())
+            .apply_keystream(&mut cipher_message);
+
+        Ok(Self {
+            crypto: KeystoreCrypto {
+                kdf: kdf_module,
+                checksum: KeystoreModule {
+                    function: "sha256".to_string(),
+                    params: ChecksumParams {},
+                    message: hex::encode(checksum(&decryption_key, &cipher_message)),
+                },
+                cipher: KeystoreModule {
+                    function: "aes-128-ctr".to_string(),
+                    params: CipherParams {
+                        iv: hex::encode(iv),
+                    },
+                    message: hex::encode(cipher_message),
+                },
+            },
+            description: String::new(),
+            pubkey: private_key.public_key().to_string(),
+            path: path.to_string(),
+            uuid: format_uuid_v4(uuid),
+            version: KEYSTORE_VERSION,
+        })
+    }
+
+    /// Decrypts the private key. Fails if the password is wrong (checksum does not match)
+    /// or the decrypted key does not match the public key stored in the keystore.
+    pub fn decrypt(&self, password: &str) -> Result<Bls12381G1PrivateKey, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+        let crypto = &self.crypto;
+        if crypto.checksum.function != "sha256" {
+            return Err(KeystoreError::UnsupportedFunction(
+                crypto.checksum.function.clone(),
+            ));
+        }
+        if crypto.cipher.function != "aes-128-ctr" {
+            return Err(KeystoreError::UnsupportedFunction(
+                crypto.cipher.function.clone(),
+            ));
+        }
+
+        let decryption_key = derive_key(&crypto.kdf, password)?;
+        let mut cipher_message = decode_hex(&crypto.cipher.message)?;
+        let checksum_message = decode_hex(&crypto.checksum.message)?;
+        // Constant time, so that the comparison does not leak how much of the checksum matches
+        let checksum_ok = checksum(&decryption_key, &cipher_message)[..].ct_eq(&checksum_message);
+        if !bool::from(checksum_ok) {
+            return Err(KeystoreError::InvalidPassword);
+        }
+
+        let iv = decode_hex(&crypto.cipher.params.iv)?;
+        if iv.len() != 16 {
+            return Err(KeystoreError::InvalidParams(format!(
+                "iv length {}",
+                iv.len()
+            )));
+        }
+        Aes128Ctr::new(decryption_key[..16].into(), iv.as_slice().into())
+            .apply_keystream(&mut cipher_message);
+
+        let private_key =
+            Bls12381G1PrivateKey::from_bytes(&cipher_message).map_err(KeystoreError::BlsError)?;
+        // Public key is optional
+        if !self.pubkey.is_empty() && private_key.public_key().to_string() != self.pubkey {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+        Ok(private_key)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|err| KeystoreError::Json(err.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, KeystoreError> {
+        serde_json::to_string_pretty(self).map_err(|err| KeystoreError::Json(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, KeystoreError> {
+        let json =
+            std::fs::read_to_string(path).map_err(|err| KeystoreError::Io(err.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), KeystoreError> {
+        std::fs::write(path, self.to_json()?).map_err(|err| KeystoreError::Io(err.to_string()))
+    }
+}
+
+/// Password is NFKD normalized and control codes are removed, as required by EIP-2335
+fn process_password(password: &str) -> Vec<u8> {
+    password
+        .nfkd()
+        .filter(|c| !matches!(*c as u32, 0x00..=0x1f | 0x7f..=0x9f))
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn derive_key(
+    kdf: &KeystoreModule<serde_json::Value>,
+    password: &str,
+) -> Result<[u8; 32], KeystoreError> {
+    let password = process_password(password);
+    let mut decryption_key = [0u8; 32];
+
+    match kdf.function.as_str() {
+        "scrypt" => {
+            let params: ScryptParams = from_json_value(&kdf.params)?;
+            if params.dklen != 32 || !params.n.is_power_of_two() {
+                return Err(KeystoreError::InvalidParams(format!("{:?}", params)));
+            }
+            let scrypt_params =
+                scrypt::Params::new(params.n.trailing_zeros() as u8, params.r, params.p, 32)
+                    .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?;
+            scrypt::scrypt(
+                &password,
+                &decode_hex(&params.salt)?,
+                &scrypt_params,
+                &mut decryption_key,
+            )
+            .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?;
+        }
+        "pbkdf2" => {
+            let params: Pbkdf2Params = from_json_value(&kdf.params)?;
+            if params.dklen != 32 || params.prf != "hmac-sha256" {
+                return Err(KeystoreError::InvalidParams(format!("{:?}", params)));
+            }
+            pbkdf2::pbkdf2_hmac::<Sha256>(
+                &password,
+                &decode_hex(&params.salt)?,
+                params.c,
+                &mut decryption_key,
+            );
+        }
+        function => return Err(KeystoreError::UnsupportedFunction(function.to_string())),
+    }
+    Ok(decryption_key)
+}
+
+fn checksum(decryption_key: &[u8; 32], cipher_message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(&decryption_key[16..]);
+    hasher.update(cipher_message);
+    hasher.finalize().into()
+}
+
+fn format_uuid_v4(mut bytes: [u8; 16]) -> String {
+    // Version 4 and RFC 4122 variant bits
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(s).map_err(|_| KeystoreError::InvalidHex(s.to_string()))
+}
+
+fn to_json_value<T: Serialize>(value: &T) -> Result<serde_json::Value, KeystoreError> {
+    serde_json::to_value(value).map_err(|err| KeystoreError::Json(err.to_string()))
+}
+
+fn from_json_value<T: for<'de> Deserialize<'de>>(
+    value: &serde_json::Value,
+) -> Result<T, KeystoreError> {
+    T::deserialize(value).map_err(|err| KeystoreError::InvalidParams(err.to_string()))
+}
+
+/// Represents an error when loading, decrypting or saving the keystore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    Io(String),
+    Json(String),
+    InvalidHex(String),
+    // Unsupported kdf, checksum or cipher function
+    UnsupportedFunction(String),
+    UnsupportedVersion(u32),
+    InvalidParams(String),
+    // Checksum does not match, ie. wrong password
+    InvalidPassword,
+    // Decrypted private key does not match the public key of the keystore
+    PublicKeyMismatch,
+    BlsError(BlsError),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for KeystoreError {}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PASSWORD: &str = "\u{1d531}\u{1d522}\u{1d530}\u{1d531}\u{1d52d}\u{1d51e}\u{1d530}\u{1d530}\u{1d534}\u{1d52c}\u{1d52f}\u{1d521}\u{1f511}";
+    const TEST_SECRET: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    fn test_keystore(kdf: &str, checksum: &str, cipher_message: &str) -> String {
+        format!(
+            r#"{{
+    "crypto": {{
+        "kdf": {},
+        "checksum": {{
+            "function": "sha256",
+            "params": {{}},
+            "message": "{}"
+        }},
+        "cipher": {{
+            "function": "aes-128-ctr",
+            "params": {{
+                "iv": "264daa3f303d7259501c93d997d84fe6"
+            }},
+            "message": "{}"
+        }}
+    }},
+    "description": "This is a test keystore.",
+    "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+    "path": "m/12381/60/0/0",
+    "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+    "version": 4
+}}"#,
+            kdf, checksum, cipher_message
+        )
+    }
+
+    // Test vectors from EIP-2335
+    #[test]
+    fn decrypt_pbkdf2_test_vector() {
+        let json = test_keystore(
+            r#"{"function": "pbkdf2", "params": {"dklen": 32, "c": 262144, "prf": "hmac-sha256", "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"}, "message": ""}"#,
+            "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1",
+            "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad",
+        );
+        let keystore = Keystore::from_json(&json).unwrap();
+        let sk = keystore.decrypt(TEST_PASSWORD).unwrap();
+        assert_eq!(sk.to_hex_secret(), TEST_SECRET);
+
+        assert_eq!(
+            keystore.decrypt("testpassword").err(),
+            Some(KeystoreError::InvalidPassword)
+        );
+    }
+
+    #[test]
+    fn decrypt_scrypt_test_vector() {
+        let json = test_keystore(
+            r#"{"function": "scrypt", "params": {"dklen": 32, "n": 262144, "p": 1, "r": 8, "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"}, "message": ""}"#,
+            "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484",
+            "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f",
+        );
+        let keystore = Keystore::from_json(&json).unwrap();
+        let sk = keystore.decrypt(TEST_PASSWORD).unwrap();
+        assert_eq!(sk.to_hex_secret(), TEST_SECRET);
+    }
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        for kdf in [
+            KeystoreKdf::Scrypt {
+                log_n: 10,
+                r: 8,
+                p: 1,
+            },
+            KeystoreKdf::Pbkdf2 { c: 1024 },
+        ] {
+            let keystore = Keystore::encrypt(&sk, "password", kdf, "m/12381/60/0/0").unwrap();
+            let keystore = Keystore::from_json(&keystore.to_json().unwrap()).unwrap();
+
+            assert_eq!(keystore.pubkey, sk.public_key().to_string());
+            assert_eq!(keystore.decrypt("password").unwrap(), sk);
+            // Control codes are removed from the password
+            assert_eq!(keystore.decrypt("pass\u{7f}word").unwrap(), sk);
+            assert_eq!(
+                keystore.decrypt("Password").err(),
+                Some(KeystoreError::InvalidPassword)
+            );
+        }
+    }
+
+    #[test]
+    fn decrypt_rejects_other_public_key() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let mut keystore =
+            Keystore::encrypt(&sk, "password", KeystoreKdf::Pbkdf2 { c: 16 }, "").unwrap();
+        keystore.pubkey = Bls12381G1PrivateKey::from_u64(2)
+            .unwrap()
+            .public_key()
+            .to_string();
+        assert_eq!(
+            keystore.decrypt("password").err(),
+            Some(KeystoreError::PublicKeyMismatch)
+        );
+    }
+}
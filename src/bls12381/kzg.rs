@@ -0,0 +1,404 @@
+use super::*;
+use blst::{blst_fp12, blst_fr, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar};
+use std::fmt;
+use std::path::Path;
+
+/// KZG commitment to a polynomial, ie. the polynomial evaluated at the secret point
+/// of the trusted setup in the exponent (compressed G1 point)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgCommitment(pub [u8; Self::LENGTH]);
+
+impl KzgCommitment {
+    pub const LENGTH: usize = 48;
+}
+
+/// KZG proof of the polynomial evaluation, ie. commitment to the quotient polynomial
+/// (p(x) - p(z)) / (x - z)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgProof(pub [u8; Self::LENGTH]);
+
+impl KzgProof {
+    pub const LENGTH: usize = 48;
+}
+
+/// Powers of the secret tau in G1 ([tau^i]G1, monomial basis) and [tau]G2,
+/// so that polynomials of degree lower than the number of G1 powers can be committed to.
+pub struct KzgTrustedSetup {
+    g1_powers: Vec<blst_p1_affine>,
+    g2_tau: blst_p2_affine,
+}
+
+impl KzgTrustedSetup {
+    /// Parses trusted setup in the text format of c-kzg (eg. trusted_setup.txt of EIP-4844):
+    /// number of G1 points, number of G2 points, G1 points in Lagrange form,
+    /// G2 points in monomial form and G1 points in monomial form (compressed points, hex).
+    /// Lagrange form is not used, polynomials are given by their coefficients.
+    pub fn from_text(text: &str) -> Result<Self, KzgError> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let mut count = || -> Result<usize, KzgError> {
+            let line = lines.next().unwrap_or_default();
+            line.parse()
+                .map_err(|_| KzgError::InvalidSetup(format!("invalid point count {}", line)))
+        };
+        let (g1_cnt, g2_cnt) = (count()?, count()?);
+        if g1_cnt == 0 || g2_cnt < 2 {
+            return Err(KzgError::InvalidSetup(format!(
+                "{} G1 points and {} G2 points",
+                g1_cnt, g2_cnt
+            )));
+        }
+
+        let lines: Vec<&str> = lines.collect();
+        if lines.len() != 2 * g1_cnt + g2_cnt {
+            return Err(KzgError::InvalidSetup(format!(
+                "{} points given, G1 monomial points are required",
+                lines.len()
+            )));
+        }
+        let g2_tau = decode_g2_point(lines[g1_cnt + 1])?;
+        let g1_powers = lines[g1_cnt + g2_cnt..]
+            .iter()
+            .map(|line| decode_g1_point(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { g1_powers, g2_tau })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, KzgError> {
+        let text = std::fs::read_to_string(path).map_err(|err| KzgError::Io(err.to_string()))?;
+        Self::from_text(&text)
+    }
+
+    /// Setup of the known secret, use it only for benchmarks and tests
+    pub fn insecure(tau: u64, max_degree: usize) -> Self {
+        let tau = fr_from_u64(tau);
+        let mut power = fr_from_u64(1);
+        let mut g1_powers = Vec::with_capacity(max_degree + 1);
+        for _ in 0..=max_degree {
+            g1_powers.push(g1_to_affine(&g1_generator_mult(&power)));
+            power = fr_mul(&power, &tau);
+        }
+        let mut g2_tau = blst_p2::default();
+        unsafe {
+            blst::blst_p2_mult(
+                &mut g2_tau,
+                blst::blst_p2_generator(),
+                fr_to_scalar(&tau).b.as_ptr(),
+                255,
+            );
+        }
+        Self {
+            g1_powers,
+            g2_tau: g2_to_affine(&g2_tau),
+        }
+    }
+
+    /// Highest degree of the polynomials which can be committed to
+    pub fn max_degree(&self) -> usize {
+        self.g1_powers.len() - 1
+    }
+
+    /// Commits to polynomial given by its coefficients (lowest degree first)
+    pub fn commit(&self, polynomial: &[blst_fr]) -> Result<KzgCommitment, KzgError> {
+        Ok(KzgCommitment(self.commit_internal(polynomial)?))
+    }
+
+    /// Evaluates polynomial at `z` and proves the evaluation, returns the evaluation and the proof
+    pub fn prove(
+        &self,
+        polynomial: &[blst_fr],
+        z: &blst_fr,
+    ) -> Result<(blst_fr, KzgProof), KzgError> {
+        // Synthetic division by (x - z), remainder is the evaluation
+        let mut quotient = vec![blst_fr::default(); polynomial.len().saturating_sub(1)];
+        let mut acc = blst_fr::default();
+        for (i, coefficient) in polynomial.iter().enumerate().skip(1).rev() {
+            acc = fr_add(coefficient, &fr_mul(&acc, z));
+            quotient[i - 1] = acc;
+        }
+        let y = match polynomial.first() {
+            Some(constant) => fr_add(constant, &fr_mul(&acc, z)),
+            None => blst_fr::default(),
+        };
+        Ok((y, KzgProof(self.commit_internal(&quotient)?)))
+    }
+
+    /// Verifies that committed polynomial evaluates to `y` at `z`:
+    /// e(C - [y]G1, G2) == e(proof, [tau]G2 - [z]G2)
+    pub fn verify(
+        &self,
+        commitment: &KzgCommitment,
+        z: &blst_fr,
+        y: &blst_fr,
+        proof: &KzgProof,
+    ) -> bool {
+        let (commitment, proof) = match (decode_g1_bytes(&commitment.0), decode_g1_bytes(&proof.0))
+        {
+            (Some(commitment), Some(proof)) => (commitment, proof),
+            _ => return false,
+        };
+
+        let mut lhs = g1_generator_mult(y);
+        let lhs_ptr: *mut blst_p1 = &mut lhs;
+        let mut x_minus_z = blst_p2::default();
+        let x_minus_z_ptr: *mut blst_p2 = &mut x_minus_z;
+        let mut neg_proof = blst_p1::default();
+        unsafe {
+            blst::blst_p1_cneg(lhs_ptr, true);
+            blst::blst_p1_add_or_double_affine(lhs_ptr, lhs_ptr, &commitment);
+
+            blst::blst_p2_mult(
+                &mut x_minus_z,
+                blst::blst_p2_generator(),
+                fr_to_scalar(z).b.as_ptr(),
+                255,
+            );
+            blst::blst_p2_cneg(x_minus_z_ptr, true);
+            blst::blst_p2_add_or_double_affine(x_minus_z_ptr, x_minus_z_ptr, &self.g2_tau);
+
+            blst::blst_p1_from_affine(&mut neg_proof, &proof);
+            blst::blst_p1_cneg(&mut neg_proof, true);
+        }
+
+        // Pairs of G1 point at infinity (eg. proof of constant polynomial) equal one
+        let g2 = unsafe { *blst::blst_p2_affine_generator() };
+        let (p1s, p2s): (Vec<blst_p1_affine>, Vec<blst_p2_affine>) = [
+            (g1_to_affine(&lhs), g2),
+            (g1_to_affine(&neg_proof), g2_to_affine(&x_minus_z)),
+        ]
+        .into_iter()
+        .filter(|(p, _)| unsafe { !blst::blst_p1_affine_is_inf(p) })
+        .unzip();
+        let f: blst_fp12 = miller_loop(&p1s, &p2s);
+        unsafe { blst::blst_fp12_is_one(&final_exp(&f)) }
+    }
+
+    fn commit_internal(&self, polynomial: &[blst_fr]) -> Result<[u8; 48], KzgError> {
+        if polynomial.len() > self.g1_powers.len() {
+            return Err(KzgError::DegreeTooHigh(
+                polynomial.len() - 1,
+                self.max_degree(),
+            ));
+        }
+        let scalars: Vec<blst_scalar> = polynomial.iter().map(fr_to_scalar).collect();
+
+        // Projective point at infinity
+        let mut ret = blst_p1::default();
+        unsafe {
+            match polynomial.len() {
+                0 => {}
+                1 => {
+                    let mut p = blst_p1::default();
+                    blst::blst_p1_from_affine(&mut p, &self.g1_powers[0]);
+                    blst::blst_p1_mult(&mut ret, &p, scalars[0].b.as_ptr(), 255);
+                }
+                cnt => {
+                    let point_ptrs: Vec<*const blst_p1_affine> =
+                        self.g1_powers[..cnt].iter().map(|p| p as _).collect();
+                    let scalar_ptrs: Vec<*const u8> =
+                        scalars.iter().map(|s| s.b.as_ptr()).collect();
+                    let scratch_size = blst::blst_p1s_mult_pippenger_scratch_sizeof(cnt);
+                    let mut scratch = vec![
+                        0 as blst::limb_t;
+                        scratch_size / core::mem::size_of::<blst::limb_t>()
+                    ];
+                    blst::blst_p1s_mult_pippenger(
+                        &mut ret,
+                        point_ptrs.as_ptr(),
+                        cnt,
+                        scalar_ptrs.as_ptr(),
+                        255,
+                        scratch.as_mut_ptr(),
+                    );
+                }
+            }
+        }
+
+        let mut bytes = [0u8; 48];
+        unsafe {
+            blst::blst_p1_compress(bytes.as_mut_ptr(), &ret);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Returns random polynomial of given degree and random evaluation point
+pub fn get_kzg_test_data(degree: usize) -> (Vec<blst_fr>, blst_fr) {
+    // Constant terms are not random
+    let polynomial = random_polynomial(fr_from_u64(degree as u64 + 1), degree + 1);
+    let z = random_polynomial(blst_fr::default(), 2)[1];
+    (polynomial, z)
+}
+
+fn fr_to_scalar(fr: &blst_fr) -> blst_scalar {
+    let mut scalar = blst_scalar::default();
+    unsafe {
+        blst::blst_scalar_from_fr(&mut scalar, fr);
+    }
+    scalar
+}
+
+fn g1_generator_mult(fr: &blst_fr) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe {
+        blst::blst_p1_mult(
+            &mut ret,
+            blst::blst_p1_generator(),
+            fr_to_scalar(fr).b.as_ptr(),
+            255,
+        );
+    }
+    ret
+}
+
+fn g1_to_affine(p: &blst_p1) -> blst_p1_affine {
+    let mut ret = blst_p1_affine::default();
+    unsafe {
+        blst::blst_p1_to_affine(&mut ret, p);
+    }
+    ret
+}
+
+fn g2_to_affine(p: &blst_p2) -> blst_p2_affine {
+    let mut ret = blst_p2_affine::default();
+    unsafe {
+        blst::blst_p2_to_affine(&mut ret, p);
+    }
+    ret
+}
+
+// Commitments and proofs may be the point at infinity (eg. proof of constant polynomial)
+fn decode_g1_bytes(bytes: &[u8; 48]) -> Option<blst_p1_affine> {
+    let mut p = blst_p1_affine::default();
+    unsafe {
+        if blst::blst_p1_uncompress(&mut p, bytes.as_ptr()) != blst::BLST_ERROR::BLST_SUCCESS
+            || !blst::blst_p1_affine_in_g1(&p)
+        {
+            return None;
+        }
+    }
+    Some(p)
+}
+
+fn decode_g1_point(hex_point: &str) -> Result<blst_p1_affine, KzgError> {
+    hex::decode(hex_point.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .and_then(|bytes: [u8; 48]| decode_g1_bytes(&bytes))
+        .ok_or_else(|| KzgError::InvalidPoint(hex_point.to_string()))
+}
+
+fn decode_g2_point(hex_point: &str) -> Result<blst_p2_affine, KzgError> {
+    let bytes: [u8; Bls12381G2Signature::LENGTH] = hex::decode(hex_point.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| KzgError::InvalidPoint(hex_point.to_string()))?;
+    let mut p = blst_p2_affine::default();
+    unsafe {
+        if blst::blst_p2_uncompress(&mut p, bytes.as_ptr()) != blst::BLST_ERROR::BLST_SUCCESS
+            || !blst::blst_p2_affine_in_g2(&p)
+        {
+            return Err(KzgError::InvalidPoint(hex_point.to_string()));
+        }
+    }
+    Ok(p)
+}
+
+/// Represents an error when loading trusted setup or committing to polynomial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KzgError {
+    Io(String),
+    InvalidSetup(String),
+    // Point is not a valid compressed point of the subgroup
+    InvalidPoint(String),
+    // Degree of the polynomial and maximal degree of the setup
+    DegreeTooHigh(usize, usize),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for KzgError {}
+
+impl fmt::Display for KzgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kzg_commit_prove_verify() {
+        let setup = KzgTrustedSetup::insecure(12345, 16);
+        for degree in [0, 1, 16] {
+            let (polynomial, z) = get_kzg_test_data(degree);
+            let commitment = setup.commit(&polynomial).unwrap();
+            let (y, proof) = setup.prove(&polynomial, &z).unwrap();
+            assert!(setup.verify(&commitment, &z, &y, &proof), "{}", degree);
+
+            let wrong_y = fr_add(&y, &fr_from_u64(1));
+            assert!(
+                !setup.verify(&commitment, &z, &wrong_y, &proof),
+                "{}",
+                degree
+            );
+            // Constant polynomial evaluates to y everywhere
+            let wrong_z = fr_add(&z, &fr_from_u64(1));
+            assert_eq!(setup.verify(&commitment, &wrong_z, &y, &proof), degree == 0);
+        }
+
+        let (polynomial, _z) = get_kzg_test_data(17);
+        assert_eq!(
+            setup.commit(&polynomial),
+            Err(KzgError::DegreeTooHigh(17, 16))
+        );
+    }
+
+    #[test]
+    fn kzg_trusted_setup_from_text() {
+        let setup = KzgTrustedSetup::insecure(7, 3);
+        let g1: Vec<String> = setup
+            .g1_powers
+            .iter()
+            .map(|p| {
+                let mut bytes = [0u8; 48];
+                unsafe { blst::blst_p1_affine_compress(bytes.as_mut_ptr(), p) };
+                hex::encode(bytes)
+            })
+            .collect();
+        let mut g2 = [[0u8; 96]; 2];
+        unsafe {
+            blst::blst_p2_affine_compress(g2[0].as_mut_ptr(), blst::blst_p2_affine_generator());
+            blst::blst_p2_affine_compress(g2[1].as_mut_ptr(), &setup.g2_tau);
+        }
+
+        // Lagrange form is not used, so monomial points stand for it
+        let mut text = format!("4\n2\n{}\n", g1.join("\n"));
+        text += &format!(
+            "{}\n{}\n{}\n",
+            hex::encode(g2[0]),
+            hex::encode(g2[1]),
+            g1.join("\n")
+        );
+        let loaded = KzgTrustedSetup::from_text(&text).unwrap();
+        assert_eq!(loaded.max_degree(), 3);
+
+        let (polynomial, z) = get_kzg_test_data(3);
+        let commitment = setup.commit(&polynomial).unwrap();
+        assert_eq!(loaded.commit(&polynomial), Ok(commitment));
+        let (y, proof) = setup.prove(&polynomial, &z).unwrap();
+        assert!(loaded.verify(&commitment, &z, &y, &proof));
+
+        // Without G1 monomial points
+        let text = format!(
+            "4\n2\n{}\n{}\n{}\n",
+            g1.join("\n"),
+            hex::encode(g2[0]),
+            hex::encode(g2[1])
+        );
+        assert!(matches!(
+            KzgTrustedSetup::from_text(&text),
+            Err(KzgError::InvalidSetup(_))
+        ));
+    }
+}
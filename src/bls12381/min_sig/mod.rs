@@ -0,0 +1,9 @@
+mod private_key;
+mod public_key;
+mod signature;
+mod signature_validator;
+
+pub use private_key::*;
+pub use public_key::*;
+pub use signature::*;
+pub use signature_validator::*;
@@ -0,0 +1,429 @@
+use super::*;
+use crate::bls12381::{
+    hash_to_g1_reader, test_data_rng, test_data_seed, test_message, test_message_rng, Bls12381Dst,
+    BlsError,
+};
+use blst::min_sig::SecretKey;
+use rand::RngCore;
+use std::fmt;
+use std::io::{self, Read};
+use std::str::FromStr;
+use subtle::{Choice, ConstantTimeEq};
+
+pub struct Bls12381G2PrivateKey(SecretKey);
+
+impl Bls12381G2PrivateKey {
+    pub const LENGTH: usize = 32;
+
+    pub fn public_key(&self) -> Bls12381G2PublicKey {
+        Bls12381G2PublicKey(self.0.sk_to_pk().to_bytes())
+    }
+
+    pub fn sign_v1(&self, message: &[u8]) -> Bls12381G1Signature {
+        self.sign_with_aug_v1(message, None)
+    }
+
+    /// Signs message with optional augmentation data, which is prepended to the message
+    /// before hashing.
+    /// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+    pub fn sign_with_aug_v1(&self, message: &[u8], aug: Option<&[u8]>) -> Bls12381G1Signature {
+        let signature = self
+            .0
+            .sign(message, BLS12381G1_CIPHERSITE_V1, aug.unwrap_or_default())
+            .to_bytes();
+        Bls12381G1Signature(signature)
+    }
+
+    /// Signs message using given domain separation tag instead of BLS12381G1_CIPHERSITE_V1
+    pub fn sign_with_dst_v1(&self, message: &[u8], dst: &Bls12381Dst) -> Bls12381G1Signature {
+        let signature = self.0.sign(message, dst.as_bytes(), &[]).to_bytes();
+        Bls12381G1Signature(signature)
+    }
+
+    /// Signs message read in chunks, giving the same signature as sign_v1
+    pub fn sign_reader_v1<R: Read>(&self, reader: R) -> io::Result<Bls12381G1Signature> {
+        self.sign_reader_with_dst_v1(reader, BLS12381G1_CIPHERSITE_V1)
+    }
+
+    /// Signs message read in chunks using given domain separation tag
+    pub fn sign_reader_with_dst_v1<R: Read>(
+        &self,
+        reader: R,
+        dst: &[u8],
+    ) -> io::Result<Bls12381G1Signature> {
+        let hash = hash_to_g1_reader(reader, dst)?;
+        let mut scalar = blst::blst_scalar::default();
+        let mut signature = blst::blst_p1::default();
+        let mut bytes = [0u8; Bls12381G1Signature::LENGTH];
+        unsafe {
+            blst::blst_scalar_from_bendian(&mut scalar, self.0.to_bytes().as_ptr());
+            blst::blst_sign_pk_in_g2(&mut signature, &hash, &scalar);
+            blst::blst_p1_compress(bytes.as_mut_ptr(), &signature);
+        }
+        Ok(Bls12381G1Signature(bytes))
+    }
+
+    /// Generates proof of possession, ie. signature of own public key
+    /// Domain specifier tag: BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+    pub fn pop_prove_v1(&self) -> Bls12381G1Signature {
+        let public_key = self.public_key();
+        let proof = self
+            .0
+            .sign(&public_key.0, BLS12381G1_POP_CIPHERSITE_V1, &[])
+            .to_bytes();
+        Bls12381G1Signature(proof)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes().to_vec()
+    }
+
+    /// Returns the private key as hex. There is no Display impl, so the key is not
+    /// printed by accident, and exposing it takes this explicit call.
+    pub fn to_hex_secret(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_bytes(slice: &[u8]) -> Result<Self, BlsError> {
+        if slice.len() != Bls12381G2PrivateKey::LENGTH {
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+        if slice.iter().all(|b| *b == 0) {
+            return Err(BlsError::ZeroKey);
+        }
+        Ok(Self(
+            SecretKey::from_bytes(slice).map_err(|_| BlsError::InvalidPrivateKey)?,
+        ))
+    }
+
+    pub fn from_u64(n: u64) -> Result<Self, BlsError> {
+        let mut bytes = [0u8; Bls12381G2PrivateKey::LENGTH];
+        bytes[Bls12381G2PrivateKey::LENGTH - 8..Bls12381G2PrivateKey::LENGTH]
+            .copy_from_slice(&n.to_be_bytes());
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Generates private key from input key material (at least 32 bytes)
+    /// using KeyGen of the BLS signature draft
+    pub fn key_gen(ikm: &[u8]) -> Result<Self, BlsError> {
+        Ok(Self(
+            SecretKey::key_gen(ikm, &[]).map_err(|_| BlsError::InvalidLength(ikm.len()))?,
+        ))
+    }
+}
+
+/// Compares private keys in constant time
+impl ConstantTimeEq for Bls12381G2PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.to_bytes()[..].ct_eq(&other.0.to_bytes()[..])
+    }
+}
+
+impl PartialEq for Bls12381G2PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Bls12381G2PrivateKey {}
+
+impl TryFrom<&[u8]> for Bls12381G2PrivateKey {
+    type Error = BlsError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(slice)
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Bls12381G2PrivateKey {
+    type Err = BlsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Input is not included in the error, since it is most likely a secret
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(String::new()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Prints only the fingerprint (first 4 bytes of the public key), so the key
+/// can safely appear in logs and test failure output.
+impl fmt::Debug for Bls12381G2PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Bls12381G2PrivateKey")
+            .field(
+                "fingerprint",
+                &format_args!("{}", hex::encode(&self.public_key().0[..4])),
+            )
+            .finish()
+    }
+}
+
+/// Private keys of the test data generators: derived from indexes 1..=cnt,
+/// or generated from the seeded RNG if test data seed is set.
+pub fn get_test_private_keys(cnt: u64) -> Vec<Bls12381G2PrivateKey> {
+    get_test_private_keys_with_seed(cnt, test_data_seed())
+}
+
+pub fn get_test_private_keys_with_seed(cnt: u64, seed: Option<u64>) -> Vec<Bls12381G2PrivateKey> {
+    match seed {
+        Some(seed) => {
+            let mut rng = test_data_rng(seed);
+            (0..cnt)
+                .map(|_| {
+                    let mut ikm = [0u8; 32];
+                    rng.fill_bytes(&mut ikm);
+                    Bls12381G2PrivateKey::key_gen(&ikm).unwrap()
+                })
+                .collect()
+        }
+        None => (1..=cnt)
+            .map(|i| Bls12381G2PrivateKey::from_u64(i).unwrap())
+            .collect(),
+    }
+}
+
+/// Generates `cnt` keys signing `msg_cnt` messages (1 <= msg_cnt <= cnt),
+/// messages are assigned to the keys round-robin, ie. key `i` signs message `i % msg_cnt`.
+pub fn get_aggregate_verify_test_data(
+    cnt: u32,
+    msg_cnt: u32,
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G2PrivateKey>,
+    Vec<Bls12381G2PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G1Signature>,
+) {
+    if msg_cnt == 0 || msg_cnt > cnt {
+        panic!("msg_cnt {} shall be in range 1..={}", msg_cnt, cnt);
+    }
+    let msg_indexes: Vec<usize> = (0..cnt as usize).map(|i| i % msg_cnt as usize).collect();
+    get_aggregate_verify_test_data_with_mapping(&msg_indexes, msg_size)
+}
+
+/// Generates keys signing messages given by the explicit mapping,
+/// ie. key `i` signs message `msg_indexes[i]`. Every message shall be signed.
+pub fn get_aggregate_verify_test_data_with_mapping(
+    msg_indexes: &[usize],
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G2PrivateKey>,
+    Vec<Bls12381G2PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G1Signature>,
+) {
+    let msg_cnt = msg_indexes.iter().max().map_or(0, |max| max + 1);
+    let mut signed = vec![false; msg_cnt];
+    for idx in msg_indexes {
+        signed[*idx] = true;
+    }
+    assert!(signed.iter().all(|s| *s), "every message shall be signed");
+
+    let sks = get_test_private_keys(msg_indexes.len() as u64);
+    let mut rng = test_message_rng();
+
+    let msgs: Vec<Vec<u8>> = if msg_cnt == 1 {
+        vec![test_message(
+            (msg_size % u8::MAX as usize) as u8,
+            msg_size,
+            &mut rng,
+        )]
+    } else {
+        (1..(msg_cnt + 1))
+            .map(|i| {
+                let u: u8 = (i % u8::MAX as usize) as u8;
+                test_message(u, msg_size, &mut rng)
+            })
+            .collect()
+    };
+    let sigs: Vec<Bls12381G1Signature> = sks
+        .iter()
+        .zip(msg_indexes.iter())
+        .map(|(sk, idx)| sk.sign_v1(&msgs[*idx]))
+        .collect();
+
+    let pks: Vec<Bls12381G2PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
+
+    (sks, pks, msgs, sigs)
+}
+
+pub fn get_aggregate_verify_test_data2(
+    msg_sizes: &[usize],
+) -> (
+    Vec<Bls12381G2PrivateKey>,
+    Vec<Bls12381G2PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G1Signature>,
+) {
+    let sks = get_test_private_keys(msg_sizes.len() as u64);
+    let mut rng = test_message_rng();
+
+    let msgs: Vec<Vec<u8>> = msg_sizes
+        .iter()
+        .map(|size| {
+            let u: u8 = (size % u8::MAX as usize) as u8;
+            test_message(u, *size, &mut rng)
+        })
+        .collect();
+
+    let sigs: Vec<Bls12381G1Signature> = sks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(sk, msg)| sk.sign_v1(msg))
+        .collect();
+
+    let pks: Vec<Bls12381G2PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
+
+    (sks, pks, msgs, sigs)
+}
+
+pub fn get_fast_aggregate_verify_test_data(
+    cnt: u32,
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G2PrivateKey>,
+    Vec<Bls12381G2PublicKey>,
+    Vec<u8>,
+    Vec<Bls12381G1Signature>,
+) {
+    let sks = get_test_private_keys(cnt.into());
+    let mut rng = test_message_rng();
+
+    let msg: Vec<u8> = test_message((cnt % u8::MAX as u32) as u8, msg_size, &mut rng);
+
+    let sigs: Vec<Bls12381G1Signature> = sks.iter().map(|sk| sk.sign_v1(&msg)).collect();
+    let pks: Vec<Bls12381G2PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
+
+    (sks, pks, msg, sigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(2, 2, 10);
+
+        assert!(verify_bls12381_v1(&msgs[0], &pks[0], &sigs[0]));
+        assert!(!verify_bls12381_v1(&msgs[0], &pks[1], &sigs[0]));
+        assert!(!verify_bls12381_v1(&msgs[1], &pks[0], &sigs[0]));
+    }
+
+    #[test]
+    fn verify_does_not_allocate() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(2, 2, 1024);
+
+        let before = crate::allocation_count();
+        assert!(verify_bls12381_v1(&msgs[0], &pks[0], &sigs[0]));
+        assert!(!verify_bls12381_v1(&msgs[1], &pks[0], &sigs[0]));
+        assert_eq!(crate::allocation_count() - before, 0);
+    }
+
+    #[test]
+    fn pop_prove_and_verify() {
+        let sk1 = Bls12381G2PrivateKey::from_u64(1).unwrap();
+        let sk2 = Bls12381G2PrivateKey::from_u64(2).unwrap();
+
+        let proof = sk1.pop_prove_v1();
+
+        assert!(pop_verify_bls12381_v1(&sk1.public_key(), &proof));
+        assert!(!pop_verify_bls12381_v1(&sk2.public_key(), &proof));
+    }
+
+    #[test]
+    fn sign_reader() {
+        let sk = Bls12381G2PrivateKey::from_u64(1).unwrap();
+        let msg = b"Test";
+        assert_eq!(sk.sign_reader_v1(&msg[..]).unwrap(), sk.sign_v1(msg));
+    }
+
+    #[test]
+    fn sign_and_verify_aggregated_multiple_messages() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+
+        let agg_sig = Bls12381G1Signature::aggregate(&sigs, true).unwrap();
+
+        let pub_keys_msgs: Vec<(Bls12381G2PublicKey, Vec<u8>)> =
+            pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
+
+        assert!(aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
+        assert!(aggregate_verify_bls12381_v1_threaded(
+            &pub_keys_msgs,
+            &agg_sig
+        ));
+
+        // Skip the last key and message tuple
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs[..9], &agg_sig));
+    }
+
+    #[test]
+    fn signature_aggregate_accepts_infinity_at_any_position() {
+        let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(3, 32);
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        let infinity = Bls12381G1Signature(infinity);
+
+        for pos in 0..sigs.len() {
+            let mut with_infinity = sigs.clone();
+            with_infinity.insert(pos, infinity);
+
+            assert_eq!(
+                Bls12381G1Signature::aggregate(&with_infinity, true),
+                Bls12381G1Signature::aggregate(&sigs, true)
+            );
+        }
+        assert_eq!(
+            Bls12381G1Signature::aggregate(&[infinity], true),
+            Ok(infinity)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_fast_aggregated() {
+        let (_sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(10, 10);
+
+        let agg_sig = Bls12381G1Signature::aggregate(&sigs, true).unwrap();
+
+        assert!(fast_aggregate_verify_bls12381_v1(&msg, &pks, &agg_sig));
+        assert!(!fast_aggregate_verify_bls12381_v1(
+            &msg,
+            &pks[..9],
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn sign_and_batch_verify() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+
+        let mut pub_keys_msgs_sigs: Vec<(Bls12381G2PublicKey, Vec<u8>, Bls12381G1Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg, sig))
+            .collect();
+
+        assert!(batch_verify_bls12381_v1(&pub_keys_msgs_sigs));
+        assert!(batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs));
+        assert!(batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs[..1]));
+        assert!(verify_multiple_aggregate_signatures_bls12381_v1(
+            &pub_keys_msgs_sigs
+        ));
+
+        let sig = pub_keys_msgs_sigs[0].2;
+        pub_keys_msgs_sigs[0].2 = pub_keys_msgs_sigs[1].2;
+        pub_keys_msgs_sigs[1].2 = sig;
+
+        assert!(!batch_verify_bls12381_v1(&pub_keys_msgs_sigs));
+        assert!(!batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs));
+        assert!(!verify_multiple_aggregate_signatures_bls12381_v1(
+            &pub_keys_msgs_sigs
+        ));
+    }
+}
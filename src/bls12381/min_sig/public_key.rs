@@ -0,0 +1,111 @@
+use crate::bls12381::{g2_affine_from_bytes, BlsError};
+use crate::copy_u8_array;
+use blst::min_sig::{AggregatePublicKey, PublicKey};
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents a BLS12-381 G2 public key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bls12381G2PublicKey(pub [u8; Self::LENGTH]);
+
+impl Bls12381G2PublicKey {
+    pub const LENGTH: usize = 96;
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    pub fn to_array(&self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    fn to_native_public_key(self) -> Result<PublicKey, BlsError> {
+        PublicKey::from_bytes(&self.0).map_err(|err| err.into())
+    }
+
+    /// Checks that public key is a valid G2 point, is not infinity and is in the G2 subgroup,
+    /// ie. all checks done on public keys during verification, without verifying anything.
+    pub fn is_valid(&self) -> bool {
+        g2_affine_from_bytes(&self.0).is_some()
+    }
+
+    /// Aggregate multiple public keys into a single one.
+    /// Every key is validated (KeyValidate: not infinity and in the G2 subgroup).
+    pub fn aggregate(public_keys: &[Bls12381G2PublicKey]) -> Result<Self, BlsError> {
+        Self::aggregate_internal(public_keys, true)
+    }
+
+    /// Aggregate multiple public keys into a single one without validation of the keys.
+    /// Use it only for keys that were already validated (eg. when registered).
+    pub fn aggregate_prevalidated(public_keys: &[Bls12381G2PublicKey]) -> Result<Self, BlsError> {
+        Self::aggregate_internal(public_keys, false)
+    }
+
+    fn aggregate_internal(
+        public_keys: &[Bls12381G2PublicKey],
+        validate: bool,
+    ) -> Result<Self, BlsError> {
+        if !public_keys.is_empty() {
+            let pk_first = public_keys[0].to_native_public_key()?;
+            if validate {
+                pk_first.validate()?;
+            }
+
+            let mut agg_pk = AggregatePublicKey::from_public_key(&pk_first);
+
+            for pk in public_keys.iter().skip(1) {
+                agg_pk.add_public_key(&pk.to_native_public_key()?, validate)?;
+            }
+            Ok(Bls12381G2PublicKey(agg_pk.to_public_key().to_bytes()))
+        } else {
+            Err(BlsError::NoPublicKeysGiven)
+        }
+    }
+}
+
+impl AsRef<[u8]> for Bls12381G2PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Bls12381G2PublicKey {
+    type Error = BlsError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Bls12381G2PublicKey::LENGTH {
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+
+        Ok(Bls12381G2PublicKey(copy_u8_array(slice)))
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Bls12381G2PublicKey {
+    type Err = BlsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Bls12381G2PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for Bls12381G2PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::bls12381::{g1_affine_from_bytes, BlsError};
+use crate::copy_u8_array;
+use blst::min_sig::{AggregateSignature, Signature};
+use std::fmt;
+use std::str::FromStr;
+use subtle::{Choice, ConstantTimeEq};
+
+pub use crate::bls12381::BLS12381G1_CIPHERSITE_V1;
+
+/// BLS12-381 proof-of-possession ciphersuite v1 for G1 signature variant
+/// Same parameters as BLS12381G1_CIPHERSITE_V1, used to prove and verify possession
+/// of the private key (signature over own public key).
+pub const BLS12381G1_POP_CIPHERSITE_V1: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Represents a BLS12-381 G1 signature (variant with 48-byte signature and 96-byte public key)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bls12381G1Signature(pub [u8; Self::LENGTH]);
+
+impl Bls12381G1Signature {
+    pub const LENGTH: usize = 48;
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    pub fn to_array(&self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    fn to_native_signature(self) -> Result<Signature, BlsError> {
+        Signature::from_bytes(&self.0).map_err(|err| err.into())
+    }
+
+    /// Checks that signature is a valid G1 point, is not infinity and is in the G1 subgroup,
+    /// ie. all checks done on signatures during verification, without verifying anything.
+    pub fn is_valid(&self) -> bool {
+        g1_affine_from_bytes(&self.0).is_some()
+    }
+
+    /// Same as to_native_signature, if `validate` is set the signature is also checked
+    /// to be in the G1 subgroup. Infinity is accepted, as by Aggregate of the IETF draft.
+    fn to_validated_native_signature(self, validate: bool) -> Result<Signature, BlsError> {
+        let sig = self.to_native_signature()?;
+        if validate {
+            sig.validate(false)?;
+        }
+        Ok(sig)
+    }
+
+    /// Aggregate multiple signatures into a single one.
+    /// If `validate` is set then each signature is checked to be in the G1 subgroup
+    /// (infinity is accepted, it adds nothing to the aggregate), otherwise signatures
+    /// are trusted (use it only for already validated signatures).
+    pub fn aggregate(signatures: &[Bls12381G1Signature], validate: bool) -> Result<Self, BlsError> {
+        if !signatures.is_empty() {
+            let sig_first = signatures[0].to_validated_native_signature(validate)?;
+
+            let mut agg_sig = AggregateSignature::from_signature(&sig_first);
+
+            for sig in signatures.iter().skip(1) {
+                agg_sig.add_signature(&sig.to_validated_native_signature(validate)?, false)?;
+            }
+            Ok(Bls12381G1Signature(agg_sig.to_signature().to_bytes()))
+        } else {
+            Err(BlsError::NoSignatureGiven)
+        }
+    }
+}
+
+/// Compares signatures in constant time, use it instead of == when comparing
+/// against the expected signature (eg. MAC-like usage of deterministic signatures)
+impl ConstantTimeEq for Bls12381G1Signature {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+impl AsRef<[u8]> for Bls12381G1Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Bls12381G1Signature {
+    type Error = BlsError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Bls12381G1Signature::LENGTH {
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+
+        Ok(Bls12381G1Signature(copy_u8_array(slice)))
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Bls12381G1Signature {
+    type Err = BlsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Bls12381G1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for Bls12381G1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
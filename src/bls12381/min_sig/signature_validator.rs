@@ -0,0 +1,407 @@
+use super::*;
+use crate::bls12381::{
+    g1_affine_from_bytes, g2_affine_from_bytes, Bls12381Dst, BATCH_VERIFY_RAND_BITS,
+};
+use crate::perf;
+use rand::Rng;
+
+/// Performs BLS12-381 G1 signature verification.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G2PublicKey,
+    signature: &Bls12381G1Signature,
+) -> bool {
+    verify_with_aug_bls12381_v1(message, public_key, signature, None)
+}
+
+/// Performs BLS12-381 G1 signature verification of the message prepended with
+/// optional augmentation data.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_with_aug_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G2PublicKey,
+    signature: &Bls12381G1Signature,
+    aug: Option<&[u8]>,
+) -> bool {
+    verify_internal(
+        message,
+        public_key,
+        signature,
+        BLS12381G1_CIPHERSITE_V1,
+        aug.unwrap_or_default(),
+    )
+}
+
+/// Performs BLS12-381 G1 signature verification using given domain separation tag
+/// instead of BLS12381G1_CIPHERSITE_V1.
+pub fn verify_with_dst_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G2PublicKey,
+    signature: &Bls12381G1Signature,
+    dst: &Bls12381Dst,
+) -> bool {
+    verify_internal(message, public_key, signature, dst.as_bytes(), &[])
+}
+
+/// Same as min-pk verify_internal, it does not allocate.
+fn verify_internal(
+    message: &[u8],
+    public_key: &Bls12381G2PublicKey,
+    signature: &Bls12381G1Signature,
+    dst: &[u8],
+    aug: &[u8],
+) -> bool {
+    let (pk, sig) = match (
+        g2_affine_from_bytes(&public_key.0),
+        g1_affine_from_bytes(&signature.0),
+    ) {
+        (Some(pk), Some(sig)) => (pk, sig),
+        _ => return false,
+    };
+
+    unsafe {
+        blst::blst_core_verify_pk_in_g2(
+            &pk,
+            &sig,
+            true,
+            message.as_ptr(),
+            message.len(),
+            dst.as_ptr(),
+            dst.len(),
+            aug.as_ptr(),
+            aug.len(),
+        ) == blst::BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+/// Performs BLS12-381 G1 proof of possession verification, ie. checks that
+/// proof is a valid signature of the public key itself.
+/// Domain specifier tag: BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn pop_verify_bls12381_v1(
+    public_key: &Bls12381G2PublicKey,
+    proof: &Bls12381G1Signature,
+) -> bool {
+    if let Ok(sig) = blst::min_sig::Signature::from_bytes(&proof.0) {
+        if let Ok(pk) = blst::min_sig::PublicKey::from_bytes(&public_key.0) {
+            let result = sig.verify(
+                true,
+                &public_key.0,
+                BLS12381G1_POP_CIPHERSITE_V1,
+                &[],
+                &pk,
+                true,
+            );
+
+            return matches!(result, blst::BLST_ERROR::BLST_SUCCESS);
+        }
+    }
+
+    false
+}
+
+/// Performs BLS12-381 G1 aggregated signature verification of
+/// multiple messages each signed with different key (no threads).
+/// Messages may be owned or borrowed, so callers do not have to copy them.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G2PublicKey, M)],
+    signature: &Bls12381G1Signature,
+) -> bool {
+    let mut pairing = blst::Pairing::new(true, BLS12381G1_CIPHERSITE_V1);
+
+    // Parse and validate public keys
+    let (pks, _) = perf!(
+        "pk_from_bytes_validate",
+        pub_keys_and_msgs
+            .iter()
+            .map(|(pk, _)| g2_affine_from_bytes(&pk.0))
+            .collect::<Option<Vec<blst::blst_p2_affine>>>()
+    );
+    let pks = match pks {
+        Some(pks) => pks,
+        None => return false,
+    };
+
+    // Aggregate
+    let (result, _) = perf!("pairing_aggregate", {
+        for (pk, (_, msg)) in pks.iter().zip(pub_keys_and_msgs.iter()) {
+            if pairing.aggregate(
+                pk,
+                false,
+                &None::<&blst::blst_p1_affine>,
+                false,
+                msg.as_ref(),
+                &[],
+            ) != blst::BLST_ERROR::BLST_SUCCESS
+            {
+                return false;
+            }
+        }
+        true
+    });
+    if !result {
+        return false;
+    }
+
+    let _ = perf!("pairing_commit", pairing.commit());
+
+    let (sig, _) = perf!(
+        "sig_from_bytes_validate",
+        g1_affine_from_bytes(&signature.0)
+    );
+    let sig = match sig {
+        Some(sig) => sig,
+        None => return false,
+    };
+
+    let (gtsig, _) = perf!("pairing_aggregated", {
+        let mut gtsig = blst::blst_fp12::default();
+        blst::Pairing::aggregated(&mut gtsig, &sig);
+        gtsig
+    });
+
+    let (result, _) = perf!("pairing_verify", pairing.finalverify(Some(&gtsig)));
+    result
+}
+
+/// Performs BLS12-381 G1 aggregated signature verification of
+/// multiple messages each signed with different key using blst (threaded).
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_threaded<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G2PublicKey, M)],
+    signature: &Bls12381G1Signature,
+) -> bool {
+    if let Ok(sig) = blst::min_sig::Signature::from_bytes(&signature.0) {
+        let mut pks = vec![];
+        let mut msg_refs = vec![];
+        for (pk, msg) in pub_keys_and_msgs.iter() {
+            if let Ok(pk) = blst::min_sig::PublicKey::from_bytes(&pk.0) {
+                pks.push(pk);
+            } else {
+                return false;
+            }
+            msg_refs.push(msg.as_ref());
+        }
+        let pks_refs: Vec<&blst::min_sig::PublicKey> = pks.iter().collect();
+
+        let result =
+            sig.aggregate_verify(true, &msg_refs, BLS12381G1_CIPHERSITE_V1, &pks_refs, true);
+
+        matches!(result, blst::BLST_ERROR::BLST_SUCCESS)
+    } else {
+        false
+    }
+}
+
+/// Performs BLS12-381 G1 aggregated signature verification
+/// one message signed with multiple keys.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn fast_aggregate_verify_bls12381_v1(
+    message: &[u8],
+    public_keys: &[Bls12381G2PublicKey],
+    signature: &Bls12381G1Signature,
+) -> bool {
+    if let Ok(agg_pk) = Bls12381G2PublicKey::aggregate(public_keys) {
+        return verify_bls12381_v1(message, &agg_pk, signature);
+    }
+
+    false
+}
+
+/// Performs BLS12-381 G1 batch verification of multiple independent
+/// (public key, message, signature) triples using blst's
+/// verify_multiple_aggregate_signatures (random linear combination of the triples).
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_multiple_aggregate_signatures_bls12381_v1(
+    pub_keys_msgs_sigs: &[(Bls12381G2PublicKey, Vec<u8>, Bls12381G1Signature)],
+) -> bool {
+    let mut pks = vec![];
+    let mut sigs = vec![];
+    let mut msg_refs = vec![];
+    for (pk, msg, sig) in pub_keys_msgs_sigs.iter() {
+        if let Ok(pk) = blst::min_sig::PublicKey::from_bytes(&pk.0) {
+            pks.push(pk);
+        } else {
+            return false;
+        }
+        if let Ok(sig) = blst::min_sig::Signature::from_bytes(&sig.0) {
+            sigs.push(sig);
+        } else {
+            return false;
+        }
+        msg_refs.push(msg.as_slice());
+    }
+    let pks_refs: Vec<&blst::min_sig::PublicKey> = pks.iter().collect();
+    let sigs_refs: Vec<&blst::min_sig::Signature> = sigs.iter().collect();
+
+    let mut rng = rand::thread_rng();
+    let rands: Vec<blst::blst_scalar> = (0..sigs.len())
+        .map(|_| {
+            let mut scalar = blst::blst_scalar::default();
+            // Random scalar must not be zero
+            let r: u64 = rng.gen::<u64>() | 1;
+            scalar.b[..8].copy_from_slice(&r.to_le_bytes());
+            scalar
+        })
+        .collect();
+
+    let result = blst::min_sig::Signature::verify_multiple_aggregate_signatures(
+        &msg_refs,
+        BLS12381G1_CIPHERSITE_V1,
+        &pks_refs,
+        true,
+        &sigs_refs,
+        true,
+        &rands,
+        BATCH_VERIFY_RAND_BITS,
+    );
+
+    matches!(result, blst::BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Performs BLS12-381 G1 batch verification of multiple independent
+/// (public key, message, signature) triples using random linear combination:
+///   e(sum(r_i * sig_i), g2) == prod(e(H(m_i), r_i * pk_i))
+/// where r_i are random 64-bit scalars.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn batch_verify_bls12381_v1(
+    pub_keys_msgs_sigs: &[(Bls12381G2PublicKey, Vec<u8>, Bls12381G1Signature)],
+) -> bool {
+    if pub_keys_msgs_sigs.is_empty() {
+        return false;
+    }
+
+    let mut pairing = blst::Pairing::new(true, BLS12381G1_CIPHERSITE_V1);
+    let Some((sigs, scalars)) = batch_verify_aggregate_scaled_pks(&mut pairing, pub_keys_msgs_sigs)
+    else {
+        return false;
+    };
+
+    // Projective point at infinity
+    let mut agg_sig = blst::blst_p1::default();
+    for (sig, scalar) in sigs.iter().zip(scalars.iter()) {
+        unsafe {
+            // agg_sig += r_i * sig_i
+            let mut q = blst::blst_p1::default();
+            let mut rq = blst::blst_p1::default();
+            blst::blst_p1_from_affine(&mut q, sig);
+            blst::blst_p1_mult(&mut rq, &q, scalar.as_ptr(), BATCH_VERIFY_RAND_BITS);
+            let agg_sig_ptr: *mut blst::blst_p1 = &mut agg_sig;
+            blst::blst_p1_add_or_double(agg_sig_ptr, agg_sig_ptr, &rq);
+        }
+    }
+
+    batch_verify_finalverify(pairing, &agg_sig)
+}
+
+/// Same as batch_verify_bls12381_v1 but sum(r_i * sig_i) is computed at once using
+/// multi-scalar multiplication (Pippenger) instead of separate scalar multiplication
+/// and addition per signature. Public keys are still scaled one by one, as each of them
+/// is paired with different message.
+/// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+pub fn batch_verify_bls12381_v1_msm(
+    pub_keys_msgs_sigs: &[(Bls12381G2PublicKey, Vec<u8>, Bls12381G1Signature)],
+) -> bool {
+    let cnt = pub_keys_msgs_sigs.len();
+    if cnt == 0 {
+        return false;
+    }
+
+    let mut pairing = blst::Pairing::new(true, BLS12381G1_CIPHERSITE_V1);
+    let Some((sigs, scalars)) = batch_verify_aggregate_scaled_pks(&mut pairing, pub_keys_msgs_sigs)
+    else {
+        return false;
+    };
+
+    // sum(r_i * sig_i)
+    let mut agg_sig = blst::blst_p1::default();
+    unsafe {
+        if cnt == 1 {
+            let mut q = blst::blst_p1::default();
+            blst::blst_p1_from_affine(&mut q, &sigs[0]);
+            blst::blst_p1_mult(
+                &mut agg_sig,
+                &q,
+                scalars[0].as_ptr(),
+                BATCH_VERIFY_RAND_BITS,
+            );
+        } else {
+            let point_ptrs: Vec<*const blst::blst_p1_affine> =
+                sigs.iter().map(|p| p as _).collect();
+            let scalar_ptrs: Vec<*const u8> = scalars.iter().map(|s| s.as_ptr()).collect();
+            let scratch_size = blst::blst_p1s_mult_pippenger_scratch_sizeof(cnt);
+            let mut scratch =
+                vec![0 as blst::limb_t; scratch_size / core::mem::size_of::<blst::limb_t>()];
+            blst::blst_p1s_mult_pippenger(
+                &mut agg_sig,
+                point_ptrs.as_ptr(),
+                cnt,
+                scalar_ptrs.as_ptr(),
+                BATCH_VERIFY_RAND_BITS,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+
+    batch_verify_finalverify(pairing, &agg_sig)
+}
+
+/// Aggregates r_i * pk_i paired with H(m_i) of each triple into the pairing and returns
+/// decoded signatures with their random scalars r_i, so that the caller sums r_i * sig_i.
+/// Returns None if any public key or signature is invalid.
+fn batch_verify_aggregate_scaled_pks(
+    pairing: &mut blst::Pairing,
+    pub_keys_msgs_sigs: &[(Bls12381G2PublicKey, Vec<u8>, Bls12381G1Signature)],
+) -> Option<(Vec<blst::blst_p1_affine>, Vec<[u8; 8]>)> {
+    let mut rng = rand::thread_rng();
+    let mut sigs = Vec::with_capacity(pub_keys_msgs_sigs.len());
+    let mut scalars = Vec::with_capacity(pub_keys_msgs_sigs.len());
+
+    for (pk, msg, sig) in pub_keys_msgs_sigs.iter() {
+        let pk = g2_affine_from_bytes(&pk.0)?;
+        sigs.push(g1_affine_from_bytes(&sig.0)?);
+
+        // Random scalar must not be zero
+        let r: u64 = rng.gen::<u64>() | 1;
+        let scalar = r.to_le_bytes();
+
+        let mut scaled_pk = blst::blst_p2_affine::default();
+        unsafe {
+            // r_i * pk_i
+            let mut p = blst::blst_p2::default();
+            let mut rp = blst::blst_p2::default();
+            blst::blst_p2_from_affine(&mut p, &pk);
+            blst::blst_p2_mult(&mut rp, &p, scalar.as_ptr(), BATCH_VERIFY_RAND_BITS);
+            blst::blst_p2_to_affine(&mut scaled_pk, &rp);
+        }
+        scalars.push(scalar);
+
+        if pairing.aggregate(
+            &scaled_pk,
+            false,
+            &None::<&blst::blst_p1_affine>,
+            false,
+            msg,
+            &[],
+        ) != blst::BLST_ERROR::BLST_SUCCESS
+        {
+            return None;
+        }
+    }
+    pairing.commit();
+
+    Some((sigs, scalars))
+}
+
+/// Checks the pairing of batch verification against e(g, sum(r_i * sig_i))
+fn batch_verify_finalverify(pairing: blst::Pairing, agg_sig: &blst::blst_p1) -> bool {
+    let mut agg_sig_affine = blst::blst_p1_affine::default();
+    unsafe {
+        blst::blst_p1_to_affine(&mut agg_sig_affine, agg_sig);
+    }
+    let mut gtsig = blst::blst_fp12::default();
+    blst::Pairing::aggregated(&mut gtsig, &agg_sig_affine);
+
+    pairing.finalverify(Some(&gtsig))
+}
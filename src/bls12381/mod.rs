@@ -1,9 +1,81 @@
+pub mod min_sig;
+
+#[cfg(feature = "manifest")]
+mod aggregate_manifest;
+mod aggregation_rules;
+#[cfg(feature = "attestation-dump")]
+mod attestation_dump;
+mod committee;
+#[cfg(feature = "cross-check")]
+mod cross_check;
+mod decoded_public_key;
+mod deposit;
+mod dkg;
+mod error;
+#[cfg(feature = "fixtures")]
+mod fixtures;
+mod hash_to_curve;
+#[cfg(feature = "mnemonic")]
+mod key_derivation;
+#[cfg(feature = "keystore")]
+mod keystore;
+mod kzg;
+mod negative_test_data;
+mod pairing;
+mod pairing_pool;
+mod point;
+mod pop_registry;
+mod precompile;
+mod precomputed;
 mod private_key;
 mod public_key;
+#[cfg(feature = "serde")]
+mod serde_utils;
 mod signature;
 mod signature_validator;
+#[cfg(feature = "ssz")]
+mod ssz_utils;
+mod test_data;
+mod test_data_cache;
+#[cfg(feature = "test-vectors")]
+mod test_vectors;
+mod threshold;
+mod variant;
 
+#[cfg(feature = "manifest")]
+pub use aggregate_manifest::*;
+pub use aggregation_rules::*;
+#[cfg(feature = "attestation-dump")]
+pub use attestation_dump::*;
+pub use committee::*;
+#[cfg(feature = "cross-check")]
+pub use cross_check::*;
+pub use decoded_public_key::*;
+pub use deposit::*;
+pub use dkg::*;
+pub use error::*;
+#[cfg(feature = "fixtures")]
+pub use fixtures::*;
+pub use hash_to_curve::*;
+#[cfg(feature = "mnemonic")]
+pub use key_derivation::*;
+#[cfg(feature = "keystore")]
+pub use keystore::*;
+pub use kzg::*;
+pub use negative_test_data::*;
+pub use pairing::*;
+pub use pairing_pool::*;
+pub use point::*;
+pub use pop_registry::*;
+pub use precompile::*;
+pub use precomputed::*;
 pub use private_key::*;
 pub use public_key::*;
 pub use signature::*;
 pub use signature_validator::*;
+pub use test_data::*;
+pub use test_data_cache::*;
+#[cfg(feature = "test-vectors")]
+pub use test_vectors::*;
+pub use threshold::*;
+pub use variant::*;
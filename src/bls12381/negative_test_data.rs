@@ -0,0 +1,213 @@
+use super::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of deliberately invalid input, which verification shall reject
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NegativeTestCase {
+    /// Signature with a flipped bit, usually not a curve point at all
+    CorruptedSignature,
+    /// Signature being a curve point outside of the prime order subgroup
+    NonSubgroupSignature,
+    /// Public key being a curve point outside of the prime order subgroup
+    NonSubgroupPublicKey,
+    /// Valid signature verified against public key of another signer
+    MismatchedKey,
+    /// Valid signature verified against message missing its last byte (message shall not be empty)
+    TruncatedMessage,
+}
+
+impl NegativeTestCase {
+    pub const ALL: [NegativeTestCase; 5] = [
+        Self::CorruptedSignature,
+        Self::NonSubgroupSignature,
+        Self::NonSubgroupPublicKey,
+        Self::MismatchedKey,
+        Self::TruncatedMessage,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CorruptedSignature => "corrupted-signature",
+            Self::NonSubgroupSignature => "non-subgroup-signature",
+            Self::NonSubgroupPublicKey => "non-subgroup-public-key",
+            Self::MismatchedKey => "mismatched-key",
+            Self::TruncatedMessage => "truncated-message",
+        }
+    }
+}
+
+impl fmt::Display for NegativeTestCase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for NegativeTestCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|case| case.name() == s)
+            .ok_or_else(|| format!("unknown negative test case: {}", s))
+    }
+}
+
+/// Compressed G1 point on the curve, but not in the G1 subgroup.
+/// Point of the smallest x coordinate (plus given offset) satisfying it is returned.
+pub fn g1_non_subgroup_point(offset: u64) -> [u8; 48] {
+    let mut bytes = [0u8; 48];
+    for x in offset.. {
+        bytes[40..].copy_from_slice(&x.to_be_bytes());
+        // compression flag
+        bytes[0] = 0x80;
+        let mut p = blst::blst_p1_affine::default();
+        unsafe {
+            if blst::blst_p1_uncompress(&mut p, bytes.as_ptr()) == blst::BLST_ERROR::BLST_SUCCESS
+                && !blst::blst_p1_affine_in_g1(&p)
+            {
+                return bytes;
+            }
+        }
+    }
+    unreachable!("half of the x coordinates are on the curve")
+}
+
+/// Compressed G2 point on the curve, but not in the G2 subgroup.
+/// Point of the smallest x coordinate (plus given offset) satisfying it is returned.
+pub fn g2_non_subgroup_point(offset: u64) -> [u8; 96] {
+    let mut bytes = [0u8; 96];
+    for x in offset.. {
+        // x = c0 + c1 * u, serialized as c1 || c0, c1 = 0
+        bytes[88..].copy_from_slice(&x.to_be_bytes());
+        bytes[0] = 0x80;
+        let mut p = blst::blst_p2_affine::default();
+        unsafe {
+            if blst::blst_p2_uncompress(&mut p, bytes.as_ptr()) == blst::BLST_ERROR::BLST_SUCCESS
+                && !blst::blst_p2_affine_in_g2(&p)
+            {
+                return bytes;
+            }
+        }
+    }
+    unreachable!("half of the x coordinates are on the curve")
+}
+
+/// Generates public key, message and signature of given case, which shall not verify
+pub fn get_negative_verify_test_data<V: Bls12381Variant>(
+    case: NegativeTestCase,
+    msg_size: usize,
+) -> (V::PublicKey, Vec<u8>, V::Signature) {
+    let (_sks, pks, mut msgs, sigs) = V::get_aggregate_verify_test_data(1, 1, msg_size);
+    let (mut pk, mut msg, mut sig) = (pks[0], msgs.remove(0), sigs[0]);
+
+    match case {
+        NegativeTestCase::CorruptedSignature => {
+            let mut bytes = V::signature_to_bytes(&sig);
+            let last = bytes.len() - 1;
+            bytes[last] ^= 1;
+            sig = V::signature_from_bytes(&bytes).unwrap();
+        }
+        NegativeTestCase::NonSubgroupSignature => sig = V::non_subgroup_signature(),
+        NegativeTestCase::NonSubgroupPublicKey => pk = V::non_subgroup_public_key(),
+        // Test keys are derived from small indexes or random
+        NegativeTestCase::MismatchedKey => pk = V::public_key(&V::private_key_from_u64(u64::MAX)),
+        NegativeTestCase::TruncatedMessage => {
+            msg.pop();
+        }
+    }
+    (pk, msg, sig)
+}
+
+/// Negative aggregate verification data: public keys, messages and signatures
+pub type NegativeTestData<V> = (
+    Vec<<V as Bls12381Variant>::PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<<V as Bls12381Variant>::Signature>,
+);
+
+/// Generates aggregate verification data of given case, which shall not verify.
+/// Only the first key, message or signature is invalid.
+pub fn get_negative_aggregate_verify_test_data<V: Bls12381Variant>(
+    case: NegativeTestCase,
+    cnt: u32,
+    msg_size: usize,
+) -> NegativeTestData<V> {
+    let (_sks, mut pks, mut msgs, mut sigs) = V::get_aggregate_verify_test_data(cnt, cnt, msg_size);
+    let (pk, msg, sig) = get_negative_verify_test_data::<V>(case, msg_size);
+    pks[0] = pk;
+    msgs[0] = msg;
+    sigs[0] = sig;
+    (pks, msgs, sigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn negative_cases_rejected<V: Bls12381Variant>() {
+        let (_sks, pks, msgs, sigs) = V::get_aggregate_verify_test_data(1, 1, 64);
+        assert!(V::verify(&msgs[0], &pks[0], &sigs[0]));
+
+        for case in NegativeTestCase::ALL {
+            let (pk, msg, sig) = get_negative_verify_test_data::<V>(case, 64);
+            assert!(!V::verify(&msg, &pk, &sig), "{}", case);
+
+            let (pks, msgs, sigs) = get_negative_aggregate_verify_test_data::<V>(case, 3, 64);
+            let pub_keys_msgs_sigs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = pks
+                .into_iter()
+                .zip(msgs)
+                .zip(sigs)
+                .map(|((pk, msg), sig)| (pk, msg, sig))
+                .collect();
+            assert!(
+                !V::verify_multiple_aggregate_signatures(&pub_keys_msgs_sigs),
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn negative_cases_rejected_min_pk() {
+        negative_cases_rejected::<MinPk>();
+    }
+
+    #[test]
+    fn negative_cases_rejected_min_sig() {
+        negative_cases_rejected::<MinSig>();
+    }
+
+    #[test]
+    fn non_subgroup_points() {
+        let p1 = g1_non_subgroup_point(0);
+        let mut pk = blst::blst_p1_affine::default();
+        unsafe {
+            assert_eq!(
+                blst::blst_p1_uncompress(&mut pk, p1.as_ptr()),
+                blst::BLST_ERROR::BLST_SUCCESS
+            );
+            assert!(!blst::blst_p1_affine_in_g1(&pk));
+        }
+        assert_ne!(g1_non_subgroup_point(p1[47] as u64 + 1), p1);
+
+        let p2 = g2_non_subgroup_point(0);
+        let mut sig = blst::blst_p2_affine::default();
+        unsafe {
+            assert_eq!(
+                blst::blst_p2_uncompress(&mut sig, p2.as_ptr()),
+                blst::BLST_ERROR::BLST_SUCCESS
+            );
+            assert!(!blst::blst_p2_affine_in_g2(&sig));
+        }
+    }
+
+    #[test]
+    fn negative_case_names() {
+        for case in NegativeTestCase::ALL {
+            assert_eq!(case.to_string().parse(), Ok(case));
+        }
+        assert!("valid".parse::<NegativeTestCase>().is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use super::*;
+use blst::{blst_fp12, blst_p1_affine, blst_p2_affine};
+
+/// Returns G1 points (public keys) and G2 points (signatures) to be paired
+pub fn get_pairing_test_data(cnt: u32) -> (Vec<blst_p1_affine>, Vec<blst_p2_affine>) {
+    let (_sks, pks, _msgs, sigs) = get_aggregate_verify_test_data(cnt, cnt, 32);
+
+    let p1s: Vec<blst_p1_affine> = pks
+        .iter()
+        .map(|pk| {
+            let mut p = blst_p1_affine::default();
+            let err = unsafe { blst::blst_p1_uncompress(&mut p, pk.0.as_ptr()) };
+            assert_eq!(err, blst::BLST_ERROR::BLST_SUCCESS);
+            p
+        })
+        .collect();
+
+    let p2s: Vec<blst_p2_affine> = sigs
+        .iter()
+        .map(|sig| {
+            let mut p = blst_p2_affine::default();
+            let err = unsafe { blst::blst_p2_uncompress(&mut p, sig.0.as_ptr()) };
+            assert_eq!(err, blst::BLST_ERROR::BLST_SUCCESS);
+            p
+        })
+        .collect();
+
+    (p1s, p2s)
+}
+
+/// Computes product of Miller loops of given pairs (no final exponentiation)
+pub fn miller_loop(p1s: &[blst_p1_affine], p2s: &[blst_p2_affine]) -> blst_fp12 {
+    let mut acc = unsafe { *blst::blst_fp12_one() };
+    let acc_ptr: *mut blst_fp12 = &mut acc;
+
+    for (p, q) in p1s.iter().zip(p2s) {
+        let mut f = blst_fp12::default();
+        unsafe {
+            blst::blst_miller_loop(&mut f, q, p);
+            blst::blst_fp12_mul(acc_ptr, acc_ptr, &f);
+        }
+    }
+    acc
+}
+
+/// Performs final exponentiation of the Miller loop output
+pub fn final_exp(f: &blst_fp12) -> blst_fp12 {
+    let mut ret = blst_fp12::default();
+    unsafe {
+        blst::blst_final_exp(&mut ret, f);
+    }
+    ret
+}
+
+/// Returns GT elements (outputs of the full pairing) for arithmetic benchmarks
+pub fn get_gt_test_data(cnt: u32) -> Vec<blst_fp12> {
+    let (p1s, p2s) = get_pairing_test_data(cnt);
+    p1s.iter()
+        .zip(p2s.iter())
+        .map(|(p, q)| final_exp(&miller_loop(&[*p], &[*q])))
+        .collect()
+}
+
+/// Multiplies two Fp12 elements
+pub fn fp12_mul(a: &blst_fp12, b: &blst_fp12) -> blst_fp12 {
+    let mut ret = blst_fp12::default();
+    unsafe {
+        blst::blst_fp12_mul(&mut ret, a, b);
+    }
+    ret
+}
+
+/// Raises GT element to the power of big-endian encoded exponent (square and multiply).
+/// Cyclotomic squaring is used, so the base must be an output of final exponentiation.
+pub fn gt_pow(base: &blst_fp12, exp: &[u8]) -> blst_fp12 {
+    let mut acc = unsafe { *blst::blst_fp12_one() };
+    let acc_ptr: *mut blst_fp12 = &mut acc;
+
+    for byte in exp {
+        for i in (0..8).rev() {
+            unsafe {
+                blst::blst_fp12_cyclotomic_sqr(acc_ptr, acc_ptr);
+                if (byte >> i) & 1 == 1 {
+                    blst::blst_fp12_mul(acc_ptr, acc_ptr, base);
+                }
+            }
+        }
+    }
+    acc
+}
@@ -0,0 +1,133 @@
+use super::*;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Pairing contexts released by the finished verifications of the current thread
+    static PAIRING_POOL: RefCell<Vec<blst::Pairing>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs `f` with pairing context taken from the thread-local pool, allocating a new one
+/// only if the pool is empty. Reused context is reset (reinitialized) before `f` is called
+/// and returned to the pool afterwards, so it is not allocated and zeroed per verification.
+pub fn with_pooled_pairing<R>(
+    hash_or_encode: bool,
+    dst: &'static [u8],
+    f: impl FnOnce(&mut blst::Pairing) -> R,
+) -> R {
+    let mut pairing = match PAIRING_POOL.with(|pool| pool.borrow_mut().pop()) {
+        Some(mut pairing) => {
+            pairing.init(hash_or_encode, dst);
+            pairing
+        }
+        None => blst::Pairing::new(hash_or_encode, dst),
+    };
+    let result = f(&mut pairing);
+    PAIRING_POOL.with(|pool| pool.borrow_mut().push(pairing));
+    result
+}
+
+/// Number of pairing contexts available for reuse in the current thread
+pub fn pairing_pool_size() -> usize {
+    PAIRING_POOL.with(|pool| pool.borrow().len())
+}
+
+/// Same as verify_bls12381_v1 but using pairing context from the thread-local pool.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_bls12381_v1_pooled(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+) -> bool {
+    let (pk, sig) = match (
+        g1_affine_from_bytes(&public_key.0),
+        g2_affine_from_bytes(&signature.0),
+    ) {
+        (Some(pk), Some(sig)) => (pk, sig),
+        _ => return false,
+    };
+
+    with_pooled_pairing(true, BLS12381_CIPHERSITE_V1, |pairing| {
+        if pairing.aggregate(&pk, false, &sig, false, message, &[])
+            != blst::BLST_ERROR::BLST_SUCCESS
+        {
+            return false;
+        }
+        pairing.commit();
+        pairing.finalverify(None)
+    })
+}
+
+/// Same as aggregate_verify_bls12381_v1 but using pairing context from the thread-local pool.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_pooled<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    if pub_keys_and_msgs.is_empty() {
+        return false;
+    }
+    let sig = match g2_affine_from_bytes(&signature.0) {
+        Some(sig) => sig,
+        None => return false,
+    };
+
+    with_pooled_pairing(true, BLS12381_CIPHERSITE_V1, |pairing| {
+        for (pk, msg) in pub_keys_and_msgs {
+            let pk = match g1_affine_from_bytes(&pk.0) {
+                Some(pk) => pk,
+                None => return false,
+            };
+            if pairing.aggregate(
+                &pk,
+                false,
+                &None::<&blst::blst_p2_affine>,
+                false,
+                msg.as_ref(),
+                &[],
+            ) != blst::BLST_ERROR::BLST_SUCCESS
+            {
+                return false;
+            }
+        }
+        pairing.commit();
+
+        let mut gtsig = blst::blst_fp12::default();
+        blst::Pairing::aggregated(&mut gtsig, &sig);
+
+        pairing.finalverify(Some(&gtsig))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled_verify_matches_verify() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(3, 3, 32);
+
+        for ((pk, msg), sig) in pks.iter().zip(msgs.iter()).zip(sigs.iter()) {
+            assert!(verify_bls12381_v1_pooled(msg, pk, sig));
+        }
+        assert!(!verify_bls12381_v1_pooled(&msgs[1], &pks[0], &sigs[0]));
+
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+        assert!(aggregate_verify_bls12381_v1_pooled(
+            &pub_keys_msgs,
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_pooled(
+            &pub_keys_msgs[1..],
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_pooled::<&[u8]>(&[], &agg_sig));
+
+        // Contexts are reused, not accumulated
+        assert_eq!(pairing_pool_size(), 1);
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+use blst::{blst_p1, blst_p1_affine, blst_p2, blst_p2_affine};
+
+/// Returns G1 points (public keys) in affine and projective coordinates
+pub fn get_g1_test_data(cnt: u32) -> (Vec<blst_p1_affine>, Vec<blst_p1>) {
+    let (p1s, _) = get_pairing_test_data(cnt);
+    let projective = p1s
+        .iter()
+        .map(|p| {
+            let mut ret = blst_p1::default();
+            unsafe { blst::blst_p1_from_affine(&mut ret, p) };
+            ret
+        })
+        .collect();
+    (p1s, projective)
+}
+
+/// Returns G2 points (signatures) in affine and projective coordinates
+pub fn get_g2_test_data(cnt: u32) -> (Vec<blst_p2_affine>, Vec<blst_p2>) {
+    let (_, p2s) = get_pairing_test_data(cnt);
+    let projective = p2s
+        .iter()
+        .map(|p| {
+            let mut ret = blst_p2::default();
+            unsafe { blst::blst_p2_from_affine(&mut ret, p) };
+            ret
+        })
+        .collect();
+    (p2s, projective)
+}
+
+/// Adds G1 points in projective coordinates
+pub fn g1_add(a: &blst_p1, b: &blst_p1) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe { blst::blst_p1_add_or_double(&mut ret, a, b) };
+    ret
+}
+
+/// Adds G1 point in affine coordinates to the point in projective coordinates
+pub fn g1_add_affine(a: &blst_p1, b: &blst_p1_affine) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe { blst::blst_p1_add_or_double_affine(&mut ret, a, b) };
+    ret
+}
+
+/// Doubles G1 point in projective coordinates
+pub fn g1_double(a: &blst_p1) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe { blst::blst_p1_double(&mut ret, a) };
+    ret
+}
+
+/// Adds G2 points in projective coordinates
+pub fn g2_add(a: &blst_p2, b: &blst_p2) -> blst_p2 {
+    let mut ret = blst_p2::default();
+    unsafe { blst::blst_p2_add_or_double(&mut ret, a, b) };
+    ret
+}
+
+/// Adds G2 point in affine coordinates to the point in projective coordinates
+pub fn g2_add_affine(a: &blst_p2, b: &blst_p2_affine) -> blst_p2 {
+    let mut ret = blst_p2::default();
+    unsafe { blst::blst_p2_add_or_double_affine(&mut ret, a, b) };
+    ret
+}
+
+/// Doubles G2 point in projective coordinates
+pub fn g2_double(a: &blst_p2) -> blst_p2 {
+    let mut ret = blst_p2::default();
+    unsafe { blst::blst_p2_double(&mut ret, a) };
+    ret
+}
+
+/// Decompresses G2 point (computes y coordinate with square root in Fp2).
+/// No subgroup check is performed.
+pub fn g2_decompress(bytes: &[u8; Bls12381G2Signature::LENGTH]) -> Option<blst_p2_affine> {
+    let mut p = blst_p2_affine::default();
+    let err = unsafe { blst::blst_p2_uncompress(&mut p, bytes.as_ptr()) };
+    if err == blst::BLST_ERROR::BLST_SUCCESS {
+        Some(p)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,183 @@
+use super::*;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Public key, whose proof of possession was verified by PopRegistry.
+/// It can be obtained only from the registry, so fast aggregate verification
+/// accepting these keys is not prone to rogue key attacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PopVerifiedPublicKey(Bls12381G1PublicKey);
+
+impl PopVerifiedPublicKey {
+    pub fn public_key(&self) -> &Bls12381G1PublicKey {
+        &self.0
+    }
+
+    /// Views verified keys as plain public keys, without copying them
+    pub fn as_public_keys(public_keys: &[Self]) -> &[Bls12381G1PublicKey] {
+        // PopVerifiedPublicKey is a transparent wrapper of Bls12381G1PublicKey,
+        // so both have the same layout
+        unsafe {
+            std::slice::from_raw_parts(
+                public_keys.as_ptr() as *const Bls12381G1PublicKey,
+                public_keys.len(),
+            )
+        }
+    }
+}
+
+/// Set of public keys registered together with their proofs of possession
+#[derive(Debug, Clone, Default)]
+pub struct PopRegistry {
+    public_keys: HashSet<Bls12381G1PublicKey>,
+}
+
+impl PopRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies the proof of possession and registers the public key.
+    /// Registering already registered key does not verify the proof again.
+    pub fn register(
+        &mut self,
+        public_key: &Bls12381G1PublicKey,
+        proof: &Bls12381G2Signature,
+    ) -> Result<PopVerifiedPublicKey, PopRegistryError> {
+        if !self.public_keys.contains(public_key) {
+            if !pop_verify_bls12381_v1(public_key, proof) {
+                return Err(PopRegistryError::InvalidProof(*public_key));
+            }
+            self.public_keys.insert(*public_key);
+        }
+        Ok(PopVerifiedPublicKey(*public_key))
+    }
+
+    /// Returns registered public key
+    pub fn get(&self, public_key: &Bls12381G1PublicKey) -> Option<PopVerifiedPublicKey> {
+        self.public_keys
+            .get(public_key)
+            .map(|pk| PopVerifiedPublicKey(*pk))
+    }
+
+    /// Returns registered public keys or error with the first key not registered
+    pub fn get_all(
+        &self,
+        public_keys: &[Bls12381G1PublicKey],
+    ) -> Result<Vec<PopVerifiedPublicKey>, PopRegistryError> {
+        public_keys
+            .iter()
+            .map(|pk| self.get(pk).ok_or(PopRegistryError::NotRegistered(*pk)))
+            .collect()
+    }
+
+    pub fn contains(&self, public_key: &Bls12381G1PublicKey) -> bool {
+        self.public_keys.contains(public_key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.public_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.public_keys.is_empty()
+    }
+}
+
+/// Represents an error when registering or looking up public keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PopRegistryError {
+    InvalidProof(Bls12381G1PublicKey),
+    NotRegistered(Bls12381G1PublicKey),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for PopRegistryError {}
+
+impl fmt::Display for PopRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Generates public keys with their proofs of possession
+pub fn get_pop_registry_test_data(
+    cnt: u32,
+) -> (Vec<Bls12381G1PublicKey>, Vec<Bls12381G2Signature>) {
+    get_test_private_keys(cnt.into())
+        .iter()
+        .map(|sk| (sk.public_key(), sk.pop_prove_v1()))
+        .unzip()
+}
+
+/// Registers public keys of the given private keys, so they can be used
+/// in fast aggregate verification
+pub fn get_pop_verified_test_keys(
+    private_keys: &[Bls12381G1PrivateKey],
+) -> Vec<PopVerifiedPublicKey> {
+    let mut registry = PopRegistry::new();
+    private_keys
+        .iter()
+        .map(|sk| {
+            registry
+                .register(&sk.public_key(), &sk.pop_prove_v1())
+                .unwrap()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pop_registry_register_and_verify() {
+        let (sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(5, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+        let mut registry = PopRegistry::new();
+        for (sk, pk) in sks.iter().zip(pks.iter()) {
+            registry.register(pk, &sk.pop_prove_v1()).unwrap();
+        }
+        assert_eq!(registry.len(), 5);
+
+        let verified = registry.get_all(&pks).unwrap();
+        assert!(fast_aggregate_verify_bls12381_v1(&msg, &verified, &agg_sig));
+        assert!(!fast_aggregate_verify_bls12381_v1(
+            &msg,
+            &verified[1..],
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn pop_registry_rejects_invalid_proofs() {
+        let (pks, proofs) = get_pop_registry_test_data(2);
+        let mut registry = PopRegistry::new();
+
+        assert_eq!(
+            registry.register(&pks[0], &proofs[1]),
+            Err(PopRegistryError::InvalidProof(pks[0]))
+        );
+        // Signature of own public key in different scheme is not a proof of possession
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        assert_eq!(
+            registry.register(&pks[0], &sk.sign_v1(&pks[0].0)),
+            Err(PopRegistryError::InvalidProof(pks[0]))
+        );
+        assert!(registry.is_empty());
+
+        assert_eq!(
+            registry
+                .register(&pks[0], &proofs[0])
+                .map(|pk| *pk.public_key()),
+            Ok(pks[0])
+        );
+        assert!(registry.contains(&pks[0]));
+        assert_eq!(
+            registry.get_all(&pks),
+            Err(PopRegistryError::NotRegistered(pks[1]))
+        );
+    }
+}
@@ -0,0 +1,501 @@
+use super::*;
+use blst::{blst_fp, blst_fp2, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar};
+
+// Encoding of the EIP-2537 precompile inputs and outputs
+// see: https://eips.ethereum.org/EIPS/eip-2537#fine-points-and-encoding-of-base-elements
+pub const EIP2537_FP_LENGTH: usize = 64;
+pub const EIP2537_FP2_LENGTH: usize = 2 * EIP2537_FP_LENGTH;
+pub const EIP2537_G1_LENGTH: usize = 2 * EIP2537_FP_LENGTH;
+pub const EIP2537_G2_LENGTH: usize = 2 * EIP2537_FP2_LENGTH;
+pub const EIP2537_SCALAR_LENGTH: usize = 32;
+pub const EIP2537_PAIRING_OUTPUT_LENGTH: usize = 32;
+
+// Gas schedule of the EIP-2537 precompiles
+pub const EIP2537_G1ADD_GAS: u64 = 375;
+pub const EIP2537_G1MUL_GAS: u64 = 12000;
+pub const EIP2537_G2ADD_GAS: u64 = 600;
+pub const EIP2537_G2MUL_GAS: u64 = 22500;
+pub const EIP2537_PAIRING_PER_PAIR_GAS: u64 = 32600;
+pub const EIP2537_PAIRING_BASE_GAS: u64 = 37700;
+pub const EIP2537_MAP_FP_TO_G1_GAS: u64 = 5500;
+pub const EIP2537_MAP_FP2_TO_G2_GAS: u64 = 23800;
+
+/// Represents an error when executing EIP-2537 precompile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrecompileError {
+    InvalidInputLength(usize),
+    InvalidFieldElement,
+    PointNotOnCurve,
+    PointNotInSubgroup,
+}
+
+fn decode_fp(bytes: &[u8]) -> Result<blst_fp, PrecompileError> {
+    // Field element is 48-byte big-endian integer padded with 16 zero bytes,
+    // it must be lower than p
+    let (padding, value) = bytes.split_at(EIP2537_FP_LENGTH - 48);
+    if padding.iter().any(|b| *b != 0) {
+        return Err(PrecompileError::InvalidFieldElement);
+    }
+
+    let mut fp = blst_fp::default();
+    let mut canonical = [0u8; 48];
+    unsafe {
+        blst::blst_fp_from_bendian(&mut fp, value.as_ptr());
+        blst::blst_bendian_from_fp(canonical.as_mut_ptr(), &fp);
+    }
+    if canonical != value {
+        return Err(PrecompileError::InvalidFieldElement);
+    }
+    Ok(fp)
+}
+
+fn decode_fp2(bytes: &[u8]) -> Result<blst_fp2, PrecompileError> {
+    let (c0, c1) = bytes.split_at(EIP2537_FP_LENGTH);
+    Ok(blst_fp2 {
+        fp: [decode_fp(c0)?, decode_fp(c1)?],
+    })
+}
+
+fn encode_fp(fp: &blst_fp, out: &mut [u8]) {
+    unsafe { blst::blst_bendian_from_fp(out[EIP2537_FP_LENGTH - 48..].as_mut_ptr(), fp) };
+}
+
+fn encode_fp2(fp2: &blst_fp2, out: &mut [u8]) {
+    let (c0, c1) = out.split_at_mut(EIP2537_FP_LENGTH);
+    encode_fp(&fp2.fp[0], c0);
+    encode_fp(&fp2.fp[1], c1);
+}
+
+/// Decodes G1 point, all zero bytes encode point at infinity
+fn decode_g1(bytes: &[u8], subgroup_check: bool) -> Result<blst_p1_affine, PrecompileError> {
+    let (x, y) = bytes.split_at(EIP2537_FP_LENGTH);
+    let p = blst_p1_affine {
+        x: decode_fp(x)?,
+        y: decode_fp(y)?,
+    };
+    unsafe {
+        if blst::blst_p1_affine_is_inf(&p) {
+            return Ok(p);
+        }
+        if !blst::blst_p1_affine_on_curve(&p) {
+            return Err(PrecompileError::PointNotOnCurve);
+        }
+        if subgroup_check && !blst::blst_p1_affine_in_g1(&p) {
+            return Err(PrecompileError::PointNotInSubgroup);
+        }
+    }
+    Ok(p)
+}
+
+/// Decodes G2 point, all zero bytes encode point at infinity
+fn decode_g2(bytes: &[u8], subgroup_check: bool) -> Result<blst_p2_affine, PrecompileError> {
+    let (x, y) = bytes.split_at(EIP2537_FP2_LENGTH);
+    let p = blst_p2_affine {
+        x: decode_fp2(x)?,
+        y: decode_fp2(y)?,
+    };
+    unsafe {
+        if blst::blst_p2_affine_is_inf(&p) {
+            return Ok(p);
+        }
+        if !blst::blst_p2_affine_on_curve(&p) {
+            return Err(PrecompileError::PointNotOnCurve);
+        }
+        if subgroup_check && !blst::blst_p2_affine_in_g2(&p) {
+            return Err(PrecompileError::PointNotInSubgroup);
+        }
+    }
+    Ok(p)
+}
+
+fn encode_g1(p: &blst_p1) -> Vec<u8> {
+    let mut out = vec![0u8; EIP2537_G1_LENGTH];
+    let mut affine = blst_p1_affine::default();
+    unsafe {
+        if blst::blst_p1_is_inf(p) {
+            return out;
+        }
+        blst::blst_p1_to_affine(&mut affine, p);
+    }
+    let (x, y) = out.split_at_mut(EIP2537_FP_LENGTH);
+    encode_fp(&affine.x, x);
+    encode_fp(&affine.y, y);
+    out
+}
+
+fn encode_g2(p: &blst_p2) -> Vec<u8> {
+    let mut out = vec![0u8; EIP2537_G2_LENGTH];
+    let mut affine = blst_p2_affine::default();
+    unsafe {
+        if blst::blst_p2_is_inf(p) {
+            return out;
+        }
+        blst::blst_p2_to_affine(&mut affine, p);
+    }
+    let (x, y) = out.split_at_mut(EIP2537_FP2_LENGTH);
+    encode_fp2(&affine.x, x);
+    encode_fp2(&affine.y, y);
+    out
+}
+
+/// Converts 32-byte big-endian scalar to blst (little-endian) representation
+fn decode_scalar(bytes: &[u8]) -> blst_scalar {
+    let mut scalar = blst_scalar::default();
+    unsafe { blst::blst_scalar_from_bendian(&mut scalar, bytes.as_ptr()) };
+    scalar
+}
+
+fn check_input_length(input: &[u8], item_length: usize) -> Result<usize, PrecompileError> {
+    if input.is_empty() || !input.len().is_multiple_of(item_length) {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    Ok(input.len() / item_length)
+}
+
+/// G1ADD: adds two G1 points, no subgroup check is performed
+pub fn precompile_g1_add(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != 2 * EIP2537_G1_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    let a = decode_g1(&input[..EIP2537_G1_LENGTH], false)?;
+    let b = decode_g1(&input[EIP2537_G1_LENGTH..], false)?;
+
+    let mut ret = blst_p1::default();
+    unsafe {
+        blst::blst_p1_from_affine(&mut ret, &a);
+        let ret_ptr: *mut blst_p1 = &mut ret;
+        blst::blst_p1_add_or_double_affine(ret_ptr, ret_ptr, &b);
+    }
+    Ok(encode_g1(&ret))
+}
+
+/// G2ADD: adds two G2 points, no subgroup check is performed
+pub fn precompile_g2_add(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != 2 * EIP2537_G2_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    let a = decode_g2(&input[..EIP2537_G2_LENGTH], false)?;
+    let b = decode_g2(&input[EIP2537_G2_LENGTH..], false)?;
+
+    let mut ret = blst_p2::default();
+    unsafe {
+        blst::blst_p2_from_affine(&mut ret, &a);
+        let ret_ptr: *mut blst_p2 = &mut ret;
+        blst::blst_p2_add_or_double_affine(ret_ptr, ret_ptr, &b);
+    }
+    Ok(encode_g2(&ret))
+}
+
+/// G1MUL: multiplies G1 point by scalar
+pub fn precompile_g1_mul(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != EIP2537_G1_LENGTH + EIP2537_SCALAR_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    precompile_g1_msm(input)
+}
+
+/// G2MUL: multiplies G2 point by scalar
+pub fn precompile_g2_mul(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != EIP2537_G2_LENGTH + EIP2537_SCALAR_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    precompile_g2_msm(input)
+}
+
+/// G1MSM: multi-scalar multiplication of G1 points (Pippenger's algorithm for more than one point)
+pub fn precompile_g1_msm(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let item_length = EIP2537_G1_LENGTH + EIP2537_SCALAR_LENGTH;
+    let cnt = check_input_length(input, item_length)?;
+
+    let mut points = Vec::with_capacity(cnt);
+    let mut scalars = Vec::with_capacity(cnt);
+    for item in input.chunks(item_length) {
+        let (point, scalar) = item.split_at(EIP2537_G1_LENGTH);
+        points.push(decode_g1(point, true)?);
+        scalars.push(decode_scalar(scalar));
+    }
+
+    let mut ret = blst_p1::default();
+    unsafe {
+        if cnt == 1 {
+            let mut p = blst_p1::default();
+            blst::blst_p1_from_affine(&mut p, &points[0]);
+            blst::blst_p1_mult(&mut ret, &p, scalars[0].b.as_ptr(), 256);
+        } else {
+            let point_ptrs: Vec<*const blst_p1_affine> = points.iter().map(|p| p as _).collect();
+            let scalar_ptrs: Vec<*const u8> = scalars.iter().map(|s| s.b.as_ptr()).collect();
+            let scratch_size = blst::blst_p1s_mult_pippenger_scratch_sizeof(cnt);
+            let mut scratch =
+                vec![0 as blst::limb_t; scratch_size / core::mem::size_of::<blst::limb_t>()];
+            blst::blst_p1s_mult_pippenger(
+                &mut ret,
+                point_ptrs.as_ptr(),
+                cnt,
+                scalar_ptrs.as_ptr(),
+                256,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+    Ok(encode_g1(&ret))
+}
+
+/// G2MSM: multi-scalar multiplication of G2 points (Pippenger's algorithm for more than one point)
+pub fn precompile_g2_msm(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let item_length = EIP2537_G2_LENGTH + EIP2537_SCALAR_LENGTH;
+    let cnt = check_input_length(input, item_length)?;
+
+    let mut points = Vec::with_capacity(cnt);
+    let mut scalars = Vec::with_capacity(cnt);
+    for item in input.chunks(item_length) {
+        let (point, scalar) = item.split_at(EIP2537_G2_LENGTH);
+        points.push(decode_g2(point, true)?);
+        scalars.push(decode_scalar(scalar));
+    }
+
+    let mut ret = blst_p2::default();
+    unsafe {
+        if cnt == 1 {
+            let mut p = blst_p2::default();
+            blst::blst_p2_from_affine(&mut p, &points[0]);
+            blst::blst_p2_mult(&mut ret, &p, scalars[0].b.as_ptr(), 256);
+        } else {
+            let point_ptrs: Vec<*const blst_p2_affine> = points.iter().map(|p| p as _).collect();
+            let scalar_ptrs: Vec<*const u8> = scalars.iter().map(|s| s.b.as_ptr()).collect();
+            let scratch_size = blst::blst_p2s_mult_pippenger_scratch_sizeof(cnt);
+            let mut scratch =
+                vec![0 as blst::limb_t; scratch_size / core::mem::size_of::<blst::limb_t>()];
+            blst::blst_p2s_mult_pippenger(
+                &mut ret,
+                point_ptrs.as_ptr(),
+                cnt,
+                scalar_ptrs.as_ptr(),
+                256,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+    Ok(encode_g2(&ret))
+}
+
+/// PAIRING: checks that product of pairings of given (G1, G2) pairs equals one,
+/// returns 1 encoded as 32-byte big-endian integer if so, 0 otherwise
+pub fn precompile_pairing(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    let item_length = EIP2537_G1_LENGTH + EIP2537_G2_LENGTH;
+    check_input_length(input, item_length)?;
+
+    let mut p1s = vec![];
+    let mut p2s = vec![];
+    for item in input.chunks(item_length) {
+        let (p1, p2) = item.split_at(EIP2537_G1_LENGTH);
+        let p1 = decode_g1(p1, true)?;
+        let p2 = decode_g2(p2, true)?;
+        // Pairing with point at infinity equals one
+        if unsafe { !blst::blst_p1_affine_is_inf(&p1) && !blst::blst_p2_affine_is_inf(&p2) } {
+            p1s.push(p1);
+            p2s.push(p2);
+        }
+    }
+
+    let gt = final_exp(&miller_loop(&p1s, &p2s));
+
+    let mut out = vec![0u8; EIP2537_PAIRING_OUTPUT_LENGTH];
+    if unsafe { blst::blst_fp12_is_one(&gt) } {
+        out[EIP2537_PAIRING_OUTPUT_LENGTH - 1] = 1;
+    }
+    Ok(out)
+}
+
+/// MAP_FP_TO_G1: maps field element to G1 point (SSWU map and cofactor clearing)
+pub fn precompile_map_fp_to_g1(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != EIP2537_FP_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    let u = decode_fp(input)?;
+
+    let mut ret = blst_p1::default();
+    unsafe { blst::blst_map_to_g1(&mut ret, &u, core::ptr::null()) };
+    Ok(encode_g1(&ret))
+}
+
+/// MAP_FP2_TO_G2: maps Fp2 element to G2 point (SSWU map and cofactor clearing)
+pub fn precompile_map_fp2_to_g2(input: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+    if input.len() != EIP2537_FP2_LENGTH {
+        return Err(PrecompileError::InvalidInputLength(input.len()));
+    }
+    let u = decode_fp2(input)?;
+
+    let mut ret = blst_p2::default();
+    unsafe { blst::blst_map_to_g2(&mut ret, &u, core::ptr::null()) };
+    Ok(encode_g2(&ret))
+}
+
+/// Gas cost of the PAIRING precompile for given number of pairs
+pub fn eip2537_pairing_gas(cnt: u32) -> u64 {
+    EIP2537_PAIRING_PER_PAIR_GAS * u64::from(cnt) + EIP2537_PAIRING_BASE_GAS
+}
+
+/// Returns encoded G1 and G2 points from the pairing test data
+fn get_precompile_points(cnt: u32) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let (p1s, p2s) = get_pairing_test_data(cnt);
+    let g1s = p1s
+        .iter()
+        .map(|p| {
+            let mut projective = blst_p1::default();
+            unsafe { blst::blst_p1_from_affine(&mut projective, p) };
+            encode_g1(&projective)
+        })
+        .collect();
+    let g2s = p2s
+        .iter()
+        .map(|p| {
+            let mut projective = blst_p2::default();
+            unsafe { blst::blst_p2_from_affine(&mut projective, p) };
+            encode_g2(&projective)
+        })
+        .collect();
+    (g1s, g2s)
+}
+
+fn get_precompile_scalar(i: usize) -> Vec<u8> {
+    // Full size scalars (lower than group order), so that all bits are processed
+    let mut scalar = vec![0x5a; EIP2537_SCALAR_LENGTH];
+    scalar[0] = 0x1f;
+    scalar[EIP2537_SCALAR_LENGTH - 1] = i as u8;
+    scalar
+}
+
+/// Returns input of the G1ADD precompile
+pub fn get_precompile_g1_add_input() -> Vec<u8> {
+    let (g1s, _) = get_precompile_points(2);
+    g1s.concat()
+}
+
+/// Returns input of the G2ADD precompile
+pub fn get_precompile_g2_add_input() -> Vec<u8> {
+    let (_, g2s) = get_precompile_points(2);
+    g2s.concat()
+}
+
+/// Returns input of the G1MSM precompile with `cnt` (point, scalar) pairs
+pub fn get_precompile_g1_msm_input(cnt: u32) -> Vec<u8> {
+    let (g1s, _) = get_precompile_points(cnt);
+    g1s.iter()
+        .enumerate()
+        .flat_map(|(i, p)| [p.clone(), get_precompile_scalar(i)].concat())
+        .collect()
+}
+
+/// Returns input of the G2MSM precompile with `cnt` (point, scalar) pairs
+pub fn get_precompile_g2_msm_input(cnt: u32) -> Vec<u8> {
+    let (_, g2s) = get_precompile_points(cnt);
+    g2s.iter()
+        .enumerate()
+        .flat_map(|(i, p)| [p.clone(), get_precompile_scalar(i)].concat())
+        .collect()
+}
+
+/// Returns input of the PAIRING precompile with `cnt` (G1, G2) pairs
+pub fn get_precompile_pairing_input(cnt: u32) -> Vec<u8> {
+    let (g1s, g2s) = get_precompile_points(cnt);
+    g1s.iter()
+        .zip(g2s.iter())
+        .flat_map(|(p1, p2)| [p1.as_slice(), p2.as_slice()].concat())
+        .collect()
+}
+
+/// Returns input of the MAP_FP_TO_G1 precompile (x coordinate of the G1 point)
+pub fn get_precompile_map_fp_to_g1_input() -> Vec<u8> {
+    let (g1s, _) = get_precompile_points(1);
+    g1s[0][..EIP2537_FP_LENGTH].to_vec()
+}
+
+/// Returns input of the MAP_FP2_TO_G2 precompile (x coordinate of the G2 point)
+pub fn get_precompile_map_fp2_to_g2_input() -> Vec<u8> {
+    let (_, g2s) = get_precompile_points(1);
+    g2s[0][..EIP2537_FP2_LENGTH].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn g1_add_matches_msm() {
+        let (g1s, _) = get_precompile_points(1);
+        let doubled = precompile_g1_add(&[g1s[0].clone(), g1s[0].clone()].concat()).unwrap();
+
+        let mut two = vec![0u8; EIP2537_SCALAR_LENGTH];
+        two[EIP2537_SCALAR_LENGTH - 1] = 2;
+        let mul_input = [g1s[0].clone(), two].concat();
+        assert_eq!(precompile_g1_mul(&mul_input).unwrap(), doubled);
+
+        // Pippenger gives the same result as the sum of multiplications
+        let msm_input = [mul_input.clone(), mul_input].concat();
+        let msm = precompile_g1_msm(&msm_input).unwrap();
+        assert_eq!(
+            msm,
+            precompile_g1_add(&[doubled.clone(), doubled].concat()).unwrap()
+        );
+    }
+
+    #[test]
+    fn g2_add_matches_msm() {
+        let (_, g2s) = get_precompile_points(1);
+        let doubled = precompile_g2_add(&[g2s[0].clone(), g2s[0].clone()].concat()).unwrap();
+
+        let mut two = vec![0u8; EIP2537_SCALAR_LENGTH];
+        two[EIP2537_SCALAR_LENGTH - 1] = 2;
+        let mul_input = [g2s[0].clone(), two].concat();
+        assert_eq!(precompile_g2_mul(&mul_input).unwrap(), doubled);
+
+        let msm_input = [mul_input.clone(), mul_input].concat();
+        let msm = precompile_g2_msm(&msm_input).unwrap();
+        assert_eq!(
+            msm,
+            precompile_g2_add(&[doubled.clone(), doubled].concat()).unwrap()
+        );
+    }
+
+    #[test]
+    fn pairing_of_point_and_its_negation_is_one() {
+        let (g1s, g2s) = get_precompile_points(1);
+        let mut neg_g1 = blst_p1::default();
+        unsafe {
+            let p = decode_g1(&g1s[0], true).unwrap();
+            blst::blst_p1_from_affine(&mut neg_g1, &p);
+            blst::blst_p1_cneg(&mut neg_g1, true);
+        }
+        let input = [
+            g1s[0].as_slice(),
+            g2s[0].as_slice(),
+            encode_g1(&neg_g1).as_slice(),
+            g2s[0].as_slice(),
+        ]
+        .concat();
+
+        let out = precompile_pairing(&input).unwrap();
+        assert_eq!(out[EIP2537_PAIRING_OUTPUT_LENGTH - 1], 1);
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        assert_eq!(
+            precompile_g1_add(&[]),
+            Err(PrecompileError::InvalidInputLength(0))
+        );
+        // Field element not lower than p
+        let input = vec![0xff; EIP2537_FP_LENGTH];
+        assert_eq!(
+            precompile_map_fp_to_g1(&input),
+            Err(PrecompileError::InvalidFieldElement)
+        );
+        // Point not on the curve
+        let mut input = get_precompile_g1_add_input();
+        input[EIP2537_G1_LENGTH - 1] ^= 1;
+        assert_eq!(
+            precompile_g1_add(&input),
+            Err(PrecompileError::PointNotOnCurve)
+        );
+    }
+}
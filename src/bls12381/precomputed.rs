@@ -0,0 +1,122 @@
+use super::min_sig::*;
+use blst::{blst_fp12, blst_fp6, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine};
+
+/// Number of Miller loop lines precomputed for a single G2 point
+const MILLER_LOOP_LINES_CNT: usize = 68;
+
+fn precompute_lines(q: &blst_p2_affine) -> Vec<blst_fp6> {
+    let mut lines = vec![blst_fp6::default(); MILLER_LOOP_LINES_CNT];
+    unsafe {
+        blst::blst_precompute_lines(lines.as_mut_ptr(), q);
+    }
+    lines
+}
+
+/// BLS12-381 G2 public key (min_sig variant) with Miller loop lines precomputed once,
+/// so they are reused by every verification.
+/// Lines can only be precomputed for G2 points, thus it is not applicable to G1 public keys
+/// (Bls12381G1PublicKey), where the fixed part of the pairing is a G1 point.
+pub struct PrecomputedG2PublicKey {
+    pk_lines: Vec<blst_fp6>,
+    neg_g2_lines: Vec<blst_fp6>,
+}
+
+impl PrecomputedG2PublicKey {
+    /// Decodes and validates public key and precomputes Miller loop lines
+    /// of the public key and of the negated G2 generator
+    pub fn new(public_key: &Bls12381G2PublicKey) -> Option<Self> {
+        let mut pk = blst_p2_affine::default();
+        let mut neg_g2 = blst_p2::default();
+        let mut neg_g2_affine = blst_p2_affine::default();
+        unsafe {
+            if blst::blst_p2_uncompress(&mut pk, public_key.0.as_ptr())
+                != blst::BLST_ERROR::BLST_SUCCESS
+                || blst::blst_p2_affine_is_inf(&pk)
+                || !blst::blst_p2_affine_in_g2(&pk)
+            {
+                return None;
+            }
+            blst::blst_p2_from_affine(&mut neg_g2, blst::blst_p2_affine_generator());
+            blst::blst_p2_cneg(&mut neg_g2, true);
+            blst::blst_p2_to_affine(&mut neg_g2_affine, &neg_g2);
+        }
+
+        Some(Self {
+            pk_lines: precompute_lines(&pk),
+            neg_g2_lines: precompute_lines(&neg_g2_affine),
+        })
+    }
+
+    /// Performs BLS12-381 G1 signature verification using precomputed lines:
+    ///   e(H(m), pk) * e(sig, -g2) == 1
+    /// Domain specifier tag: BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+    pub fn verify(&self, message: &[u8], signature: &Bls12381G1Signature) -> bool {
+        let mut sig = blst_p1_affine::default();
+        let mut hash = blst_p1::default();
+        let mut hash_affine = blst_p1_affine::default();
+        let mut f = blst_fp12::default();
+        let mut f_sig = blst_fp12::default();
+        let mut result = blst_fp12::default();
+        let aug: &[u8] = &[];
+
+        unsafe {
+            if blst::blst_p1_uncompress(&mut sig, signature.0.as_ptr())
+                != blst::BLST_ERROR::BLST_SUCCESS
+                || blst::blst_p1_affine_is_inf(&sig)
+                || !blst::blst_p1_affine_in_g1(&sig)
+            {
+                return false;
+            }
+
+            blst::blst_hash_to_g1(
+                &mut hash,
+                message.as_ptr(),
+                message.len(),
+                BLS12381G1_CIPHERSITE_V1.as_ptr(),
+                BLS12381G1_CIPHERSITE_V1.len(),
+                aug.as_ptr(),
+                aug.len(),
+            );
+            blst::blst_p1_to_affine(&mut hash_affine, &hash);
+
+            blst::blst_miller_loop_lines(&mut f, self.pk_lines.as_ptr(), &hash_affine);
+            blst::blst_miller_loop_lines(&mut f_sig, self.neg_g2_lines.as_ptr(), &sig);
+            let f_ptr: *mut blst_fp12 = &mut f;
+            blst::blst_fp12_mul(f_ptr, f_ptr, &f_sig);
+            blst::blst_final_exp(&mut result, &f);
+
+            blst::blst_fp12_is_one(&result)
+        }
+    }
+}
+
+/// Returns G2 public key and messages signed with its private key
+pub fn get_precomputed_verify_test_data(
+    msg_cnt: u32,
+    msg_size: usize,
+) -> (Bls12381G2PublicKey, Vec<Vec<u8>>, Vec<Bls12381G1Signature>) {
+    let (sks, pks, msgs, _sigs) = get_aggregate_verify_test_data(msg_cnt, msg_cnt, msg_size);
+
+    // All messages signed with the first key
+    let sigs: Vec<Bls12381G1Signature> = msgs.iter().map(|msg| sks[0].sign_v1(msg)).collect();
+
+    (pks[0], msgs, sigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precomputed_verify_matches_verify() {
+        let (pk, msgs, sigs) = get_precomputed_verify_test_data(2, 10);
+        let precomputed_pk = PrecomputedG2PublicKey::new(&pk).unwrap();
+
+        assert!(verify_bls12381_v1(&msgs[0], &pk, &sigs[0]));
+        assert!(precomputed_pk.verify(&msgs[0], &sigs[0]));
+
+        // Signature of other message
+        assert!(!verify_bls12381_v1(&msgs[0], &pk, &sigs[1]));
+        assert!(!precomputed_pk.verify(&msgs[0], &sigs[1]));
+    }
+}
@@ -1,5 +1,10 @@
 use super::*;
 use blst::min_pk::SecretKey;
+use rand::RngCore;
+use std::fmt;
+use std::io::{self, Read};
+use std::str::FromStr;
+use subtle::{Choice, ConstantTimeEq};
 
 pub struct Bls12381G1PrivateKey(SecretKey);
 
@@ -11,29 +16,212 @@ impl Bls12381G1PrivateKey {
     }
 
     pub fn sign_v1(&self, message: &[u8]) -> Bls12381G2Signature {
-        let signature = self.0.sign(message, BLS12381_CIPHERSITE_V1, &[]).to_bytes();
+        self.sign_with_aug_v1(message, None)
+    }
+
+    /// Signs message with optional augmentation data, which is prepended to the message
+    /// before hashing, so protocols can bind extra context into the signature.
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub fn sign_with_aug_v1(&self, message: &[u8], aug: Option<&[u8]>) -> Bls12381G2Signature {
+        let signature = self
+            .0
+            .sign(message, BLS12381_CIPHERSITE_V1, aug.unwrap_or_default())
+            .to_bytes();
+        Bls12381G2Signature(signature)
+    }
+
+    /// Signs message according to given scheme
+    pub fn sign_with_scheme_v1(&self, message: &[u8], scheme: Scheme) -> Bls12381G2Signature {
+        let signature = match scheme {
+            Scheme::Aug => self.0.sign(message, scheme.dst(), &self.public_key().0),
+            Scheme::Basic | Scheme::Pop => self.0.sign(message, scheme.dst(), &[]),
+        };
+        Bls12381G2Signature(signature.to_bytes())
+    }
+
+    /// Signs message using given domain separation tag instead of BLS12381_CIPHERSITE_V1
+    pub fn sign_with_dst_v1(&self, message: &[u8], dst: &Bls12381Dst) -> Bls12381G2Signature {
+        let signature = self.0.sign(message, dst.as_bytes(), &[]).to_bytes();
         Bls12381G2Signature(signature)
     }
 
+    /// Signs message in basic scheme
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_
+    pub fn sign_basic_v1(&self, message: &[u8]) -> Bls12381G2Signature {
+        self.sign_with_scheme_v1(message, Scheme::Basic)
+    }
+
+    /// Signs message prepended with own public key (message augmentation scheme)
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_
+    pub fn sign_aug_v1(&self, message: &[u8]) -> Bls12381G2Signature {
+        self.sign_with_scheme_v1(message, Scheme::Aug)
+    }
+
+    /// Signs message read in chunks, giving the same signature as sign_v1
+    /// (message size is not limited by memory)
+    pub fn sign_reader_v1<R: Read>(&self, reader: R) -> io::Result<Bls12381G2Signature> {
+        self.sign_reader_with_dst_v1(reader, BLS12381_CIPHERSITE_V1)
+    }
+
+    /// Signs message read in chunks using given domain separation tag
+    pub fn sign_reader_with_dst_v1<R: Read>(
+        &self,
+        reader: R,
+        dst: &[u8],
+    ) -> io::Result<Bls12381G2Signature> {
+        let hash = hash_to_g2_reader(reader, dst)?;
+        let mut scalar = blst::blst_scalar::default();
+        let mut signature = blst::blst_p2::default();
+        let mut bytes = [0u8; Bls12381G2Signature::LENGTH];
+        unsafe {
+            blst::blst_scalar_from_bendian(&mut scalar, self.0.to_bytes().as_ptr());
+            blst::blst_sign_pk_in_g1(&mut signature, &hash, &scalar);
+            blst::blst_p2_compress(bytes.as_mut_ptr(), &signature);
+        }
+        Ok(Bls12381G2Signature(bytes))
+    }
+
+    /// Generates proof of possession, ie. signature of own public key
+    /// Domain specifier tag: BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub fn pop_prove_v1(&self) -> Bls12381G2Signature {
+        let public_key = self.public_key();
+        let proof = self
+            .0
+            .sign(&public_key.0, BLS12381_POP_CIPHERSITE_V1, &[])
+            .to_bytes();
+        Bls12381G2Signature(proof)
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes().to_vec()
     }
 
-    pub fn from_bytes(slice: &[u8]) -> Result<Self, ()> {
+    /// Returns the private key as hex. There is no Display impl, so the key is not
+    /// printed by accident, and exposing it takes this explicit call.
+    pub fn to_hex_secret(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_bytes(slice: &[u8]) -> Result<Self, BlsError> {
         if slice.len() != Bls12381G1PrivateKey::LENGTH {
-            return Err(());
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+        if slice.iter().all(|b| *b == 0) {
+            return Err(BlsError::ZeroKey);
         }
-        Ok(Self(SecretKey::from_bytes(slice).map_err(|_| ())?))
+        Ok(Self(
+            SecretKey::from_bytes(slice).map_err(|_| BlsError::InvalidPrivateKey)?,
+        ))
     }
 
-    pub fn from_u64(n: u64) -> Result<Self, ()> {
+    pub fn from_u64(n: u64) -> Result<Self, BlsError> {
         let mut bytes = [0u8; Bls12381G1PrivateKey::LENGTH];
-        (&mut bytes[Bls12381G1PrivateKey::LENGTH - 8..Bls12381G1PrivateKey::LENGTH])
+        bytes[Bls12381G1PrivateKey::LENGTH - 8..Bls12381G1PrivateKey::LENGTH]
             .copy_from_slice(&n.to_be_bytes());
 
-        Ok(Self(SecretKey::from_bytes(&bytes).map_err(|_| ())?))
+        Self::from_bytes(&bytes)
+    }
+
+    /// Generates private key from input key material (at least 32 bytes)
+    /// using KeyGen of the BLS signature draft
+    pub fn key_gen(ikm: &[u8]) -> Result<Self, BlsError> {
+        Ok(Self(
+            SecretKey::key_gen(ikm, &[]).map_err(|_| BlsError::InvalidLength(ikm.len()))?,
+        ))
+    }
+}
+
+/// Compares private keys in constant time
+impl ConstantTimeEq for Bls12381G1PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.to_bytes()[..].ct_eq(&other.0.to_bytes()[..])
+    }
+}
+
+impl PartialEq for Bls12381G1PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Bls12381G1PrivateKey {}
+
+impl TryFrom<&[u8]> for Bls12381G1PrivateKey {
+    type Error = BlsError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(slice)
+    }
+}
+
+//======
+// blst
+//======
+
+impl From<SecretKey> for Bls12381G1PrivateKey {
+    fn from(secret_key: SecretKey) -> Self {
+        Self(secret_key)
+    }
+}
+
+impl From<Bls12381G1PrivateKey> for SecretKey {
+    fn from(private_key: Bls12381G1PrivateKey) -> Self {
+        private_key.0
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for Bls12381G1PrivateKey {
+    type Err = BlsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Input is not included in the error, since it is most likely a secret
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(String::new()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Prints only the fingerprint (first 4 bytes of the public key), so the key
+/// can safely appear in logs and test failure output.
+impl fmt::Debug for Bls12381G1PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Bls12381G1PrivateKey")
+            .field(
+                "fingerprint",
+                &format_args!("{}", hex::encode(&self.public_key().0[..4])),
+            )
+            .finish()
+    }
+}
+/// Private keys of the test data generators: derived from indexes 1..=cnt,
+/// or generated from the seeded RNG if test data seed is set.
+pub fn get_test_private_keys(cnt: u64) -> Vec<Bls12381G1PrivateKey> {
+    get_test_private_keys_with_seed(cnt, test_data_seed())
+}
+
+pub fn get_test_private_keys_with_seed(cnt: u64, seed: Option<u64>) -> Vec<Bls12381G1PrivateKey> {
+    match seed {
+        Some(seed) => {
+            let mut rng = test_data_rng(seed);
+            (0..cnt)
+                .map(|_| {
+                    let mut ikm = [0u8; 32];
+                    rng.fill_bytes(&mut ikm);
+                    Bls12381G1PrivateKey::key_gen(&ikm).unwrap()
+                })
+                .collect()
+        }
+        None => (1..=cnt)
+            .map(|i| Bls12381G1PrivateKey::from_u64(i).unwrap())
+            .collect(),
     }
 }
+
+/// Generates `cnt` keys signing `msg_cnt` messages (1 <= msg_cnt <= cnt),
+/// messages are assigned to the keys round-robin, ie. key `i` signs message `i % msg_cnt`.
 pub fn get_aggregate_verify_test_data(
     cnt: u32,
     msg_cnt: u32,
@@ -44,31 +232,53 @@ pub fn get_aggregate_verify_test_data(
     Vec<Vec<u8>>,
     Vec<Bls12381G2Signature>,
 ) {
-    let sks: Vec<Bls12381G1PrivateKey> = (1..(cnt + 1))
-        .map(|i| Bls12381G1PrivateKey::from_u64(i.into()).unwrap())
-        .collect();
+    if msg_cnt == 0 || msg_cnt > cnt {
+        panic!("msg_cnt {} shall be in range 1..={}", msg_cnt, cnt);
+    }
+    let msg_indexes: Vec<usize> = (0..cnt as usize).map(|i| i % msg_cnt as usize).collect();
+    get_aggregate_verify_test_data_with_mapping(&msg_indexes, msg_size)
+}
 
-    let (msgs, sigs): (Vec<Vec<u8>>, Vec<Bls12381G2Signature>) = if msg_cnt == cnt {
-        let msgs: Vec<Vec<u8>> = (1..(cnt + 1))
-            .map(|i| {
-                let u: u8 = (i % u8::MAX as u32) as u8;
-                vec![u; msg_size]
-            })
-            .collect();
-        let sigs: Vec<Bls12381G2Signature> = sks
-            .iter()
-            .zip(msgs.clone())
-            .map(|(sk, msg)| sk.sign_v1(&msg))
-            .collect();
-        (msgs, sigs)
-    } else if msg_cnt == 1 {
-        let msgs: Vec<Vec<u8>> = vec![vec![(msg_size % u8::MAX as usize) as u8; msg_size]];
+/// Generates keys signing messages given by the explicit mapping,
+/// ie. key `i` signs message `msg_indexes[i]`. Every message shall be signed.
+pub fn get_aggregate_verify_test_data_with_mapping(
+    msg_indexes: &[usize],
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G1PrivateKey>,
+    Vec<Bls12381G1PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G2Signature>,
+) {
+    let msg_cnt = msg_indexes.iter().max().map_or(0, |max| max + 1);
+    let mut signed = vec![false; msg_cnt];
+    for idx in msg_indexes {
+        signed[*idx] = true;
+    }
+    assert!(signed.iter().all(|s| *s), "every message shall be signed");
 
-        let sigs: Vec<Bls12381G2Signature> = sks.iter().map(|sk| sk.sign_v1(&msgs[0])).collect();
-        (msgs, sigs)
+    let sks = get_test_private_keys(msg_indexes.len() as u64);
+    let mut rng = test_message_rng();
+
+    let msgs: Vec<Vec<u8>> = if msg_cnt == 1 {
+        vec![test_message(
+            (msg_size % u8::MAX as usize) as u8,
+            msg_size,
+            &mut rng,
+        )]
     } else {
-        panic!("msg_cnt {} might be equal to cnt {} or 1", msg_cnt, cnt);
+        (1..(msg_cnt + 1))
+            .map(|i| {
+                let u: u8 = (i % u8::MAX as usize) as u8;
+                test_message(u, msg_size, &mut rng)
+            })
+            .collect()
     };
+    let sigs: Vec<Bls12381G2Signature> = sks
+        .iter()
+        .zip(msg_indexes.iter())
+        .map(|(sk, idx)| sk.sign_v1(&msgs[*idx]))
+        .collect();
 
     let pks: Vec<Bls12381G1PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
 
@@ -82,23 +292,83 @@ pub fn get_aggregate_verify_test_data2(
     Vec<Vec<u8>>,
     Vec<Bls12381G2Signature>,
 ) {
-    let cnt = msg_sizes.len() as u64;
-    let sks: Vec<Bls12381G1PrivateKey> = (1..(cnt + 1))
-        .map(|i| Bls12381G1PrivateKey::from_u64(i).unwrap())
-        .collect();
+    let sks = get_test_private_keys(msg_sizes.len() as u64);
+    let mut rng = test_message_rng();
 
     let msgs: Vec<Vec<u8>> = msg_sizes
         .iter()
         .map(|size| {
             let u: u8 = (size % u8::MAX as usize) as u8;
-            vec![u; *size]
+            test_message(u, *size, &mut rng)
         })
         .collect();
 
     let sigs: Vec<Bls12381G2Signature> = sks
         .iter()
-        .zip(msgs.clone())
-        .map(|(sk, msg)| sk.sign_v1(&msg))
+        .zip(msgs.iter())
+        .map(|(sk, msg)| sk.sign_v1(msg))
+        .collect();
+
+    let pks: Vec<Bls12381G1PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
+
+    (sks, pks, msgs, sigs)
+}
+
+/// Same as get_aggregate_verify_test_data but signatures are generated in message
+/// augmentation scheme, in which the messages signed by different keys may repeat.
+pub fn get_aggregate_verify_aug_test_data(
+    cnt: u32,
+    msg_cnt: u32,
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G1PrivateKey>,
+    Vec<Bls12381G1PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G2Signature>,
+) {
+    let (sks, pks, msgs, _) = get_aggregate_verify_test_data(cnt, msg_cnt, msg_size);
+
+    let sigs: Vec<Bls12381G2Signature> = sks
+        .iter()
+        .zip(msgs.iter().cycle())
+        .map(|(sk, msg)| sk.sign_aug_v1(msg))
+        .collect();
+
+    (sks, pks, msgs, sigs)
+}
+
+/// Test data for basic scheme aggregate verification, messages are distinct
+/// (unlike in get_aggregate_verify_test_data, which repeats them every 255 keys).
+pub fn get_aggregate_verify_basic_test_data(
+    cnt: u32,
+    msg_size: usize,
+) -> (
+    Vec<Bls12381G1PrivateKey>,
+    Vec<Bls12381G1PublicKey>,
+    Vec<Vec<u8>>,
+    Vec<Bls12381G2Signature>,
+) {
+    assert!(
+        msg_size >= 4,
+        "msg_size {} too small to be distinct",
+        msg_size
+    );
+
+    let sks = get_test_private_keys(cnt.into());
+    let mut rng = test_message_rng();
+
+    let msgs: Vec<Vec<u8>> = (1..(cnt + 1))
+        .map(|i| {
+            let mut msg = test_message((i % u8::MAX as u32) as u8, msg_size, &mut rng);
+            msg[..4].copy_from_slice(&i.to_be_bytes());
+            msg
+        })
+        .collect();
+
+    let sigs: Vec<Bls12381G2Signature> = sks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(sk, msg)| sk.sign_basic_v1(msg))
         .collect();
 
     let pks: Vec<Bls12381G1PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
@@ -115,11 +385,10 @@ pub fn get_fast_aggregate_verify_test_data(
     Vec<u8>,
     Vec<Bls12381G2Signature>,
 ) {
-    let sks: Vec<Bls12381G1PrivateKey> = (1..(cnt + 1))
-        .map(|i| Bls12381G1PrivateKey::from_u64(i.into()).unwrap())
-        .collect();
+    let sks = get_test_private_keys(cnt.into());
+    let mut rng = test_message_rng();
 
-    let msg: Vec<u8> = vec![(cnt % u8::MAX as u32) as u8; msg_size];
+    let msg: Vec<u8> = test_message((cnt % u8::MAX as u32) as u8, msg_size, &mut rng);
 
     let sigs: Vec<Bls12381G2Signature> = sks.iter().map(|sk| sk.sign_v1(&msg)).collect();
     let pks: Vec<Bls12381G1PublicKey> = sks.iter().map(|sk| sk.public_key()).collect();
@@ -130,12 +399,16 @@ pub fn get_fast_aggregate_verify_test_data(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
 
     #[test]
     fn sign_and_verify() {
         let test_sk = "408157791befddd702672dcfcfc99da3512f9c0ea818890fcb6ab749580ef2cf";
         let test_pk = "93b1aa7542a5423e21d8e84b4472c31664412cc604a666e9fdf03baf3c758e728c7a11576ebb01110ac39a0df95636e2";
-        let test_message_hash = hash("Test").as_bytes().to_vec();
+        // Blake2b-256 hash of "Test"
+        let test_message_hash =
+            hex::decode("8d2143d76615c515b5cc88fa7806aef268edeea87571c8f8b21a19f77b9993ba")
+                .unwrap();
         let test_signature = "8b84ff5a1d4f8095ab8a80518ac99230ed24a7d1ec90c4105f9c719aa7137ed5d7ce1454d4a953f5f55f3959ab416f3014f4cd2c361e4d32c6b4704a70b0e2e652a908f501acb54ec4e79540be010e3fdc1fbf8e7af61625705e185a71c884f1";
         let sk = Bls12381G1PrivateKey::from_bytes(&hex::decode(test_sk).unwrap()).unwrap();
         let pk = Bls12381G1PublicKey::from_str(test_pk).unwrap();
@@ -146,12 +419,701 @@ mod tests {
         assert!(verify_bls12381_v1(&test_message_hash, &pk, &sig));
     }
 
+    #[test]
+    fn verify_does_not_allocate() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(2, 2, 1024);
+
+        let before = crate::allocation_count();
+        assert!(verify_bls12381_v1(&msgs[0], &pks[0], &sigs[0]));
+        assert!(!verify_bls12381_v1(&msgs[1], &pks[0], &sigs[0]));
+        // Signature of the message without augmentation
+        assert!(!verify_aug_bls12381_v1(&msgs[0], &pks[0], &sigs[0]));
+        assert_eq!(crate::allocation_count() - before, 0);
+    }
+
+    #[test]
+    fn pop_prove_and_verify() {
+        let sk1 = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let sk2 = Bls12381G1PrivateKey::from_u64(2).unwrap();
+
+        let proof = sk1.pop_prove_v1();
+
+        assert!(pop_verify_bls12381_v1(&sk1.public_key(), &proof));
+        // Proof does not match other key
+        assert!(!pop_verify_bls12381_v1(&sk2.public_key(), &proof));
+        // Proof is not a regular signature of the public key
+        assert_ne!(proof, sk1.sign_v1(&sk1.public_key().0));
+    }
+
+    #[test]
+    fn sign_and_verify_with_dst() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let msg = b"Test";
+        let dst = Bls12381Dst::new(b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_").unwrap();
+
+        let sig = sk.sign_with_dst_v1(msg, &dst);
+        assert!(verify_with_dst_bls12381_v1(msg, &pk, &sig, &dst));
+        // Signature does not verify under the default tag
+        assert!(!verify_bls12381_v1(msg, &pk, &sig));
+
+        // Default tag is the v1 ciphersuite
+        let sig = sk.sign_with_dst_v1(msg, &Bls12381Dst::default());
+        assert_eq!(sig, sk.sign_v1(msg));
+    }
+
+    #[test]
+    fn sign_reader() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let msg = vec![7u8; 3 * crate::HASH_READ_CHUNK_SIZE / 2];
+        let dst = Bls12381Dst::new(b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_").unwrap();
+
+        assert_eq!(sk.sign_reader_v1(msg.as_slice()).unwrap(), sk.sign_v1(&msg));
+        assert_eq!(
+            sk.sign_reader_with_dst_v1(msg.as_slice(), dst.as_bytes())
+                .unwrap(),
+            sk.sign_with_dst_v1(&msg, &dst)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_aug() {
+        let sk1 = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let sk2 = Bls12381G1PrivateKey::from_u64(2).unwrap();
+        let msg = b"Test".to_vec();
+
+        let sig1 = sk1.sign_aug_v1(&msg);
+        assert!(verify_aug_bls12381_v1(&msg, &sk1.public_key(), &sig1));
+        assert!(!verify_aug_bls12381_v1(&msg, &sk2.public_key(), &sig1));
+        assert!(!verify_bls12381_v1(&msg, &sk1.public_key(), &sig1));
+
+        // Same message signed by different keys can be aggregated
+        let sig2 = sk2.sign_aug_v1(&msg);
+        let agg_sig = Bls12381G2Signature::aggregate(&[sig1, sig2], true).unwrap();
+        let pub_keys_msgs = vec![(sk1.public_key(), msg.clone()), (sk2.public_key(), msg)];
+        assert!(aggregate_verify_aug_bls12381_v1(&pub_keys_msgs, &agg_sig));
+        assert!(!aggregate_verify_aug_bls12381_v1(
+            &pub_keys_msgs[..1],
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn sign_and_aggregate_verify_basic() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_basic_test_data(3, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.into_iter().zip(msgs).collect();
+
+        assert!(aggregate_verify_basic_bls12381_v1(&pub_keys_msgs, &agg_sig));
+
+        // Duplicated message is rejected even if the signature is valid
+        let sk = Bls12381G1PrivateKey::from_u64(10).unwrap();
+        let msg = pub_keys_msgs[0].1.clone();
+        let agg_sig =
+            Bls12381G2Signature::aggregate(&[agg_sig, sk.sign_basic_v1(&msg)], true).unwrap();
+        let mut dup_pub_keys_msgs = pub_keys_msgs.clone();
+        dup_pub_keys_msgs.push((sk.public_key(), msg));
+
+        assert!(!messages_distinct(&dup_pub_keys_msgs));
+        assert!(!aggregate_verify_basic_bls12381_v1(
+            &dup_pub_keys_msgs,
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn sign_and_verify_with_scheme() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let msg = b"Test";
+        let schemes = [Scheme::Basic, Scheme::Aug, Scheme::Pop];
+
+        assert_eq!(Scheme::default(), Scheme::Pop);
+        for scheme in schemes {
+            let sig = sk.sign_with_scheme_v1(msg, scheme);
+            for other in schemes {
+                assert_eq!(
+                    verify_with_scheme_bls12381_v1(msg, &pk, &sig, other),
+                    scheme == other
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_with_aug() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let msg = b"Test";
+        let aug = b"context";
+
+        let sig = sk.sign_with_aug_v1(msg, Some(aug));
+        assert!(verify_with_aug_bls12381_v1(msg, &pk, &sig, Some(aug)));
+        assert!(!verify_with_aug_bls12381_v1(msg, &pk, &sig, None));
+        assert!(!verify_bls12381_v1(msg, &pk, &sig));
+
+        // Augmentation data is prepended to the message
+        let aug_msg = [aug.as_slice(), msg.as_slice()].concat();
+        assert!(verify_bls12381_v1(&aug_msg, &pk, &sig));
+
+        assert_eq!(sk.sign_with_aug_v1(msg, None), sk.sign_v1(msg));
+    }
+
+    #[test]
+    fn verify_with_flags() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let msg = b"Test";
+        let sig = sk.sign_v1(msg);
+
+        for flags in VerifyFlags::all() {
+            assert_eq!(
+                verify_with_flags_bls12381_v1(msg, &pk, &sig, flags),
+                flags.hash_or_encode
+            );
+        }
+    }
+
+    #[test]
+    fn aggregate_signature_incrementally() {
+        let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(5, 32);
+
+        let mut agg_sig = AggregateSignature::new();
+        for sig in &sigs {
+            agg_sig.add(sig).unwrap();
+        }
+
+        assert_eq!(
+            agg_sig.finalize(),
+            Bls12381G2Signature::aggregate(&sigs, true)
+        );
+        assert_eq!(
+            AggregateSignature::new().finalize(),
+            Err(BlsError::NoSignatureGiven)
+        );
+    }
+
+    #[test]
+    fn aggregate_public_key_incrementally() {
+        let (_sks, pks, _msg, _sigs) = get_fast_aggregate_verify_test_data(5, 32);
+
+        let mut agg_pk = AggregatePublicKey::new();
+        for pk in &pks {
+            agg_pk.add(pk).unwrap();
+        }
+        assert_eq!(agg_pk.finalize(), Bls12381G1PublicKey::aggregate(&pks));
+
+        assert_eq!(agg_pk.remove(&pks[2]), Ok(true));
+        assert_eq!(agg_pk.remove(&pks[2]), Ok(false));
+        let remaining = [pks[0], pks[1], pks[3], pks[4]];
+        assert_eq!(
+            agg_pk.finalize(),
+            Bls12381G1PublicKey::aggregate(&remaining)
+        );
+
+        for pk in &remaining {
+            assert_eq!(agg_pk.remove(pk), Ok(true));
+        }
+        assert_eq!(agg_pk.finalize(), Err(BlsError::NoPublicKeysGiven));
+    }
+
+    #[test]
+    fn aggregate_verify_iter() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(5, 5, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+        assert!(aggregate_verify_bls12381_v1_iter(
+            pks.iter().zip(msgs.iter().map(|msg| msg.as_slice())),
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_iter(
+            pks.iter()
+                .zip(msgs.iter().map(|msg| msg.as_slice()))
+                .skip(1),
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_iter(
+            std::iter::empty(),
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn aggregate_verify_borrowed() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(5, 5, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+
+        assert!(aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
+        assert!(aggregate_verify_bls12381_v1_threaded(
+            &pub_keys_msgs,
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs[1..], &agg_sig));
+        assert!(!aggregate_verify_bls12381_v1_threaded(
+            &pub_keys_msgs[1..],
+            &agg_sig
+        ));
+        assert!(messages_distinct(&pub_keys_msgs));
+    }
+
+    #[test]
+    fn aggregate_verify_batch_hash() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(5, 5, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+
+        assert!(aggregate_verify_bls12381_v1_hash_each(
+            &pub_keys_msgs,
+            &agg_sig
+        ));
+        assert!(aggregate_verify_bls12381_v1_batch_hash(
+            &pub_keys_msgs,
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_hash_each(
+            &pub_keys_msgs[1..],
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_batch_hash(
+            &pub_keys_msgs[1..],
+            &agg_sig
+        ));
+        assert!(!aggregate_verify_bls12381_v1_batch_hash::<&[u8]>(
+            &[],
+            &agg_sig
+        ));
+    }
+
+    #[test]
+    fn aggregate_verify_chunked() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.into_iter().zip(msgs).collect();
+
+        for chunk_size in [0, 1, 3, 10, 100] {
+            assert!(aggregate_verify_bls12381_v1_chunked(
+                &pub_keys_msgs,
+                &agg_sig,
+                chunk_size
+            ));
+            assert!(!aggregate_verify_bls12381_v1_chunked(
+                &pub_keys_msgs[1..],
+                &agg_sig,
+                chunk_size
+            ));
+        }
+        assert!(!aggregate_verify_bls12381_v1_chunked(&[], &agg_sig, 4));
+    }
+
+    #[test]
+    fn aggregate_parallel_matches_aggregate() {
+        for validate in [true, false] {
+            for cnt in [1, 31, 32, 33, 100] {
+                let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(cnt, 32);
+                assert_eq!(
+                    Bls12381G2Signature::aggregate_parallel(&sigs, validate),
+                    Bls12381G2Signature::aggregate(&sigs, validate)
+                );
+            }
+
+            // Invalid signature at the beginning of the second chunk
+            let (_sks, _pks, _msg, mut sigs) = get_fast_aggregate_verify_test_data(33, 32);
+            sigs[Bls12381G2Signature::AGGREGATE_PARALLEL_CHUNK_SIZE] =
+                Bls12381G2Signature([0xff; 96]);
+            assert_eq!(
+                Bls12381G2Signature::aggregate_parallel(&sigs, validate),
+                Bls12381G2Signature::aggregate(&sigs, validate)
+            );
+
+            assert_eq!(
+                Bls12381G2Signature::aggregate_parallel(&[], validate),
+                Err(BlsError::NoSignatureGiven)
+            );
+        }
+    }
+
+    #[test]
+    fn signature_aggregate_errors() {
+        for validate in [true, false] {
+            assert_eq!(
+                Bls12381G2Signature::aggregate(&[], validate),
+                Err(BlsError::NoSignatureGiven)
+            );
+            // Not a valid compressed G2 point
+            assert_eq!(
+                Bls12381G2Signature::aggregate(&[Bls12381G2Signature([0xff; 96])], validate),
+                Err(BlsError::InvalidPoint)
+            );
+        }
+    }
+
+    #[test]
+    fn signature_aggregate_accepts_infinity_at_any_position() {
+        let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(3, 32);
+        let infinity = Bls12381G2Signature(G2_POINT_AT_INFINITY);
+
+        for pos in 0..sigs.len() {
+            let mut with_infinity = sigs.clone();
+            with_infinity.insert(pos, infinity);
+
+            let expected = Bls12381G2Signature::aggregate(&sigs, true);
+            assert_eq!(
+                Bls12381G2Signature::aggregate(&with_infinity, true),
+                expected
+            );
+            assert_eq!(
+                Bls12381G2Signature::aggregate_parallel(&with_infinity, true),
+                expected
+            );
+            let mut agg_sig = AggregateSignature::new();
+            with_infinity
+                .iter()
+                .try_for_each(|sig| agg_sig.add(sig))
+                .unwrap();
+            assert_eq!(agg_sig.finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn signature_aggregate_of_infinity_is_infinity() {
+        let infinity = Bls12381G2Signature(G2_POINT_AT_INFINITY);
+
+        assert_eq!(
+            Bls12381G2Signature::aggregate(&[infinity], true),
+            Ok(infinity)
+        );
+        assert_eq!(
+            Bls12381G2Signature::aggregate_parallel(&[infinity], true),
+            Ok(infinity)
+        );
+        let mut agg_sig = AggregateSignature::new();
+        agg_sig.add(&infinity).unwrap();
+        assert_eq!(agg_sig.finalize(), Ok(infinity));
+    }
+
+    #[test]
+    fn aggregate_verify_detailed() {
+        let (sks, pks, msgs, sigs) = get_aggregate_verify_test_data(5, 5, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let mut pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.into_iter().zip(msgs).collect();
+
+        assert_eq!(
+            aggregate_verify_bls12381_v1_detailed(&pub_keys_msgs, &agg_sig),
+            Ok(())
+        );
+        assert_eq!(
+            aggregate_verify_bls12381_v1_detailed(&pub_keys_msgs, &sigs[0]),
+            Err(VerifyError::PairingMismatch)
+        );
+        assert_eq!(
+            aggregate_verify_bls12381_v1_detailed(&pub_keys_msgs, &Bls12381G2Signature([0xff; 96])),
+            Err(VerifyError::InvalidSignature)
+        );
+        assert_eq!(
+            aggregate_verify_bls12381_v1_detailed::<Vec<u8>>(&[], &agg_sig),
+            Err(VerifyError::NoPublicKeysGiven)
+        );
+
+        pub_keys_msgs[3].0 = Bls12381G1PublicKey([0xff; 48]);
+        assert_eq!(
+            aggregate_verify_bls12381_v1_detailed(&pub_keys_msgs, &agg_sig),
+            Err(VerifyError::InvalidPublicKey(3))
+        );
+
+        let msg = vec![1u8; 32];
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = sks
+            .iter()
+            .map(|sk| (sk.public_key(), msg.clone()))
+            .collect();
+        let sigs: Vec<Bls12381G2Signature> = sks.iter().map(|sk| sk.sign_basic_v1(&msg)).collect();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        assert_eq!(
+            aggregate_verify_with_scheme_bls12381_v1_detailed(
+                &pub_keys_msgs,
+                &agg_sig,
+                Scheme::Basic
+            ),
+            Err(VerifyError::MessagesNotDistinct)
+        );
+    }
+
+    #[test]
+    fn fast_aggregate_verify_duplicate_signers() {
+        let (_sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(3, 32);
+        let modes = [
+            DuplicateSigners::Allow,
+            DuplicateSigners::Reject,
+            DuplicateSigners::Deduplicate,
+            DuplicateSigners::Multiplicity,
+        ];
+        // Infinity key below has no proof of possession, so verify keys unchecked
+        let verify = |pks: &[Bls12381G1PublicKey], sig: &Bls12381G2Signature| {
+            modes.map(|mode| {
+                fast_aggregate_verify_with_duplicates_bls12381_v1_unchecked(&msg, pks, sig, mode)
+            })
+        };
+
+        // No duplicates, all modes accept
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        assert_eq!(verify(&pks, &agg_sig), [true; 4]);
+
+        // Key repeated but signed only once, counting it twice must not pass
+        let dup_pks = [pks[0], pks[1], pks[2], pks[0]];
+        assert_eq!(verify(&dup_pks, &agg_sig), [false, false, true, false]);
+
+        // Signature repeated along with the key, ie. the same signer counted twice
+        let dup_sig =
+            Bls12381G2Signature::aggregate(&[sigs[0], sigs[1], sigs[2], sigs[0]], true).unwrap();
+        assert_eq!(verify(&dup_pks, &dup_sig), [true, false, false, true]);
+
+        // Key repeated many times
+        let dup_pks = [pks[1]; 7];
+        let dup_sig = Bls12381G2Signature::aggregate(&[sigs[1]; 7], true).unwrap();
+        assert_eq!(verify(&dup_pks, &dup_sig), [true, false, false, true]);
+        assert_eq!(verify(&dup_pks, &sigs[1]), [false, false, true, false]);
+
+        // Point at infinity adds nothing to the aggregate, every mode validates
+        // each key and rejects it at any position
+        let mut infinity_pk = [0u8; Bls12381G1PublicKey::LENGTH];
+        infinity_pk[0] = 0xc0;
+        for pos in 0..=pks.len() {
+            let mut keys = pks.clone();
+            keys.insert(pos, Bls12381G1PublicKey(infinity_pk));
+            assert_eq!(verify(&keys, &agg_sig), [false; 4]);
+        }
+
+        assert_eq!(verify(&[], &agg_sig), [false; 4]);
+    }
+
+    #[test]
+    fn private_key_from_bytes_errors() {
+        assert_eq!(
+            Bls12381G1PrivateKey::from_bytes(&[1u8; 31]).err(),
+            Some(BlsError::InvalidLength(31))
+        );
+        assert_eq!(
+            Bls12381G1PrivateKey::from_u64(0).err(),
+            Some(BlsError::ZeroKey)
+        );
+        // Greater than the curve order
+        assert_eq!(
+            Bls12381G1PrivateKey::from_bytes(&[0xff; 32]).err(),
+            Some(BlsError::InvalidPrivateKey)
+        );
+    }
+
+    #[test]
+    fn private_key_text_and_bytes_conversions() {
+        let test_sk = "408157791befddd702672dcfcfc99da3512f9c0ea818890fcb6ab749580ef2cf";
+        let sk = Bls12381G1PrivateKey::from_str(test_sk).unwrap();
+        assert_eq!(sk.to_hex_secret(), test_sk);
+
+        let bytes = hex::decode(test_sk).unwrap();
+        let sk2 = Bls12381G1PrivateKey::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(sk2.public_key(), sk.public_key());
+
+        assert_eq!(
+            Bls12381G1PrivateKey::from_str("not hex").err(),
+            Some(BlsError::InvalidHex(String::new()))
+        );
+        assert_eq!(
+            Bls12381G1PrivateKey::try_from(&bytes[1..]).err(),
+            Some(BlsError::InvalidLength(31))
+        );
+    }
+
+    #[test]
+    fn constant_time_eq() {
+        let sk1 = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let sk2 = Bls12381G1PrivateKey::from_u64(2).unwrap();
+        assert!(bool::from(
+            sk1.ct_eq(&Bls12381G1PrivateKey::from_u64(1).unwrap())
+        ));
+        assert!(!bool::from(sk1.ct_eq(&sk2)));
+        assert!(sk1 != sk2);
+
+        let sig1 = sk1.sign_v1(b"message");
+        let sig2 = sk2.sign_v1(b"message");
+        assert!(bool::from(sig1.ct_eq(&sk1.sign_v1(b"message"))));
+        assert!(!bool::from(sig1.ct_eq(&sig2)));
+    }
+
+    #[test]
+    fn uncompressed_encoding() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let sig = sk.sign_v1(b"message");
+
+        let pk_bytes = pk.to_uncompressed_bytes().unwrap();
+        assert_eq!(
+            Bls12381G1PublicKey::from_uncompressed_bytes(&pk_bytes),
+            Ok(pk)
+        );
+        let sig_bytes = sig.to_uncompressed_bytes().unwrap();
+        assert_eq!(
+            Bls12381G2Signature::from_uncompressed_bytes(&sig_bytes),
+            Ok(sig)
+        );
+
+        // Compressed encoding is not accepted
+        assert_eq!(
+            Bls12381G1PublicKey::from_uncompressed_bytes(&pk.0),
+            Err(BlsError::InvalidLength(48))
+        );
+        // Point not on the curve
+        let mut bad_pk_bytes = pk_bytes;
+        bad_pk_bytes[95] ^= 1;
+        assert_eq!(
+            Bls12381G1PublicKey::from_uncompressed_bytes(&bad_pk_bytes),
+            Err(BlsError::InvalidPoint)
+        );
+        let mut bad_sig_bytes = sig_bytes;
+        bad_sig_bytes[191] ^= 1;
+        assert_eq!(
+            Bls12381G2Signature::from_uncompressed_bytes(&bad_sig_bytes),
+            Err(BlsError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn public_key_and_signature_is_valid() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        assert!(sk.public_key().is_valid());
+        assert!(sk.sign_v1(b"message").is_valid());
+
+        assert!(!Bls12381G1PublicKey([0xff; 48]).is_valid());
+        assert!(!Bls12381G2Signature([0xff; 96]).is_valid());
+
+        // Point at infinity
+        let mut inf_pk = [0u8; 48];
+        inf_pk[0] = 0xc0;
+        assert!(!Bls12381G1PublicKey(inf_pk).is_valid());
+        let mut inf_sig = [0u8; 96];
+        inf_sig[0] = 0xc0;
+        assert!(!Bls12381G2Signature(inf_sig).is_valid());
+    }
+
+    #[test]
+    fn public_keys_as_map_keys_and_sorted_sets() {
+        let (_sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(5, 32);
+
+        let committee: std::collections::BTreeSet<Bls12381G1PublicKey> =
+            pks.iter().rev().copied().collect();
+        assert!(committee
+            .iter()
+            .zip(committee.iter().skip(1))
+            .all(|(a, b)| a < b));
+
+        let sigs_by_pk: std::collections::HashMap<Bls12381G1PublicKey, Bls12381G2Signature> =
+            pks.iter().copied().zip(sigs.iter().copied()).collect();
+        for (pk, sig) in &sigs_by_pk {
+            assert!(verify_bls12381_v1(&msg, pk, sig));
+            assert_eq!(pk.as_ref(), pk.as_bytes());
+            assert_eq!(sig.to_array().as_slice(), sig.as_ref());
+        }
+    }
+
+    #[test]
+    fn private_key_debug_is_redacted() {
+        let test_sk = "408157791befddd702672dcfcfc99da3512f9c0ea818890fcb6ab749580ef2cf";
+        let sk = Bls12381G1PrivateKey::from_str(test_sk).unwrap();
+
+        let debug = format!("{:?}", sk);
+        assert_eq!(debug, "Bls12381G1PrivateKey { fingerprint: 93b1aa75 }");
+        assert!(!debug.contains(&test_sk[..8]));
+    }
+
+    #[test]
+    fn blst_conversions() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let sig = sk.sign_v1(b"message");
+
+        let native_sk: SecretKey = Bls12381G1PrivateKey::from_u64(1).unwrap().into();
+        assert_eq!(Bls12381G1PrivateKey::from(native_sk), sk);
+
+        let native_pk = blst::min_pk::PublicKey::try_from(&pk).unwrap();
+        assert_eq!(Bls12381G1PublicKey::from(native_pk), pk);
+        let pk_point = blst::blst_p1_affine::try_from(&pk).unwrap();
+        assert_eq!(Bls12381G1PublicKey::from(&pk_point), pk);
+
+        let native_sig = blst::min_pk::Signature::try_from(&sig).unwrap();
+        assert_eq!(Bls12381G2Signature::from(native_sig), sig);
+        let sig_point = blst::blst_p2_affine::try_from(&sig).unwrap();
+        assert_eq!(Bls12381G2Signature::from(&sig_point), sig);
+
+        assert_eq!(
+            blst::blst_p2_affine::try_from(&Bls12381G2Signature([0xff; 96])).err(),
+            Some(BlsError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn test_private_keys_are_reproducible() {
+        let keys = get_test_private_keys_with_seed(3, None);
+        for (i, sk) in keys.iter().enumerate() {
+            assert!(*sk == Bls12381G1PrivateKey::from_u64(i as u64 + 1).unwrap());
+        }
+
+        let keys = get_test_private_keys_with_seed(3, Some(42));
+        assert!(keys == get_test_private_keys_with_seed(3, Some(42)));
+        assert!(keys != get_test_private_keys_with_seed(3, Some(43)));
+        assert!(keys[0] != keys[1]);
+
+        assert_eq!(
+            Bls12381G1PrivateKey::key_gen(&[0u8; 31]).err(),
+            Some(BlsError::InvalidLength(31))
+        );
+    }
+
+    #[test]
+    fn aggregate_verify_several_signers_per_message() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(6, 3, 32);
+        assert_eq!(msgs.len(), 3);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = pks
+            .iter()
+            .enumerate()
+            .map(|(i, pk)| (*pk, msgs[i % 3].clone()))
+            .collect();
+        assert!(aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
+
+        let msg_indexes = [1, 1, 1, 0];
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data_with_mapping(&msg_indexes, 32);
+        assert_eq!(msgs.len(), 2);
+        for (i, (pk, sig)) in pks.iter().zip(sigs.iter()).enumerate() {
+            assert!(verify_bls12381_v1(&msgs[msg_indexes[i]], pk, sig));
+        }
+    }
+
+    #[test]
+    fn dst_length_is_validated() {
+        assert_eq!(Bls12381Dst::new(&[]), Err(ParseBlsDstError::Empty));
+        assert_eq!(
+            Bls12381Dst::new(&[b'A'; Bls12381Dst::MAX_LENGTH + 1]),
+            Err(ParseBlsDstError::InvalidLength(Bls12381Dst::MAX_LENGTH + 1))
+        );
+        assert!(Bls12381Dst::new(&[b'A'; Bls12381Dst::MAX_LENGTH]).is_ok());
+    }
+
     #[test]
     fn sign_and_verify_aggregated_multiple_messages() {
         let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
 
         // Aggregate the signature
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
 
         let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
             pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
@@ -165,7 +1127,7 @@ mod tests {
         let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(1, 1, 10);
 
         // Aggregate the signature (in fact it does not make sense to aggregate one signature)
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
 
         // Aggregated signature of one signature must be the same
         assert_eq!(agg_sig, sigs[0]);
@@ -182,7 +1144,7 @@ mod tests {
         let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
 
         // Aggregate the signature
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
 
         let mut msgs_rev = msgs.clone();
         msgs_rev.reverse();
@@ -191,10 +1153,7 @@ mod tests {
             pks.iter().zip(msgs_rev).map(|(pk, sk)| (*pk, sk)).collect();
 
         // Verify the messages in reversed order against public keys and aggregated signature
-        assert_eq!(
-            aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig),
-            false
-        );
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
     }
 
     #[test]
@@ -202,7 +1161,7 @@ mod tests {
         let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
 
         // Aggregate the signature
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
 
         // Skip the last key and message tuple
         let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = pks
@@ -214,24 +1173,119 @@ mod tests {
 
         // Verify the incomplete messages against public keys and aggregated
         // signature from all messages
-        assert_eq!(
-            aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig),
-            false
-        );
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
 
         // Aggregate the signatures from incomplete messages
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs[0..9]).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs[0..9], true).unwrap();
         // Verify the incomplete messages against public keys and aggregated
         // signature from incomplete messages
         assert!(aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
     }
 
+    #[test]
+    fn aggregated_wrong_signature_rejected() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
+
+        // Valid signature, but of other messages
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs[1..], true).unwrap();
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig));
+
+        // Single signature instead of the aggregated one
+        assert!(!aggregate_verify_bls12381_v1(&pub_keys_msgs, &sigs[0]));
+    }
+
+    #[test]
+    fn aggregate_verify_phases_matches_aggregate_verify() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let other_sig = Bls12381G2Signature::aggregate(&sigs[1..], true).unwrap();
+        let mut infinity = [0u8; 96];
+        infinity[0] = 0xc0;
+
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
+        let mut invalid_pk_msgs = pub_keys_msgs.clone();
+        invalid_pk_msgs[3].0 = Bls12381G1PublicKey([0xff; 48]);
+
+        for (pub_keys_msgs, sig, expected) in [
+            (&pub_keys_msgs[..], agg_sig, true),
+            (&pub_keys_msgs[..], other_sig, false),
+            (&pub_keys_msgs[..], sigs[0], false),
+            (&pub_keys_msgs[..], Bls12381G2Signature(infinity), false),
+            (&pub_keys_msgs[..], Bls12381G2Signature([0xff; 96]), false),
+            (&pub_keys_msgs[..9], agg_sig, false),
+            (&invalid_pk_msgs[..], agg_sig, false),
+        ] {
+            assert_eq!(aggregate_verify_bls12381_v1(pub_keys_msgs, &sig), expected);
+            assert_eq!(
+                aggregate_verify_bls12381_v1_phases(pub_keys_msgs, &sig),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_multiple_aggregate_signatures() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+
+        let mut pub_keys_msgs_sigs: Vec<(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg, sig))
+            .collect();
+
+        assert!(verify_multiple_aggregate_signatures_bls12381_v1(
+            &pub_keys_msgs_sigs
+        ));
+
+        // Swap signatures of the first two triples
+        let sig = pub_keys_msgs_sigs[0].2;
+        pub_keys_msgs_sigs[0].2 = pub_keys_msgs_sigs[1].2;
+        pub_keys_msgs_sigs[1].2 = sig;
+
+        assert!(!verify_multiple_aggregate_signatures_bls12381_v1(
+            &pub_keys_msgs_sigs
+        ));
+    }
+
+    #[test]
+    fn sign_and_batch_verify() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 10, 10);
+
+        let mut pub_keys_msgs_sigs: Vec<(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg, sig))
+            .collect();
+
+        assert!(batch_verify_bls12381_v1(&pub_keys_msgs_sigs));
+        assert!(!batch_verify_bls12381_v1(&[]));
+        assert!(batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs));
+        assert!(batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs[..1]));
+        assert!(!batch_verify_bls12381_v1_msm(&[]));
+
+        // Swap signatures of the first two triples, aggregated signature stays the same
+        // but random scalars make the check fail
+        let sig = pub_keys_msgs_sigs[0].2;
+        pub_keys_msgs_sigs[0].2 = pub_keys_msgs_sigs[1].2;
+        pub_keys_msgs_sigs[1].2 = sig;
+
+        assert!(!batch_verify_bls12381_v1(&pub_keys_msgs_sigs));
+        assert!(!batch_verify_bls12381_v1_msm(&pub_keys_msgs_sigs));
+    }
+
     #[test]
     fn sign_and_verify_fast_aggregated() {
-        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(10, 1, 10);
+        let (sks, _pks, msgs, sigs) = get_aggregate_verify_test_data(10, 1, 10);
+        let pks = get_pop_verified_test_keys(&sks);
 
         // Aggregate the signature
-        let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
 
         // Verify the message against public keys and aggregated signature
         assert!(fast_aggregate_verify_bls12381_v1(
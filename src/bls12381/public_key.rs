@@ -1,8 +1,7 @@
+use crate::bls12381::{g1_affine_from_bytes, BlsError};
 use crate::copy_u8_array;
-use blst::{
-    min_pk::{AggregatePublicKey, PublicKey},
-    BLST_ERROR,
-};
+use blst::min_pk::{AggregatePublicKey as NativeAggregatePublicKey, PublicKey};
+use blst::{blst_p1_affine, BLST_ERROR};
 use std::fmt;
 use std::str::FromStr;
 
@@ -12,38 +11,152 @@ pub struct Bls12381G1PublicKey(pub [u8; Self::LENGTH]);
 
 impl Bls12381G1PublicKey {
     pub const LENGTH: usize = 48;
+    pub const UNCOMPRESSED_LENGTH: usize = 96;
 
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_vec()
     }
 
-    fn to_native_public_key(self) -> Result<PublicKey, ParseBlsPublicKeyError> {
+    pub fn to_array(&self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    fn to_native_public_key(self) -> Result<PublicKey, BlsError> {
         PublicKey::from_bytes(&self.0).map_err(|err| err.into())
     }
 
-    /// Aggregate multiple public keys into a single one
-    pub fn aggregate(public_keys: &[Bls12381G1PublicKey]) -> Result<Self, ParseBlsPublicKeyError> {
+    /// Checks that public key is a valid G1 point, is not infinity and is in the G1 subgroup,
+    /// ie. all checks done on public keys during verification, without verifying anything.
+    pub fn is_valid(&self) -> bool {
+        g1_affine_from_bytes(&self.0).is_some()
+    }
+
+    /// Returns uncompressed encoding of the public key (both point coordinates)
+    pub fn to_uncompressed_bytes(&self) -> Result<[u8; Self::UNCOMPRESSED_LENGTH], BlsError> {
+        Ok(self.to_native_public_key()?.serialize())
+    }
+
+    /// Parses public key from uncompressed encoding. Point is checked to be on the curve,
+    /// but (as for compressed encoding) subgroup check is done when the public key is used.
+    pub fn from_uncompressed_bytes(slice: &[u8]) -> Result<Self, BlsError> {
+        if slice.len() != Self::UNCOMPRESSED_LENGTH {
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+        let point = PublicKey::deserialize(slice)?;
+        Ok(Self(point.compress()))
+    }
+
+    /// Aggregate multiple public keys into a single one.
+    /// Every key is validated (KeyValidate: not infinity and in the G1 subgroup).
+    pub fn aggregate(public_keys: &[Bls12381G1PublicKey]) -> Result<Self, BlsError> {
+        Self::aggregate_internal(public_keys, true)
+    }
+
+    /// Aggregate multiple public keys into a single one without validation of the keys.
+    /// Use it only for keys that were already validated (eg. when registered).
+    pub fn aggregate_prevalidated(public_keys: &[Bls12381G1PublicKey]) -> Result<Self, BlsError> {
+        Self::aggregate_internal(public_keys, false)
+    }
+
+    fn aggregate_internal(
+        public_keys: &[Bls12381G1PublicKey],
+        validate: bool,
+    ) -> Result<Self, BlsError> {
         if !public_keys.is_empty() {
             let pk_first = public_keys[0].to_native_public_key()?;
+            if validate {
+                pk_first.validate()?;
+            }
 
-            let mut agg_pk = AggregatePublicKey::from_public_key(&pk_first);
+            let mut agg_pk = NativeAggregatePublicKey::from_public_key(&pk_first);
 
             for pk in public_keys.iter().skip(1) {
-                agg_pk.add_public_key(&pk.to_native_public_key()?, true)?;
+                agg_pk.add_public_key(&pk.to_native_public_key()?, validate)?;
             }
             Ok(Bls12381G1PublicKey(agg_pk.to_public_key().to_bytes()))
         } else {
-            Err(ParseBlsPublicKeyError::NoPublicKeysGiven)
+            Err(BlsError::NoPublicKeysGiven)
         }
     }
 }
 
+/// Aggregates public keys incrementally, so that aggregate of slowly changing signer set
+/// does not have to be recomputed from all the keys on every change.
+/// Added keys are kept, so that a key can be removed by recomputing the aggregate.
+#[derive(Default)]
+pub struct AggregatePublicKey {
+    public_keys: Vec<Bls12381G1PublicKey>,
+    agg_pk: Option<NativeAggregatePublicKey>,
+}
+
+impl AggregatePublicKey {
+    pub fn new() -> Self {
+        Self {
+            public_keys: vec![],
+            agg_pk: None,
+        }
+    }
+
+    /// Adds public key to the aggregate, public key is validated
+    pub fn add(&mut self, public_key: &Bls12381G1PublicKey) -> Result<(), BlsError> {
+        let pk = public_key.to_native_public_key()?;
+        match &mut self.agg_pk {
+            Some(agg_pk) => agg_pk.add_public_key(&pk, true)?,
+            None => {
+                pk.validate()?;
+                self.agg_pk = Some(NativeAggregatePublicKey::from_public_key(&pk));
+            }
+        }
+        self.public_keys.push(*public_key);
+        Ok(())
+    }
+
+    /// Removes (single occurrence of) public key and recomputes the aggregate from
+    /// the remaining keys, which were already validated when added.
+    /// Returns false if public key was not added before.
+    pub fn remove(&mut self, public_key: &Bls12381G1PublicKey) -> Result<bool, BlsError> {
+        let idx = match self.public_keys.iter().position(|pk| pk == public_key) {
+            Some(idx) => idx,
+            None => return Ok(false),
+        };
+        self.public_keys.remove(idx);
+
+        self.agg_pk = None;
+        for pk in &self.public_keys {
+            let pk = pk.to_native_public_key()?;
+            match &mut self.agg_pk {
+                Some(agg_pk) => agg_pk.add_public_key(&pk, false)?,
+                None => self.agg_pk = Some(NativeAggregatePublicKey::from_public_key(&pk)),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns aggregated public key, fails if there are no keys
+    pub fn finalize(&self) -> Result<Bls12381G1PublicKey, BlsError> {
+        match &self.agg_pk {
+            Some(agg_pk) => Ok(Bls12381G1PublicKey(agg_pk.to_public_key().to_bytes())),
+            None => Err(BlsError::NoPublicKeysGiven),
+        }
+    }
+}
+
+impl AsRef<[u8]> for Bls12381G1PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl TryFrom<&[u8]> for Bls12381G1PublicKey {
-    type Error = ParseBlsPublicKeyError;
+    type Error = BlsError;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
         if slice.len() != Bls12381G1PublicKey::LENGTH {
-            return Err(ParseBlsPublicKeyError::InvalidLength(slice.len()));
+            return Err(BlsError::InvalidLength(slice.len()));
         }
 
         Ok(Bls12381G1PublicKey(copy_u8_array(slice)))
@@ -51,33 +164,44 @@ impl TryFrom<&[u8]> for Bls12381G1PublicKey {
 }
 
 //======
-// error
+// blst
 //======
 
-impl From<BLST_ERROR> for ParseBlsPublicKeyError {
-    fn from(error: BLST_ERROR) -> Self {
-        let err_msg = format!("{:?}", error);
-        Self::BlsError(err_msg)
+impl From<PublicKey> for Bls12381G1PublicKey {
+    fn from(public_key: PublicKey) -> Self {
+        Self(public_key.compress())
+    }
+}
+
+impl TryFrom<&Bls12381G1PublicKey> for PublicKey {
+    type Error = BlsError;
+
+    /// Decompresses the point, it is not validated (see PublicKey::validate)
+    fn try_from(public_key: &Bls12381G1PublicKey) -> Result<Self, Self::Error> {
+        PublicKey::from_bytes(&public_key.0).map_err(|err| err.into())
     }
 }
 
-/// Represents an error when retrieving BLS public key from hex or when aggregating.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ParseBlsPublicKeyError {
-    InvalidHex(String),
-    InvalidLength(usize),
-    NoPublicKeysGiven,
-    // Error returned by underlying BLS library
-    BlsError(String),
+impl From<&blst_p1_affine> for Bls12381G1PublicKey {
+    fn from(point: &blst_p1_affine) -> Self {
+        let mut bytes = [0u8; Self::LENGTH];
+        unsafe {
+            blst::blst_p1_affine_compress(bytes.as_mut_ptr(), point);
+        }
+        Self(bytes)
+    }
 }
 
-#[cfg(not(feature = "alloc"))]
-impl std::error::Error for ParseBlsPublicKeyError {}
+impl TryFrom<&Bls12381G1PublicKey> for blst_p1_affine {
+    type Error = BlsError;
 
-#[cfg(not(feature = "alloc"))]
-impl fmt::Display for ParseBlsPublicKeyError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+    /// Decompresses the point, it is not checked to be in the G1 subgroup
+    fn try_from(public_key: &Bls12381G1PublicKey) -> Result<Self, Self::Error> {
+        let mut point = blst_p1_affine::default();
+        match unsafe { blst::blst_p1_uncompress(&mut point, public_key.0.as_ptr()) } {
+            BLST_ERROR::BLST_SUCCESS => Ok(point),
+            err => Err(err.into()),
+        }
     }
 }
 
@@ -86,17 +210,17 @@ impl fmt::Display for ParseBlsPublicKeyError {
 //======
 
 impl FromStr for Bls12381G1PublicKey {
-    type Err = ParseBlsPublicKeyError;
+    type Err = BlsError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = hex::decode(s).map_err(|_| ParseBlsPublicKeyError::InvalidHex(s.to_owned()))?;
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(s.to_owned()))?;
         Self::try_from(bytes.as_slice())
     }
 }
 
 impl fmt::Display for Bls12381G1PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", hex::encode(self.to_vec()))
+        write!(f, "{}", hex::encode(self.0))
     }
 }
 
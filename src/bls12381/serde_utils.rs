@@ -0,0 +1,139 @@
+use super::*;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Serializes bytes as hex string for human-readable formats (eg. JSON)
+/// and as raw bytes for binary formats.
+pub(crate) fn serialize_bytes<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Counterpart of serialize_bytes, hex string may be 0x prefixed (as in Ethereum JSON APIs)
+pub(crate) fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.trim_start_matches("0x"))
+            .map_err(|_| D::Error::custom(BlsError::InvalidHex(s)))
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "byte array")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    // Some binary formats encode bytes as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+impl Serialize for Bls12381G1PrivateKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bls12381G1PrivateKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        Self::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Bls12381G1PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bls12381G1PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        Self::try_from(bytes.as_slice()).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Bls12381G2Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bls12381G2Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        Self::try_from(bytes.as_slice()).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_json_roundtrip() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        let pk = sk.public_key();
+        let sig = sk.sign_v1(b"message");
+
+        let json = serde_json::to_string(&pk).unwrap();
+        assert_eq!(json, format!("\"{}\"", pk));
+        assert_eq!(
+            serde_json::from_str::<Bls12381G1PublicKey>(&json).unwrap(),
+            pk
+        );
+
+        let json = serde_json::to_string(&sig).unwrap();
+        assert_eq!(json, format!("\"{}\"", sig));
+        assert_eq!(
+            serde_json::from_str::<Bls12381G2Signature>(&json).unwrap(),
+            sig
+        );
+
+        let json = serde_json::to_string(&sk).unwrap();
+        assert_eq!(json, format!("\"{}\"", hex::encode(sk.to_bytes())));
+        let sk2: Bls12381G1PrivateKey = serde_json::from_str(&json).unwrap();
+        assert!(sk2 == sk);
+    }
+
+    #[test]
+    fn serde_json_invalid_input() {
+        assert!(serde_json::from_str::<Bls12381G1PublicKey>("\"zz\"").is_err());
+        assert!(serde_json::from_str::<Bls12381G1PublicKey>("\"0102\"").is_err());
+        assert!(serde_json::from_str::<Bls12381G2Signature>("\"\"").is_err());
+        assert!(serde_json::from_str::<Bls12381G1PrivateKey>(&format!(
+            "\"{}\"",
+            hex::encode([0u8; 32])
+        ))
+        .is_err());
+    }
+}
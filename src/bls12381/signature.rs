@@ -1,13 +1,13 @@
 use crate::*;
-use blst::{
-    min_pk::{AggregateSignature, Signature},
-    BLST_ERROR,
-};
+use blst::min_pk::{AggregateSignature as NativeAggregateSignature, Signature};
+use blst::{blst_p2_affine, BLST_ERROR};
+use rayon::prelude::*;
 use std::borrow::ToOwned;
 use std::fmt;
 use std::str::FromStr;
 use std::string::String;
 use std::vec::Vec;
+use subtle::{Choice, ConstantTimeEq};
 
 /// BLS12-381 ciphersuite v1
 /// It has following parameters
@@ -17,47 +17,241 @@ use std::vec::Vec;
 ///    - signature variant: G2 minimal pubkey size
 ///  - scheme:
 ///    - proof-of-possession
+///
 /// More details: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-04
 pub const BLS12381_CIPHERSITE_V1: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
+/// BLS12-381 ciphersuite v1 for G1 signature variant (minimal signature size)
+/// Same parameters as BLS12381_CIPHERSITE_V1 but hash-to-curve: BLS12381G1_XMD:SHA-256_SSWU_RO
+pub const BLS12381G1_CIPHERSITE_V1: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// BLS12-381 proof-of-possession ciphersuite v1
+/// Same parameters as BLS12381_CIPHERSITE_V1, used to prove and verify possession
+/// of the private key (signature over own public key).
+/// More details: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-04#section-4.2.3
+pub const BLS12381_POP_CIPHERSITE_V1: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// BLS12-381 basic ciphersuite v1
+/// Same parameters as BLS12381_CIPHERSITE_V1 but scheme: basic,
+/// ie. messages in aggregate verification must be distinct.
+/// More details: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-04#section-3.1
+pub const BLS12381_BASIC_CIPHERSITE_V1: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// BLS12-381 message augmentation ciphersuite v1
+/// Same parameters as BLS12381_CIPHERSITE_V1 but scheme: message augmentation,
+/// ie. public key of the signer is prepended to the message before hashing.
+/// More details: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-04#section-3.2
+pub const BLS12381_AUG_CIPHERSITE_V1: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+
+/// BLS signature scheme, determines domain separation tag and how messages are hashed.
+/// More details: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-04#section-3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// Messages in aggregate verification must be distinct
+    Basic,
+    /// Message is prepended with the public key of the signer
+    Aug,
+    /// Rogue key attacks are prevented by proof of possession of the public keys
+    #[default]
+    Pop,
+}
+
+impl Scheme {
+    /// Ciphersuite domain separation tag of the scheme
+    pub fn dst(&self) -> &'static [u8] {
+        match self {
+            Scheme::Basic => BLS12381_BASIC_CIPHERSITE_V1,
+            Scheme::Aug => BLS12381_AUG_CIPHERSITE_V1,
+            Scheme::Pop => BLS12381_CIPHERSITE_V1,
+        }
+    }
+}
+
+/// Domain separation tag used to hash messages to curve points.
+/// Allows signing and verification with tags other than BLS12381_CIPHERSITE_V1,
+/// eg. to interoperate with deployments using a different ciphersuite tag.
+/// More details: https://datatracker.ietf.org/doc/html/rfc9380#section-3.1
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Bls12381Dst(Vec<u8>);
+
+impl Bls12381Dst {
+    /// Tags longer than this must be hashed first (H2C-OVERSIZE-DST), which is not supported
+    pub const MAX_LENGTH: usize = 255;
+
+    pub fn new(dst: &[u8]) -> Result<Self, ParseBlsDstError> {
+        if dst.is_empty() {
+            return Err(ParseBlsDstError::Empty);
+        }
+        if dst.len() > Self::MAX_LENGTH {
+            return Err(ParseBlsDstError::InvalidLength(dst.len()));
+        }
+        Ok(Self(dst.to_vec()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Default for Bls12381Dst {
+    fn default() -> Self {
+        Self(BLS12381_CIPHERSITE_V1.to_vec())
+    }
+}
+
 /// Represents a BLS12-381 G2 signature (variant with 96-byte signature and 48-byte public key)
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Bls12381G2Signature(pub [u8; Self::LENGTH]);
 
 impl Bls12381G2Signature {
     pub const LENGTH: usize = 96;
+    pub const UNCOMPRESSED_LENGTH: usize = 192;
+    /// Number of signatures aggregated serially by single task in aggregate_parallel
+    pub const AGGREGATE_PARALLEL_CHUNK_SIZE: usize = 32;
 
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_vec()
     }
 
-    fn to_native_signature(self) -> Result<Signature, ParseBlsSignatureError> {
+    pub fn to_array(&self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    fn to_native_signature(self) -> Result<Signature, BlsError> {
         Signature::from_bytes(&self.0).map_err(|err| err.into())
     }
 
-    /// Aggregate multiple signatures into a single one
-    pub fn aggregate(signatures: &[Bls12381G2Signature]) -> Result<Self, ParseBlsSignatureError> {
+    /// Same as to_native_signature, if `validate` is set the signature is also checked
+    /// to be in the G2 subgroup. Infinity is accepted, as by Aggregate of the IETF draft.
+    fn to_validated_native_signature(self, validate: bool) -> Result<Signature, BlsError> {
+        let sig = self.to_native_signature()?;
+        if validate {
+            sig.validate(false)?;
+        }
+        Ok(sig)
+    }
+
+    /// Checks that signature is a valid G2 point, is not infinity and is in the G2 subgroup,
+    /// ie. all checks done on signatures during verification, without verifying anything.
+    pub fn is_valid(&self) -> bool {
+        g2_affine_from_bytes(&self.0).is_some()
+    }
+
+    /// Returns uncompressed encoding of the signature (both point coordinates)
+    pub fn to_uncompressed_bytes(&self) -> Result<[u8; Self::UNCOMPRESSED_LENGTH], BlsError> {
+        Ok(self.to_native_signature()?.serialize())
+    }
+
+    /// Parses signature from uncompressed encoding. Point is checked to be on the curve,
+    /// but (as for compressed encoding) subgroup check is done when the signature is used.
+    pub fn from_uncompressed_bytes(slice: &[u8]) -> Result<Self, BlsError> {
+        if slice.len() != Self::UNCOMPRESSED_LENGTH {
+            return Err(BlsError::InvalidLength(slice.len()));
+        }
+        let point = Signature::deserialize(slice)?;
+        Ok(Self(point.compress()))
+    }
+
+    /// Aggregate multiple signatures into a single one.
+    /// If `validate` is set then each signature is checked to be in the G2 subgroup
+    /// (infinity is accepted, it adds nothing to the aggregate), otherwise signatures
+    /// are trusted (use it only for already validated signatures).
+    pub fn aggregate(signatures: &[Bls12381G2Signature], validate: bool) -> Result<Self, BlsError> {
         if !signatures.is_empty() {
-            let sig_first = signatures[0].to_native_signature()?;
+            let sig_first = signatures[0].to_validated_native_signature(validate)?;
 
-            let mut agg_sig = AggregateSignature::from_signature(&sig_first);
+            let mut agg_sig = NativeAggregateSignature::from_signature(&sig_first);
 
             for sig in signatures.iter().skip(1) {
-                agg_sig.add_signature(&sig.to_native_signature()?, true)?;
+                agg_sig.add_signature(&sig.to_validated_native_signature(validate)?, false)?;
             }
             Ok(Bls12381G2Signature(agg_sig.to_signature().to_bytes()))
         } else {
-            Err(ParseBlsSignatureError::NoSignatureGiven)
+            Err(BlsError::NoSignatureGiven)
         }
     }
+
+    /// Aggregate multiple signatures into a single one, chunks of the signatures are
+    /// aggregated in parallel and the results are combined pairwise (tree reduction).
+    /// Equivalent to aggregate with the same validate flag.
+    pub fn aggregate_parallel(
+        signatures: &[Bls12381G2Signature],
+        validate: bool,
+    ) -> Result<Self, BlsError> {
+        let agg_sig = signatures
+            .par_chunks(Self::AGGREGATE_PARALLEL_CHUNK_SIZE)
+            .map(|chunk| {
+                let sig_first = chunk[0].to_validated_native_signature(validate)?;
+                let mut agg_sig = NativeAggregateSignature::from_signature(&sig_first);
+
+                for sig in chunk.iter().skip(1) {
+                    agg_sig.add_signature(&sig.to_validated_native_signature(validate)?, false)?;
+                }
+                Ok::<_, BlsError>(agg_sig)
+            })
+            .try_reduce_with(|mut a, b| {
+                a.add_aggregate(&b);
+                Ok(a)
+            })
+            .ok_or(BlsError::NoSignatureGiven)??;
+
+        Ok(Bls12381G2Signature(agg_sig.to_signature().to_bytes()))
+    }
+}
+
+/// Aggregates signatures incrementally, eg. as they arrive over the network,
+/// instead of collecting them into a slice for Bls12381G2Signature::aggregate
+#[derive(Default)]
+pub struct AggregateSignature(Option<NativeAggregateSignature>);
+
+impl AggregateSignature {
+    pub fn new() -> Self {
+        Self(None)
+    }
+
+    /// Adds signature to the aggregate, signature is checked to be in the G2 subgroup
+    pub fn add(&mut self, signature: &Bls12381G2Signature) -> Result<(), BlsError> {
+        let sig = signature.to_validated_native_signature(true)?;
+        match &mut self.0 {
+            Some(agg_sig) => agg_sig.add_signature(&sig, false)?,
+            None => self.0 = Some(NativeAggregateSignature::from_signature(&sig)),
+        }
+        Ok(())
+    }
+
+    /// Returns aggregated signature, fails if no signature was added
+    pub fn finalize(self) -> Result<Bls12381G2Signature, BlsError> {
+        match self.0 {
+            Some(agg_sig) => Ok(Bls12381G2Signature(agg_sig.to_signature().to_bytes())),
+            None => Err(BlsError::NoSignatureGiven),
+        }
+    }
+}
+
+/// Compares signatures in constant time, use it instead of == when comparing
+/// against the expected signature (eg. MAC-like usage of deterministic signatures)
+impl ConstantTimeEq for Bls12381G2Signature {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+impl AsRef<[u8]> for Bls12381G2Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 impl TryFrom<&[u8]> for Bls12381G2Signature {
-    type Error = ParseBlsSignatureError;
+    type Error = BlsError;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
         if slice.len() != Bls12381G2Signature::LENGTH {
-            return Err(ParseBlsSignatureError::InvalidLength(slice.len()));
+            return Err(BlsError::InvalidLength(slice.len()));
         }
 
         Ok(Bls12381G2Signature(copy_u8_array(slice)))
@@ -68,48 +262,94 @@ impl TryFrom<&[u8]> for Bls12381G2Signature {
 // error
 //======
 
-impl From<BLST_ERROR> for ParseBlsSignatureError {
-    fn from(error: BLST_ERROR) -> Self {
-        let err_msg = format!("{:?}", error);
-        Self::BlsError(err_msg)
-    }
-}
-
-/// Represents an error when retrieving BLS signature from hex or when aggregating.
+/// Represents an error when creating domain separation tag.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ParseBlsSignatureError {
-    InvalidHex(String),
+pub enum ParseBlsDstError {
+    Empty,
     InvalidLength(usize),
-    NoSignatureGiven,
-    // Error returned by underlying BLS library
-    BlsError(String),
 }
 
 #[cfg(not(feature = "alloc"))]
-impl std::error::Error for ParseBlsSignatureError {}
+impl std::error::Error for ParseBlsDstError {}
 
-impl fmt::Display for ParseBlsSignatureError {
+impl fmt::Display for ParseBlsDstError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+//======
+// blst
+//======
+
+impl From<Signature> for Bls12381G2Signature {
+    fn from(signature: Signature) -> Self {
+        Self(signature.compress())
+    }
+}
+
+impl TryFrom<&Bls12381G2Signature> for Signature {
+    type Error = BlsError;
+
+    /// Decompresses the point, it is not validated (see Signature::validate)
+    fn try_from(signature: &Bls12381G2Signature) -> Result<Self, Self::Error> {
+        Signature::from_bytes(&signature.0).map_err(|err| err.into())
+    }
+}
+
+impl From<&blst_p2_affine> for Bls12381G2Signature {
+    fn from(point: &blst_p2_affine) -> Self {
+        let mut bytes = [0u8; Self::LENGTH];
+        unsafe {
+            blst::blst_p2_affine_compress(bytes.as_mut_ptr(), point);
+        }
+        Self(bytes)
+    }
+}
+
+impl TryFrom<&Bls12381G2Signature> for blst_p2_affine {
+    type Error = BlsError;
+
+    /// Decompresses the point, it is not checked to be in the G2 subgroup
+    fn try_from(signature: &Bls12381G2Signature) -> Result<Self, Self::Error> {
+        let mut point = blst_p2_affine::default();
+        match unsafe { blst::blst_p2_uncompress(&mut point, signature.0.as_ptr()) } {
+            BLST_ERROR::BLST_SUCCESS => Ok(point),
+            err => Err(err.into()),
+        }
+    }
+}
+
 //======
 // text
 //======
 
 impl FromStr for Bls12381G2Signature {
-    type Err = ParseBlsSignatureError;
+    type Err = BlsError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = hex::decode(s).map_err(|_| ParseBlsSignatureError::InvalidHex(s.to_owned()))?;
+        let bytes = hex::decode(s).map_err(|_| BlsError::InvalidHex(s.to_owned()))?;
         Self::try_from(bytes.as_slice())
     }
 }
 
+impl FromStr for Bls12381Dst {
+    type Err = ParseBlsDstError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.as_bytes())
+    }
+}
+
+impl fmt::Debug for Bls12381Dst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", String::from_utf8_lossy(&self.0))
+    }
+}
+
 impl fmt::Display for Bls12381G2Signature {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", hex::encode(self.to_vec()))
+        write!(f, "{}", hex::encode(self.0))
     }
 }
 
@@ -1,5 +1,37 @@
 use super::*;
 use crate::perf;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Size in bits of the random scalars used to combine signatures in batch verification
+pub const BATCH_VERIFY_RAND_BITS: usize = 64;
+
+/// Represents a reason of the aggregated signature verification failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    // Signature is not a valid G2 point, is infinity or is not in the G2 subgroup
+    InvalidSignature,
+    // Public key at given index is not a valid G1 point, is infinity or is not in the G1 subgroup
+    InvalidPublicKey(usize),
+    // No public keys and messages given
+    NoPublicKeysGiven,
+    // Messages repeat, which is not allowed in the basic scheme
+    MessagesNotDistinct,
+    // Hashing message at given index to curve failed
+    HashToCurveFailed(usize),
+    // All inputs are valid, but signature does not match them
+    PairingMismatch,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for VerifyError {}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
 
 /// Performs BLS12-381 G2 signature verification.
 /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
@@ -8,56 +40,245 @@ pub fn verify_bls12381_v1(
     public_key: &Bls12381G1PublicKey,
     signature: &Bls12381G2Signature,
 ) -> bool {
-    if let Ok(sig) = blst::min_pk::Signature::from_bytes(&signature.0) {
-        if let Ok(pk) = blst::min_pk::PublicKey::from_bytes(&public_key.0) {
-            let result = sig.verify(true, message, BLS12381_CIPHERSITE_V1, &[], &pk, true);
+    verify_with_aug_bls12381_v1(message, public_key, signature, None)
+}
+
+/// Performs BLS12-381 G2 signature verification of the message prepended with
+/// optional augmentation data.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_with_aug_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+    aug: Option<&[u8]>,
+) -> bool {
+    verify_internal(
+        message,
+        public_key,
+        signature,
+        BLS12381_CIPHERSITE_V1,
+        aug.unwrap_or_default(),
+    )
+}
+
+/// Performs BLS12-381 G2 signature verification according to given scheme.
+pub fn verify_with_scheme_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+    scheme: Scheme,
+) -> bool {
+    let aug: &[u8] = match scheme {
+        Scheme::Aug => &public_key.0,
+        Scheme::Basic | Scheme::Pop => &[],
+    };
+    verify_internal(message, public_key, signature, scheme.dst(), aug)
+}
 
-            match result {
-                blst::BLST_ERROR::BLST_SUCCESS => return true,
-                _ => return false,
+/// Performs BLS12-381 G2 signature verification using given domain separation tag
+/// instead of BLS12381_CIPHERSITE_V1.
+pub fn verify_with_dst_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+    dst: &Bls12381Dst,
+) -> bool {
+    verify_internal(message, public_key, signature, dst.as_bytes(), &[])
+}
+
+/// Points are decoded on the stack and blst_core_verify_pk_in_g1 keeps its pairing context
+/// on the stack too, so (unlike blst::min_pk::Signature::verify, which concatenates aug and
+/// message and allocates pairing context) single verification does not allocate.
+fn verify_internal(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+    dst: &[u8],
+    aug: &[u8],
+) -> bool {
+    let (pk, sig) = match (
+        g1_affine_from_bytes(&public_key.0),
+        g2_affine_from_bytes(&signature.0),
+    ) {
+        (Some(pk), Some(sig)) => (pk, sig),
+        _ => return false,
+    };
+
+    unsafe {
+        blst::blst_core_verify_pk_in_g1(
+            &pk,
+            &sig,
+            true,
+            message.as_ptr(),
+            message.len(),
+            dst.as_ptr(),
+            dst.len(),
+            aug.as_ptr(),
+            aug.len(),
+        ) == blst::BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+/// Optional checks and hashing mode of the signature verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyFlags {
+    /// Hash message to curve (true) or encode it to curve (false, nonuniform encoding)
+    pub hash_or_encode: bool,
+    /// Check that public key is not infinity and is in the G1 subgroup
+    pub pk_validate: bool,
+    /// Check that signature is in the G2 subgroup
+    pub sig_groupcheck: bool,
+}
+
+impl VerifyFlags {
+    /// All combinations of the flags
+    pub fn all() -> Vec<VerifyFlags> {
+        let mut flags = vec![];
+        for hash_or_encode in [true, false] {
+            for pk_validate in [true, false] {
+                for sig_groupcheck in [true, false] {
+                    flags.push(VerifyFlags {
+                        hash_or_encode,
+                        pk_validate,
+                        sig_groupcheck,
+                    });
+                }
             }
         }
+        flags
     }
+}
 
-    false
+impl Default for VerifyFlags {
+    /// Flags used by verify_bls12381_v1
+    fn default() -> Self {
+        Self {
+            hash_or_encode: true,
+            pk_validate: true,
+            sig_groupcheck: true,
+        }
+    }
 }
 
-/// Local implementation of aggregated verify for no_std and WASM32 variants (no threads)
-/// see: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#name-coreaggregateverify
-/// Inspired with blst::min_pk::Signature::aggregate_verify
-fn aggregate_verify_bls12381_v1_no_threads(
-    pub_keys_and_msgs: &[(Bls12381G1PublicKey, Vec<u8>)],
-    signature: blst::min_pk::Signature,
+/// Performs BLS12-381 G2 signature verification with given optional checks.
+/// Skip the checks only for keys and signatures that were already validated.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_with_flags_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+    flags: VerifyFlags,
 ) -> bool {
-    // Below structs are copies of PublicKey and Signature
-    // Redefining them to be able to access point field, which is private for PublicKey and Signature
-    struct LocalPublicKey {
-        point: blst::blst_p1_affine,
+    let mut pk = blst::blst_p1_affine::default();
+    let mut sig = blst::blst_p2_affine::default();
+    unsafe {
+        if blst::blst_p1_uncompress(&mut pk, public_key.0.as_ptr())
+            != blst::BLST_ERROR::BLST_SUCCESS
+            || blst::blst_p2_uncompress(&mut sig, signature.0.as_ptr())
+                != blst::BLST_ERROR::BLST_SUCCESS
+        {
+            return false;
+        }
+        if flags.pk_validate
+            && (blst::blst_p1_affine_is_inf(&pk) || !blst::blst_p1_affine_in_g1(&pk))
+        {
+            return false;
+        }
+        if flags.sig_groupcheck && !blst::blst_p2_affine_in_g2(&sig) {
+            return false;
+        }
+
+        blst::blst_core_verify_pk_in_g1(
+            &pk,
+            &sig,
+            flags.hash_or_encode,
+            message.as_ptr(),
+            message.len(),
+            BLS12381_CIPHERSITE_V1.as_ptr(),
+            BLS12381_CIPHERSITE_V1.len(),
+            [].as_ptr(),
+            0,
+        ) == blst::BLST_ERROR::BLST_SUCCESS
     }
-    struct LocalSignature {
-        point: blst::blst_p2_affine,
+}
+
+/// Performs BLS12-381 proof of possession verification, ie. checks that
+/// proof is a valid signature of the public key itself.
+/// Domain specifier tag: BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn pop_verify_bls12381_v1(
+    public_key: &Bls12381G1PublicKey,
+    proof: &Bls12381G2Signature,
+) -> bool {
+    if let Ok(sig) = blst::min_pk::Signature::from_bytes(&proof.0) {
+        if let Ok(pk) = blst::min_pk::PublicKey::from_bytes(&public_key.0) {
+            let result = sig.verify(
+                true,
+                &public_key.0,
+                BLS12381_POP_CIPHERSITE_V1,
+                &[],
+                &pk,
+                true,
+            );
+
+            return matches!(result, blst::BLST_ERROR::BLST_SUCCESS);
+        }
     }
+
+    false
+}
+
+/// Same as aggregate_verify_bls12381_v1, but public key parsing and validation, pairing
+/// aggregation, commit, signature subgroup check and final verification are measured
+/// separately, so that they can be compared with the calc model. Meant for measurements only,
+/// aggregate_verify_bls12381_v1 shall be used to verify signatures.
+/// see: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#name-coreaggregateverify
+pub fn aggregate_verify_bls12381_v1_phases<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    let signature = match perf!("sig_from_bytes", blst::blst_p2_affine::try_from(signature)) {
+        (Ok(sig), _) => sig,
+        (Err(_), _) => return false,
+    };
+
     let mut pairing = blst::Pairing::new(true, BLS12381_CIPHERSITE_V1);
 
+    // Parse public keys
+    let (pks, _) = perf!(
+        "pk_from_bytes",
+        pub_keys_and_msgs
+            .iter()
+            .map(|(pk, _)| blst::blst_p1_affine::try_from(pk))
+            .collect::<Result<Vec<blst::blst_p1_affine>, BlsError>>()
+    );
+    let pks = match pks {
+        Ok(pks) => pks,
+        Err(_) => return false,
+    };
+
+    // Validate public keys (infinity and subgroup check), so it is not repeated when aggregating
+    let (result, _) = perf!(
+        "pk_validate",
+        pks.iter().all(|pk| unsafe {
+            !blst::blst_p1_affine_is_inf(pk) && blst::blst_p1_affine_in_g1(pk)
+        })
+    );
+    if !result {
+        return false;
+    }
+
     // Aggregate
     let (result, _) = perf!("pairing_aggregate", {
-        for (pk, msg) in pub_keys_and_msgs.iter() {
-            if let Ok(pk) = blst::min_pk::PublicKey::from_bytes(&pk.0) {
-                // transmute to LocalPublicKey to access point field
-                let local_pk: LocalPublicKey = unsafe { core::mem::transmute(pk) };
-
-                if pairing.aggregate(
-                    &local_pk.point,
-                    true,
-                    &unsafe { core::ptr::null::<blst::blst_p2_affine>().as_ref() },
-                    false,
-                    msg,
-                    &[],
-                ) != blst::BLST_ERROR::BLST_SUCCESS
-                {
-                    return false;
-                }
-            } else {
+        for (pk, (_, msg)) in pks.iter().zip(pub_keys_and_msgs.iter()) {
+            if pairing.aggregate(
+                pk,
+                false,
+                &None::<&blst::blst_p2_affine>,
+                false,
+                msg.as_ref(),
+                &[],
+            ) != blst::BLST_ERROR::BLST_SUCCESS
+            {
                 return false;
             }
         }
@@ -69,50 +290,40 @@ fn aggregate_verify_bls12381_v1_no_threads(
 
     let _ = perf!("pairing_commit", pairing.commit());
 
-    let (result, _) = perf!(
-        "validate",
-        if let Err(_err) = signature.validate(false) {
-            false
-        } else {
-            true
-        }
-    );
+    let (result, _) = perf!("validate", unsafe {
+        !blst::blst_p2_affine_is_inf(&signature) && blst::blst_p2_affine_in_g2(&signature)
+    });
     if !result {
         return false;
     };
 
-    // transmute to LocalSignature to access point field
-
     let (gtsig, _) = perf!("pairing_aggregated", {
-        let local_sig: LocalSignature = unsafe { core::mem::transmute(signature) };
         let mut gtsig = blst::blst_fp12::default();
-        blst::Pairing::aggregated(&mut gtsig, &local_sig.point);
+        blst::Pairing::aggregated(&mut gtsig, &signature);
         gtsig
     });
 
-    let _ = perf!("pairing_verify", pairing.finalverify(Some(&gtsig)));
-    true
+    let (result, _) = perf!("pairing_verify", pairing.finalverify(Some(&gtsig)));
+    result
 }
 
 /// Performs BLS12-381 G2 aggregated signature verification of
 /// multiple messages each signed with different key.
+/// Messages may be owned or borrowed, so callers do not have to copy them.
 /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
-pub fn aggregate_verify_bls12381_v1(
-    pub_keys_and_msgs: &[(Bls12381G1PublicKey, Vec<u8>)],
+pub fn aggregate_verify_bls12381_v1<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
     signature: &Bls12381G2Signature,
 ) -> bool {
-    if let (Ok(sig), _) = perf!(
-        "sig_from_bytes",
-        blst::min_pk::Signature::from_bytes(&signature.0)
-    ) {
-        aggregate_verify_bls12381_v1_no_threads(pub_keys_and_msgs, sig)
-    } else {
-        false
-    }
+    aggregate_verify_bls12381_v1_iter_detailed(
+        pub_keys_and_msgs.iter().map(|(pk, msg)| (pk, msg.as_ref())),
+        signature,
+    )
+    .is_ok()
 }
 
-pub fn aggregate_verify_bls12381_v1_threaded(
-    pub_keys_and_msgs: &[(Bls12381G1PublicKey, Vec<u8>)],
+pub fn aggregate_verify_bls12381_v1_threaded<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
     signature: &Bls12381G2Signature,
 ) -> bool {
     if let Ok(sig) = blst::min_pk::Signature::from_bytes(&signature.0) {
@@ -125,7 +336,7 @@ pub fn aggregate_verify_bls12381_v1_threaded(
                 } else {
                     return false;
                 }
-                msg_refs.push(msg.as_slice());
+                msg_refs.push(msg.as_ref());
             }
             let pks_refs: Vec<&blst::min_pk::PublicKey> = pks.iter().collect();
 
@@ -139,35 +350,691 @@ pub fn aggregate_verify_bls12381_v1_threaded(
     }
 }
 
+/// Performs BLS12-381 G2 signature verification in message augmentation scheme,
+/// ie. signature is over the public key prepended to the message.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_
+pub fn verify_aug_bls12381_v1(
+    message: &[u8],
+    public_key: &Bls12381G1PublicKey,
+    signature: &Bls12381G2Signature,
+) -> bool {
+    verify_with_scheme_bls12381_v1(message, public_key, signature, Scheme::Aug)
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification according to given scheme.
+/// In basic scheme all messages must be distinct, otherwise verification fails.
+pub fn aggregate_verify_with_scheme_bls12381_v1<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+    scheme: Scheme,
+) -> bool {
+    match scheme {
+        Scheme::Basic | Scheme::Aug => {
+            aggregate_verify_with_scheme_bls12381_v1_detailed(pub_keys_and_msgs, signature, scheme)
+                .is_ok()
+        }
+        Scheme::Pop => aggregate_verify_bls12381_v1(pub_keys_and_msgs, signature),
+    }
+}
+
+/// Same as aggregate_verify_with_scheme_bls12381_v1 but returns the reason of the failure.
+pub fn aggregate_verify_with_scheme_bls12381_v1_detailed<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+    scheme: Scheme,
+) -> Result<(), VerifyError> {
+    if scheme == Scheme::Basic {
+        let (distinct, _) = perf!("messages_distinct", messages_distinct(pub_keys_and_msgs));
+        if !distinct {
+            return Err(VerifyError::MessagesNotDistinct);
+        }
+    }
+    aggregate_verify_dst_internal(
+        pub_keys_and_msgs.iter().map(|(pk, msg)| (pk, msg.as_ref())),
+        signature,
+        scheme.dst(),
+        scheme == Scheme::Aug,
+    )
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification in message augmentation scheme.
+/// Messages do not need to be distinct, since each one is prepended with the public key.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_
+pub fn aggregate_verify_aug_bls12381_v1<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    aggregate_verify_with_scheme_bls12381_v1(pub_keys_and_msgs, signature, Scheme::Aug)
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification in basic scheme.
+/// Basic scheme requires all messages to be distinct, otherwise verification fails.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_
+pub fn aggregate_verify_basic_bls12381_v1<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    aggregate_verify_with_scheme_bls12381_v1(pub_keys_and_msgs, signature, Scheme::Basic)
+}
+
+/// Checks that no message repeats, as required by the basic scheme
+pub fn messages_distinct<M: AsRef<[u8]>>(pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)]) -> bool {
+    let mut msgs = HashSet::with_capacity(pub_keys_and_msgs.len());
+    pub_keys_and_msgs
+        .iter()
+        .all(|(_, msg)| msgs.insert(msg.as_ref()))
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification of public keys and messages
+/// borrowed from the iterator, so that messages do not have to be copied into owned vectors.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_iter<'a>(
+    pub_keys_and_msgs: impl Iterator<Item = (&'a Bls12381G1PublicKey, &'a [u8])>,
+    signature: &Bls12381G2Signature,
+) -> bool {
+    aggregate_verify_bls12381_v1_iter_detailed(pub_keys_and_msgs, signature).is_ok()
+}
+
+/// Same as aggregate_verify_bls12381_v1_iter but returns the reason of the failure.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_iter_detailed<'a>(
+    pub_keys_and_msgs: impl Iterator<Item = (&'a Bls12381G1PublicKey, &'a [u8])>,
+    signature: &Bls12381G2Signature,
+) -> Result<(), VerifyError> {
+    aggregate_verify_dst_internal(pub_keys_and_msgs, signature, BLS12381_CIPHERSITE_V1, false)
+}
+
+/// Same as aggregate_verify_bls12381_v1 but returns the reason of the failure,
+/// eg. index of the invalid public key.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_detailed<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> Result<(), VerifyError> {
+    aggregate_verify_bls12381_v1_iter_detailed(
+        pub_keys_and_msgs.iter().map(|(pk, msg)| (pk, msg.as_ref())),
+        signature,
+    )
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification processing (public key, message)
+/// pairs in chunks of given size. Public keys of a single chunk only are decoded at once
+/// and the pairing is committed after each chunk, so memory usage does not grow with the
+/// number of pairs. Chunk size 0 is treated as 1.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_chunked(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, Vec<u8>)],
+    signature: &Bls12381G2Signature,
+    chunk_size: usize,
+) -> bool {
+    if pub_keys_and_msgs.is_empty() {
+        return false;
+    }
+    let sig = match g2_affine_from_bytes(&signature.0) {
+        Some(sig) => sig,
+        None => return false,
+    };
+
+    let mut pairing = blst::Pairing::new(true, BLS12381_CIPHERSITE_V1);
+    let mut pks = Vec::with_capacity(chunk_size.max(1));
+
+    for chunk in pub_keys_and_msgs.chunks(chunk_size.max(1)) {
+        pks.clear();
+        for (pk, _) in chunk {
+            match g1_affine_from_bytes(&pk.0) {
+                Some(pk) => pks.push(pk),
+                None => return false,
+            }
+        }
+        for (pk, (_, msg)) in pks.iter().zip(chunk.iter()) {
+            if pairing.aggregate(pk, false, &None::<&blst::blst_p2_affine>, false, msg, &[])
+                != blst::BLST_ERROR::BLST_SUCCESS
+            {
+                return false;
+            }
+        }
+        // Accumulate Miller loops of the chunk, so nothing is retained for the next one
+        pairing.commit();
+    }
+
+    let mut gtsig = blst::blst_fp12::default();
+    blst::Pairing::aggregated(&mut gtsig, &sig);
+
+    pairing.finalverify(Some(&gtsig))
+}
+
+/// Aggregated verification with given domain separation tag.
+/// If `augment` is set then each message is prepended with its public key.
+fn aggregate_verify_dst_internal<'a>(
+    pub_keys_and_msgs: impl Iterator<Item = (&'a Bls12381G1PublicKey, &'a [u8])>,
+    signature: &Bls12381G2Signature,
+    dst: &[u8],
+    augment: bool,
+) -> Result<(), VerifyError> {
+    let (sig, _) = perf!(
+        "sig_from_bytes_validate",
+        g2_affine_from_bytes(&signature.0)
+    );
+    let sig = sig.ok_or(VerifyError::InvalidSignature)?;
+
+    let mut pairing = blst::Pairing::new(true, dst);
+
+    let (result, _) = perf!("pairing_aggregate", {
+        let mut cnt = 0;
+        for (pk, msg) in pub_keys_and_msgs {
+            let pk_point = match g1_affine_from_bytes(&pk.0) {
+                Some(pk_point) => pk_point,
+                None => return Err(VerifyError::InvalidPublicKey(cnt)),
+            };
+            let aug: &[u8] = if augment { &pk.0 } else { &[] };
+            if pairing.aggregate(
+                &pk_point,
+                false,
+                &None::<&blst::blst_p2_affine>,
+                false,
+                msg,
+                aug,
+            ) != blst::BLST_ERROR::BLST_SUCCESS
+            {
+                return Err(VerifyError::HashToCurveFailed(cnt));
+            }
+            cnt += 1;
+        }
+        // Nothing to verify the signature against
+        if cnt > 0 {
+            Ok(())
+        } else {
+            Err(VerifyError::NoPublicKeysGiven)
+        }
+    });
+    result?;
+
+    let _ = perf!("pairing_commit", pairing.commit());
+
+    let (gtsig, _) = perf!("pairing_aggregated", {
+        let mut gtsig = blst::blst_fp12::default();
+        blst::Pairing::aggregated(&mut gtsig, &sig);
+        gtsig
+    });
+
+    let (result, _) = perf!("pairing_verify", pairing.finalverify(Some(&gtsig)));
+    if result {
+        Ok(())
+    } else {
+        Err(VerifyError::PairingMismatch)
+    }
+}
+
 /// Performs BLS12-381 G2 aggregated signature verification
 /// one message signed with multiple keys.
+/// Public keys come from PopRegistry, which verified their proofs of possession.
 /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
 pub fn fast_aggregate_verify_bls12381_v1(
+    message: &[u8],
+    public_keys: &[PopVerifiedPublicKey],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    fast_aggregate_verify_bls12381_v1_unchecked(
+        message,
+        PopVerifiedPublicKey::as_public_keys(public_keys),
+        signature,
+    )
+}
+
+/// Same as fast_aggregate_verify_bls12381_v1 for keys, whose proofs of possession
+/// the caller verified on its own (eg. keys of consensus validators or test vectors).
+pub(crate) fn fast_aggregate_verify_bls12381_v1_unchecked(
     message: &[u8],
     public_keys: &[Bls12381G1PublicKey],
     signature: &Bls12381G2Signature,
 ) -> bool {
-    if let Ok(agg_pk) = Bls12381G1PublicKey::aggregate(public_keys) {
-        return verify_bls12381_v1(message, &agg_pk, signature);
+    fast_aggregate_verify_with_duplicates_bls12381_v1_unchecked(
+        message,
+        public_keys,
+        signature,
+        DuplicateSigners::Allow,
+    )
+}
+
+/// Determines how public keys repeated in fast aggregate verification are treated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateSigners {
+    /// Every occurrence of the key is aggregated (as in fast_aggregate_verify_bls12381_v1)
+    #[default]
+    Allow,
+    /// Verification fails if any key repeats
+    Reject,
+    /// Each distinct key is aggregated once, so repeated key is not counted as another signer
+    Deduplicate,
+    /// Each distinct key is validated once and aggregated multiplied by the number of
+    /// its occurrences. Accepts the same signatures as Allow, which validates every
+    /// occurrence of the key.
+    Multiplicity,
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification
+/// one message signed with multiple keys, treating repeated keys as requested.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn fast_aggregate_verify_with_duplicates_bls12381_v1(
+    message: &[u8],
+    public_keys: &[PopVerifiedPublicKey],
+    signature: &Bls12381G2Signature,
+    duplicates: DuplicateSigners,
+) -> bool {
+    fast_aggregate_verify_with_duplicates_bls12381_v1_unchecked(
+        message,
+        PopVerifiedPublicKey::as_public_keys(public_keys),
+        signature,
+        duplicates,
+    )
+}
+
+/// Same as fast_aggregate_verify_with_duplicates_bls12381_v1 for keys, whose proofs
+/// of possession the caller verified on its own.
+pub(crate) fn fast_aggregate_verify_with_duplicates_bls12381_v1_unchecked(
+    message: &[u8],
+    public_keys: &[Bls12381G1PublicKey],
+    signature: &Bls12381G2Signature,
+    duplicates: DuplicateSigners,
+) -> bool {
+    let agg_pk = match duplicates {
+        DuplicateSigners::Allow => Bls12381G1PublicKey::aggregate(public_keys).ok(),
+        DuplicateSigners::Reject => {
+            let mut keys = HashSet::with_capacity(public_keys.len());
+            if !public_keys.iter().all(|pk| keys.insert(pk)) {
+                return false;
+            }
+            Bls12381G1PublicKey::aggregate(public_keys).ok()
+        }
+        DuplicateSigners::Deduplicate => {
+            let mut keys = HashSet::with_capacity(public_keys.len());
+            let distinct: Vec<Bls12381G1PublicKey> = public_keys
+                .iter()
+                .filter(|pk| keys.insert(*pk))
+                .copied()
+                .collect();
+            Bls12381G1PublicKey::aggregate(&distinct).ok()
+        }
+        DuplicateSigners::Multiplicity => aggregate_public_keys_with_multiplicity(public_keys),
+    };
+
+    match agg_pk {
+        Some(agg_pk) => verify_bls12381_v1(message, &agg_pk, signature),
+        None => false,
     }
+}
 
-    false
+/// Aggregates public keys as sum of count_i * pk_i over distinct keys,
+/// so each key is decoded and validated only once.
+fn aggregate_public_keys_with_multiplicity(
+    public_keys: &[Bls12381G1PublicKey],
+) -> Option<Bls12381G1PublicKey> {
+    if public_keys.is_empty() {
+        return None;
+    }
+    let mut counts: HashMap<&Bls12381G1PublicKey, u32> = HashMap::new();
+    for pk in public_keys {
+        *counts.entry(pk).or_default() += 1;
+    }
+
+    // Projective point at infinity
+    let mut agg_pk = blst::blst_p1::default();
+    for (pk, cnt) in counts {
+        let pk = g1_affine_from_bytes(&pk.0)?;
+        let scalar = cnt.to_le_bytes();
+        unsafe {
+            let mut p = blst::blst_p1::default();
+            blst::blst_p1_from_affine(&mut p, &pk);
+            if cnt > 1 {
+                let mut rp = blst::blst_p1::default();
+                blst::blst_p1_mult(&mut rp, &p, scalar.as_ptr(), 32);
+                p = rp;
+            }
+            let agg_pk_ptr: *mut blst::blst_p1 = &mut agg_pk;
+            blst::blst_p1_add_or_double(agg_pk_ptr, agg_pk_ptr, &p);
+        }
+    }
+
+    let mut bytes = [0u8; Bls12381G1PublicKey::LENGTH];
+    unsafe {
+        blst::blst_p1_compress(bytes.as_mut_ptr(), &agg_pk);
+    }
+    Some(Bls12381G1PublicKey(bytes))
 }
 
-pub fn hash_to_g2(msg: &[u8]) {
+/// Performs BLS12-381 G2 batch verification of multiple independent
+/// (public key, message, signature) triples using blst's
+/// verify_multiple_aggregate_signatures (random linear combination of the triples).
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn verify_multiple_aggregate_signatures_bls12381_v1(
+    pub_keys_msgs_sigs: &[(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)],
+) -> bool {
+    let mut pks = vec![];
+    let mut sigs = vec![];
+    let mut msg_refs = vec![];
+    for (pk, msg, sig) in pub_keys_msgs_sigs.iter() {
+        if let Ok(pk) = blst::min_pk::PublicKey::from_bytes(&pk.0) {
+            pks.push(pk);
+        } else {
+            return false;
+        }
+        if let Ok(sig) = blst::min_pk::Signature::from_bytes(&sig.0) {
+            sigs.push(sig);
+        } else {
+            return false;
+        }
+        msg_refs.push(msg.as_slice());
+    }
+    let pks_refs: Vec<&blst::min_pk::PublicKey> = pks.iter().collect();
+    let sigs_refs: Vec<&blst::min_pk::Signature> = sigs.iter().collect();
+
+    let mut rng = rand::thread_rng();
+    let rands: Vec<blst::blst_scalar> = (0..sigs.len())
+        .map(|_| {
+            let mut scalar = blst::blst_scalar::default();
+            // Random scalar must not be zero
+            let r: u64 = rng.gen::<u64>() | 1;
+            scalar.b[..8].copy_from_slice(&r.to_le_bytes());
+            scalar
+        })
+        .collect();
+
+    let result = blst::min_pk::Signature::verify_multiple_aggregate_signatures(
+        &msg_refs,
+        BLS12381_CIPHERSITE_V1,
+        &pks_refs,
+        true,
+        &sigs_refs,
+        true,
+        &rands,
+        BATCH_VERIFY_RAND_BITS,
+    );
+
+    matches!(result, blst::BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Decodes compressed G1 point, checking it is not infinity and is in the G1 subgroup
+pub(crate) fn g1_affine_from_bytes(
+    bytes: &[u8; Bls12381G1PublicKey::LENGTH],
+) -> Option<blst::blst_p1_affine> {
+    let mut p = blst::blst_p1_affine::default();
+    unsafe {
+        if blst::blst_p1_uncompress(&mut p, bytes.as_ptr()) != blst::BLST_ERROR::BLST_SUCCESS
+            || blst::blst_p1_affine_is_inf(&p)
+            || !blst::blst_p1_affine_in_g1(&p)
+        {
+            return None;
+        }
+    }
+    Some(p)
+}
+
+/// Decodes compressed G2 point, checking it is not infinity and is in the G2 subgroup
+pub(crate) fn g2_affine_from_bytes(
+    bytes: &[u8; Bls12381G2Signature::LENGTH],
+) -> Option<blst::blst_p2_affine> {
+    let mut p = blst::blst_p2_affine::default();
+    unsafe {
+        if blst::blst_p2_uncompress(&mut p, bytes.as_ptr()) != blst::BLST_ERROR::BLST_SUCCESS
+            || blst::blst_p2_affine_is_inf(&p)
+            || !blst::blst_p2_affine_in_g2(&p)
+        {
+            return None;
+        }
+    }
+    Some(p)
+}
+
+/// Performs BLS12-381 G2 batch verification of multiple independent
+/// (public key, message, signature) triples using random linear combination:
+///   e(g1, sum(r_i * sig_i)) == prod(e(r_i * pk_i, H(m_i)))
+/// where r_i are random 64-bit scalars.
+/// Single final exponentiation is shared by all triples.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn batch_verify_bls12381_v1(
+    pub_keys_msgs_sigs: &[(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)],
+) -> bool {
+    if pub_keys_msgs_sigs.is_empty() {
+        return false;
+    }
+
+    let mut pairing = blst::Pairing::new(true, BLS12381_CIPHERSITE_V1);
+    let Some((sigs, scalars)) = batch_verify_aggregate_scaled_pks(&mut pairing, pub_keys_msgs_sigs)
+    else {
+        return false;
+    };
+
+    // Projective point at infinity
+    let mut agg_sig = blst::blst_p2::default();
+    for (sig, scalar) in sigs.iter().zip(scalars.iter()) {
+        unsafe {
+            // agg_sig += r_i * sig_i
+            let mut q = blst::blst_p2::default();
+            let mut rq = blst::blst_p2::default();
+            blst::blst_p2_from_affine(&mut q, sig);
+            blst::blst_p2_mult(&mut rq, &q, scalar.as_ptr(), BATCH_VERIFY_RAND_BITS);
+            let agg_sig_ptr: *mut blst::blst_p2 = &mut agg_sig;
+            blst::blst_p2_add_or_double(agg_sig_ptr, agg_sig_ptr, &rq);
+        }
+    }
+
+    batch_verify_finalverify(pairing, &agg_sig)
+}
+
+/// Same as batch_verify_bls12381_v1 but sum(r_i * sig_i) is computed at once using
+/// multi-scalar multiplication (Pippenger) instead of separate scalar multiplication
+/// and addition per signature. Public keys are still scaled one by one, as each of them
+/// is paired with different message.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn batch_verify_bls12381_v1_msm(
+    pub_keys_msgs_sigs: &[(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)],
+) -> bool {
+    let cnt = pub_keys_msgs_sigs.len();
+    if cnt == 0 {
+        return false;
+    }
+
+    let mut pairing = blst::Pairing::new(true, BLS12381_CIPHERSITE_V1);
+    let Some((sigs, scalars)) = batch_verify_aggregate_scaled_pks(&mut pairing, pub_keys_msgs_sigs)
+    else {
+        return false;
+    };
+
+    // sum(r_i * sig_i)
+    let mut agg_sig = blst::blst_p2::default();
+    unsafe {
+        if cnt == 1 {
+            let mut q = blst::blst_p2::default();
+            blst::blst_p2_from_affine(&mut q, &sigs[0]);
+            blst::blst_p2_mult(
+                &mut agg_sig,
+                &q,
+                scalars[0].as_ptr(),
+                BATCH_VERIFY_RAND_BITS,
+            );
+        } else {
+            let point_ptrs: Vec<*const blst::blst_p2_affine> =
+                sigs.iter().map(|p| p as _).collect();
+            let scalar_ptrs: Vec<*const u8> = scalars.iter().map(|s| s.as_ptr()).collect();
+            let scratch_size = blst::blst_p2s_mult_pippenger_scratch_sizeof(cnt);
+            let mut scratch =
+                vec![0 as blst::limb_t; scratch_size / core::mem::size_of::<blst::limb_t>()];
+            blst::blst_p2s_mult_pippenger(
+                &mut agg_sig,
+                point_ptrs.as_ptr(),
+                cnt,
+                scalar_ptrs.as_ptr(),
+                BATCH_VERIFY_RAND_BITS,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+
+    batch_verify_finalverify(pairing, &agg_sig)
+}
+
+/// Aggregates r_i * pk_i paired with H(m_i) of each triple into the pairing and returns
+/// decoded signatures with their random scalars r_i, so that the caller sums r_i * sig_i.
+/// Returns None if any public key or signature is invalid.
+fn batch_verify_aggregate_scaled_pks(
+    pairing: &mut blst::Pairing,
+    pub_keys_msgs_sigs: &[(Bls12381G1PublicKey, Vec<u8>, Bls12381G2Signature)],
+) -> Option<(Vec<blst::blst_p2_affine>, Vec<[u8; 8]>)> {
+    let mut rng = rand::thread_rng();
+    let mut sigs = Vec::with_capacity(pub_keys_msgs_sigs.len());
+    let mut scalars = Vec::with_capacity(pub_keys_msgs_sigs.len());
+
+    for (pk, msg, sig) in pub_keys_msgs_sigs.iter() {
+        let pk = g1_affine_from_bytes(&pk.0)?;
+        sigs.push(g2_affine_from_bytes(&sig.0)?);
+
+        // Random scalar must not be zero
+        let r: u64 = rng.gen::<u64>() | 1;
+        let scalar = r.to_le_bytes();
+
+        let mut scaled_pk = blst::blst_p1_affine::default();
+        unsafe {
+            // r_i * pk_i
+            let mut p = blst::blst_p1::default();
+            let mut rp = blst::blst_p1::default();
+            blst::blst_p1_from_affine(&mut p, &pk);
+            blst::blst_p1_mult(&mut rp, &p, scalar.as_ptr(), BATCH_VERIFY_RAND_BITS);
+            blst::blst_p1_to_affine(&mut scaled_pk, &rp);
+        }
+        scalars.push(scalar);
+
+        if pairing.aggregate(
+            &scaled_pk,
+            false,
+            &None::<&blst::blst_p2_affine>,
+            false,
+            msg,
+            &[],
+        ) != blst::BLST_ERROR::BLST_SUCCESS
+        {
+            return None;
+        }
+    }
+    pairing.commit();
+
+    Some((sigs, scalars))
+}
+
+/// Checks the pairing of batch verification against e(g, sum(r_i * sig_i))
+fn batch_verify_finalverify(pairing: blst::Pairing, agg_sig: &blst::blst_p2) -> bool {
+    let mut agg_sig_affine = blst::blst_p2_affine::default();
+    unsafe {
+        blst::blst_p2_to_affine(&mut agg_sig_affine, agg_sig);
+    }
+    let mut gtsig = blst::blst_fp12::default();
+    blst::Pairing::aggregated(&mut gtsig, &agg_sig_affine);
+
+    pairing.finalverify(Some(&gtsig))
+}
+
+/// Hashes message to G2 using the BLS12381_CIPHERSITE_V1 domain separation tag
+pub fn hash_to_g2(msg: &[u8]) -> blst::blst_p2 {
+    hash_to_g2_with_dst(msg, BLS12381_CIPHERSITE_V1)
+}
+
+/// Hashes message to G2 (BLS12381G2_XMD:SHA-256_SSWU_RO_ suite) using given domain separation tag
+pub fn hash_to_g2_with_dst(msg: &[u8], dst: &[u8]) -> blst::blst_p2 {
     let mut q = blst::blst_p2::default();
     let aug: &[u8] = &[];
-    //let mut sig_aff = blst::blst_p2_aff::default();
-    //let mut sig_ser = [0u8; $sig_ser_size];
     unsafe {
         blst::blst_hash_to_g2(
             &mut q,
             msg.as_ptr(),
             msg.len(),
-            BLS12381_CIPHERSITE_V1.as_ptr(),
-            BLS12381_CIPHERSITE_V1.len(),
+            dst.as_ptr(),
+            dst.len(),
+            aug.as_ptr(),
+            aug.len(),
+        );
+    }
+    q
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification hashing each message
+/// separately with hash_to_g2 before pairing (counterpart of aggregate_verify_bls12381_v1_batch_hash).
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_hash_each<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    let hashes: Vec<blst::blst_p2> = pub_keys_and_msgs
+        .iter()
+        .map(|(_, msg)| hash_to_g2(msg.as_ref()))
+        .collect();
+    aggregate_verify_hashed(pub_keys_and_msgs, &hashes, signature)
+}
+
+/// Performs BLS12-381 G2 aggregated signature verification hashing all messages
+/// at once with hash_to_g2_batch before pairing.
+/// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+pub fn aggregate_verify_bls12381_v1_batch_hash<M: AsRef<[u8]>>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    let msgs: Vec<&[u8]> = pub_keys_and_msgs
+        .iter()
+        .map(|(_, msg)| msg.as_ref())
+        .collect();
+    let hashes = hash_to_g2_batch(&msgs, BLS12381_CIPHERSITE_V1);
+    aggregate_verify_hashed(pub_keys_and_msgs, &hashes, signature)
+}
+
+/// Checks e(g1, sig) == prod(e(pk_i, hash_i)) for messages already hashed to G2
+fn aggregate_verify_hashed<M>(
+    pub_keys_and_msgs: &[(Bls12381G1PublicKey, M)],
+    hashes: &[blst::blst_p2],
+    signature: &Bls12381G2Signature,
+) -> bool {
+    if pub_keys_and_msgs.is_empty() {
+        return false;
+    }
+    let sig = match g2_affine_from_bytes(&signature.0) {
+        Some(sig) => sig,
+        None => return false,
+    };
+    let pks = match pub_keys_and_msgs
+        .iter()
+        .map(|(pk, _)| g1_affine_from_bytes(&pk.0))
+        .collect::<Option<Vec<blst::blst_p1_affine>>>()
+    {
+        Some(pks) => pks,
+        None => return false,
+    };
+    let hashes: Vec<blst::blst_p2_affine> = hashes
+        .iter()
+        .map(|q| {
+            let mut q_affine = blst::blst_p2_affine::default();
+            unsafe {
+                blst::blst_p2_to_affine(&mut q_affine, q);
+            }
+            q_affine
+        })
+        .collect();
+
+    let lhs = miller_loop(&[unsafe { *blst::blst_p1_affine_generator() }], &[sig]);
+    let rhs = miller_loop(&pks, &hashes);
+    unsafe { blst::blst_fp12_finalverify(&lhs, &rhs) }
+}
+
+/// Hashes message to G1 using the BLS12381G1_CIPHERSITE_V1 domain separation tag
+pub fn hash_to_g1(msg: &[u8]) -> blst::blst_p1 {
+    let mut q = blst::blst_p1::default();
+    let aug: &[u8] = &[];
+    unsafe {
+        blst::blst_hash_to_g1(
+            &mut q,
+            msg.as_ptr(),
+            msg.len(),
+            BLS12381G1_CIPHERSITE_V1.as_ptr(),
+            BLS12381G1_CIPHERSITE_V1.len(),
             aug.as_ptr(),
             aug.len(),
         );
     }
+    q
 }
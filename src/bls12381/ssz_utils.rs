@@ -0,0 +1,107 @@
+use super::*;
+use ssz::{Decode, DecodeError, Encode};
+
+// Public keys and signatures are SSZ fixed-length byte vectors (Bytes48, Bytes96),
+// so they are encoded as the compressed points. Decoding checks the length only,
+// as deserialization does.
+
+impl Encode for Bls12381G1PublicKey {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::LENGTH
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        Self::LENGTH
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0);
+    }
+}
+
+impl Decode for Bls12381G1PublicKey {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::LENGTH
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::try_from(bytes).map_err(|_| DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: Self::LENGTH,
+        })
+    }
+}
+
+impl Encode for Bls12381G2Signature {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::LENGTH
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        Self::LENGTH
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0);
+    }
+}
+
+impl Decode for Bls12381G2Signature {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::LENGTH
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::try_from(bytes).map_err(|_| DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: Self::LENGTH,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ssz_roundtrip() {
+        let (_sks, pks, _msg, sigs) = get_fast_aggregate_verify_test_data(3, 32);
+
+        let bytes = pks[0].as_ssz_bytes();
+        assert_eq!(bytes, pks[0].to_vec());
+        assert_eq!(Bls12381G1PublicKey::from_ssz_bytes(&bytes), Ok(pks[0]));
+
+        let bytes = sigs[0].as_ssz_bytes();
+        assert_eq!(bytes, sigs[0].to_vec());
+        assert_eq!(Bls12381G2Signature::from_ssz_bytes(&bytes), Ok(sigs[0]));
+
+        // Lists of fixed-length items are concatenated
+        let bytes = pks.as_ssz_bytes();
+        assert_eq!(bytes.len(), 3 * Bls12381G1PublicKey::LENGTH);
+        assert_eq!(Vec::<Bls12381G1PublicKey>::from_ssz_bytes(&bytes), Ok(pks));
+
+        assert_eq!(
+            Bls12381G2Signature::from_ssz_bytes(&bytes[..95]),
+            Err(DecodeError::InvalidByteLength {
+                len: 95,
+                expected: Bls12381G2Signature::LENGTH
+            })
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use once_cell::sync::OnceCell;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::io::{self, Read};
+
+/// Seed of the test data generators (eg. get_aggregate_verify_test_data).
+/// If not set, test private keys are derived from their indexes.
+static TEST_DATA_SEED: OnceCell<u64> = OnceCell::new();
+
+/// Whether test messages are filled with random bytes (from the seeded RNG)
+/// instead of a single repeated byte.
+static RANDOM_TEST_MESSAGES: OnceCell<bool> = OnceCell::new();
+
+// Separates message RNG stream from the private keys one
+const MESSAGE_SEED_DOMAIN: u64 = 0x6d65_7373_6167_6573;
+
+/// Sets seed of the test data generators, returns false if it was already set
+pub fn set_test_data_seed(seed: u64) -> bool {
+    TEST_DATA_SEED.set(seed).is_ok()
+}
+
+pub fn test_data_seed() -> Option<u64> {
+    TEST_DATA_SEED.get().copied()
+}
+
+/// RNG producing the same sequence for given seed across runs and machines
+/// (as long as the same version of the rand crate is used)
+pub fn test_data_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Sets whether test messages are random, returns false if it was already set
+pub fn set_random_test_messages(random: bool) -> bool {
+    RANDOM_TEST_MESSAGES.set(random).is_ok()
+}
+
+pub fn random_test_messages() -> bool {
+    RANDOM_TEST_MESSAGES.get().copied().unwrap_or_default()
+}
+
+/// RNG filling test messages, if random test messages are enabled.
+/// Seeded with test data seed (or 0 if not set).
+pub fn test_message_rng() -> Option<StdRng> {
+    random_test_messages()
+        .then(|| test_data_rng(test_data_seed().unwrap_or_default() ^ MESSAGE_SEED_DOMAIN))
+}
+
+/// Test message of given size, filled with given byte or with random bytes if RNG is given
+pub fn test_message(fill: u8, size: usize, rng: &mut Option<StdRng>) -> Vec<u8> {
+    let mut msg = vec![fill; size];
+    if let Some(rng) = rng {
+        rng.fill_bytes(&mut msg);
+    }
+    msg
+}
+
+/// Test message generated while being read, so that its size is not limited by memory.
+/// Contents are the same as of test_message (for random messages as long as reads are
+/// multiples of 4 bytes).
+pub struct TestMessageReader {
+    fill: u8,
+    remaining: u64,
+    rng: Option<StdRng>,
+}
+
+impl TestMessageReader {
+    pub fn new(fill: u8, size: u64) -> Self {
+        Self {
+            fill,
+            remaining: size,
+            rng: test_message_rng(),
+        }
+    }
+}
+
+impl Read for TestMessageReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf
+            .len()
+            .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let chunk = &mut buf[..len];
+        match &mut self.rng {
+            Some(rng) => rng.fill_bytes(chunk),
+            None => chunk.fill(self.fill),
+        }
+        self.remaining -= len as u64;
+        Ok(len)
+    }
+}
+
+// Separates message sizes RNG stream from the other ones
+const MESSAGE_SIZES_SEED_DOMAIN: u64 = 0x6d73_675f_7369_7a65;
+
+/// Distribution of the message sizes, to reflect production traffic mix in benchmarks
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageSizeDistribution {
+    /// Sizes, whose natural logarithm is normally distributed with given mean and deviation
+    LogNormal { mu: f64, sigma: f64 },
+    /// Empirical histogram of (size, weight) buckets
+    Histogram(Vec<(usize, f64)>),
+}
+
+impl MessageSizeDistribution {
+    /// Parses log-normal distribution given as "<mu>,<sigma>" of ln(size)
+    pub fn parse_log_normal(text: &str) -> Result<Self, String> {
+        let params = match text.split(',').map(str::trim).collect::<Vec<&str>>()[..] {
+            [mu, sigma] => mu.parse::<f64>().ok().zip(sigma.parse::<f64>().ok()),
+            _ => None,
+        };
+        match params {
+            Some((mu, sigma)) if mu.is_finite() && sigma >= 0.0 && sigma.is_finite() => {
+                Ok(Self::LogNormal { mu, sigma })
+            }
+            _ => Err(format!("invalid log-normal mu and sigma: {}", text)),
+        }
+    }
+
+    /// Parses histogram with one "<size> <weight>" bucket per line,
+    /// empty lines and lines starting with '#' are skipped.
+    pub fn parse_histogram(text: &str) -> Result<Self, String> {
+        let mut buckets = vec![];
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bucket = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                [size, weight] => size.parse::<usize>().ok().zip(weight.parse::<f64>().ok()),
+                _ => None,
+            };
+            match bucket {
+                Some((size, weight)) if weight >= 0.0 && weight.is_finite() => {
+                    buckets.push((size, weight))
+                }
+                _ => return Err(format!("invalid histogram line {}: {}", idx + 1, line)),
+            }
+        }
+        if buckets.iter().map(|(_, weight)| weight).sum::<f64>() <= 0.0 {
+            return Err("histogram has no weight".to_string());
+        }
+        Ok(Self::Histogram(buckets))
+    }
+
+    /// Draws `cnt` message sizes, the same for given seed
+    pub fn sample(&self, cnt: usize, seed: u64) -> Vec<usize> {
+        let mut rng = test_data_rng(seed ^ MESSAGE_SIZES_SEED_DOMAIN);
+        (0..cnt)
+            .map(|_| match self {
+                Self::LogNormal { mu, sigma } => {
+                    // Box-Muller transform
+                    let u1: f64 = 1.0 - rng.gen::<f64>();
+                    let u2: f64 = rng.gen::<f64>();
+                    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                    (mu + sigma * z).exp().round() as usize
+                }
+                Self::Histogram(buckets) => {
+                    let total: f64 = buckets.iter().map(|(_, weight)| weight).sum();
+                    let mut point = rng.gen::<f64>() * total;
+                    for (size, weight) in buckets {
+                        if point < *weight {
+                            return *size;
+                        }
+                        point -= weight;
+                    }
+                    // Rounding errors
+                    buckets.iter().rev().find(|(_, w)| *w > 0.0).unwrap().0
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        assert_eq!(test_message(7, 3, &mut None), vec![7, 7, 7]);
+
+        let msg = test_message(7, 64, &mut Some(test_data_rng(1)));
+        assert_eq!(msg, test_message(0, 64, &mut Some(test_data_rng(1))));
+        assert_ne!(msg, test_message(7, 64, &mut Some(test_data_rng(2))));
+        assert_ne!(msg, vec![7; 64]);
+    }
+
+    #[test]
+    fn test_message_reader() {
+        let mut msg = vec![];
+        TestMessageReader::new(7, 1000)
+            .read_to_end(&mut msg)
+            .unwrap();
+        assert_eq!(msg, test_message(7, 1000, &mut None));
+
+        let mut buf = [0u8; 64];
+        let mut reader = TestMessageReader::new(7, 100);
+        assert_eq!(reader.read(&mut buf).unwrap(), 64);
+        assert_eq!(reader.read(&mut buf).unwrap(), 36);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn message_size_distributions() {
+        let log_normal = MessageSizeDistribution::LogNormal {
+            mu: 1024f64.ln(),
+            sigma: 0.5,
+        };
+        assert_eq!(
+            MessageSizeDistribution::parse_log_normal("6.9, 0.5"),
+            Ok(MessageSizeDistribution::LogNormal {
+                mu: 6.9,
+                sigma: 0.5
+            })
+        );
+        assert!(MessageSizeDistribution::parse_log_normal("6.9").is_err());
+        assert!(MessageSizeDistribution::parse_log_normal("6.9,1,2").is_err());
+        assert!(MessageSizeDistribution::parse_log_normal("6.9,-1").is_err());
+        let mut sizes = log_normal.sample(1001, 1);
+        assert_eq!(sizes, log_normal.sample(1001, 1));
+        assert_ne!(sizes, log_normal.sample(1001, 2));
+        // Median of the log-normal distribution is exp(mu)
+        sizes.sort();
+        assert!(sizes[500] > 900 && sizes[500] < 1150);
+
+        let histogram =
+            MessageSizeDistribution::parse_histogram("# size weight\n100 3\n\n200000 1\n10 0\n")
+                .unwrap();
+        assert_eq!(
+            histogram,
+            MessageSizeDistribution::Histogram(vec![(100, 3.0), (200000, 1.0), (10, 0.0)])
+        );
+        let sizes = histogram.sample(1000, 1);
+        let small = sizes.iter().filter(|size| **size == 100).count();
+        assert_eq!(
+            sizes.iter().filter(|size| **size == 200000).count(),
+            1000 - small
+        );
+        assert!(small > 650 && small < 850);
+
+        assert!(MessageSizeDistribution::parse_histogram("100").is_err());
+        assert!(MessageSizeDistribution::parse_histogram("100 -1").is_err());
+        assert!(MessageSizeDistribution::parse_histogram("100 0").is_err());
+    }
+}
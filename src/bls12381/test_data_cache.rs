@@ -0,0 +1,262 @@
+use super::*;
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory, where test data generated by TestDataCache is memoized across runs
+static TEST_DATA_CACHE_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Sets directory of the test data cache, returns false if it was already set
+pub fn set_test_data_cache_dir(dir: PathBuf) -> bool {
+    TEST_DATA_CACHE_DIR.set(dir).is_ok()
+}
+
+pub fn test_data_cache_dir() -> Option<PathBuf> {
+    TEST_DATA_CACHE_DIR.get().cloned()
+}
+
+/// Generates test data (keys, messages and signatures) once per parameters
+/// and reuses it across repeats and sweep points.
+/// If directory is given, the data is also stored there and loaded by subsequent runs
+/// (signatures are checked to be valid points in the subgroup when loading, but not verified,
+/// public keys are derived from private keys).
+pub struct TestDataCache<V: Bls12381Variant> {
+    dir: Option<PathBuf>,
+    aggregate_verify: HashMap<AggregateVerifyKey, TestData<V, Vec<Vec<u8>>>>,
+    fast_aggregate_verify: HashMap<(u32, usize), TestData<V, Vec<u8>>>,
+}
+
+/// Version of the cache file format and of the test data generators, bump it when
+/// either of them changes, so that files stored by older versions are not loaded
+const TEST_DATA_CACHE_VERSION: u32 = 1;
+
+/// Parameters of the aggregate verify test data: (cnt, msg_cnt, msg_size)
+type AggregateVerifyKey = (u32, u32, usize);
+
+impl<V: Bls12381Variant> TestDataCache<V> {
+    /// Cache memoized in the directory set by set_test_data_cache_dir (if any)
+    pub fn new() -> Self {
+        Self::with_dir(test_data_cache_dir())
+    }
+
+    pub fn with_dir(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            aggregate_verify: HashMap::new(),
+            fast_aggregate_verify: HashMap::new(),
+        }
+    }
+
+    /// Cached result of get_aggregate_verify_test_data
+    pub fn aggregate_verify(
+        &mut self,
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+    ) -> &TestData<V, Vec<Vec<u8>>> {
+        let dir = &self.dir;
+        self.aggregate_verify
+            .entry((cnt, msg_cnt, msg_size))
+            .or_insert_with(|| {
+                let path = dir.as_deref().map(|dir| {
+                    cache_file_path::<V>(dir, "aggregate_verify", cnt, msg_cnt, msg_size)
+                });
+                load_or_generate::<V>(path.as_deref(), || {
+                    V::get_aggregate_verify_test_data(cnt, msg_cnt, msg_size)
+                })
+            })
+    }
+
+    /// Cached result of get_fast_aggregate_verify_test_data
+    pub fn fast_aggregate_verify(&mut self, cnt: u32, msg_size: usize) -> &TestData<V, Vec<u8>> {
+        let dir = &self.dir;
+        self.fast_aggregate_verify
+            .entry((cnt, msg_size))
+            .or_insert_with(|| {
+                let path = dir.as_deref().map(|dir| {
+                    cache_file_path::<V>(dir, "fast_aggregate_verify", cnt, 1, msg_size)
+                });
+                let (sks, pks, mut msgs, sigs) = load_or_generate::<V>(path.as_deref(), || {
+                    let (sks, pks, msg, sigs) =
+                        V::get_fast_aggregate_verify_test_data(cnt, msg_size);
+                    (sks, pks, vec![msg], sigs)
+                });
+                (sks, pks, msgs.pop().unwrap_or_default(), sigs)
+            })
+    }
+}
+
+impl<V: Bls12381Variant> Default for TestDataCache<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// File name reflects all the settings the generated data depends on
+fn cache_file_path<V: Bls12381Variant>(
+    dir: &Path,
+    kind: &str,
+    cnt: u32,
+    msg_cnt: u32,
+    msg_size: usize,
+) -> PathBuf {
+    let keys = match test_data_seed() {
+        Some(seed) => format!("seed{}", seed),
+        None => "indexes".to_string(),
+    };
+    let msgs = if random_test_messages() {
+        "random"
+    } else {
+        "filled"
+    };
+    dir.join(format!(
+        "v{}_{}_{}_{}_{}_{}_{}_{}.bin",
+        TEST_DATA_CACHE_VERSION,
+        V::NAME,
+        kind,
+        cnt,
+        msg_cnt,
+        msg_size,
+        keys,
+        msgs
+    ))
+}
+
+fn load_or_generate<V: Bls12381Variant>(
+    path: Option<&Path>,
+    generate: impl FnOnce() -> TestData<V, Vec<Vec<u8>>>,
+) -> TestData<V, Vec<Vec<u8>>> {
+    let path = match path {
+        Some(path) => path,
+        None => return generate(),
+    };
+    if let Some(data) = fs::read(path)
+        .ok()
+        .and_then(|bytes| decode_test_data::<V>(&bytes))
+    {
+        return data;
+    }
+
+    let data = generate();
+    // Failing to store the data only makes the next run slower
+    if let Err(err) = fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+        .and_then(|_| fs::write(path, encode_test_data::<V>(&data)))
+    {
+        eprintln!("failed to store test data in {}: {}", path.display(), err);
+    }
+    data
+}
+
+// Private keys, messages and signatures, each of them as a list of length-prefixed items
+fn encode_test_data<V: Bls12381Variant>(data: &TestData<V, Vec<Vec<u8>>>) -> Vec<u8> {
+    let (sks, _pks, msgs, sigs) = data;
+    let mut bytes = vec![];
+    let sks: Vec<Vec<u8>> = sks.iter().map(V::private_key_to_bytes).collect();
+    let sigs: Vec<Vec<u8>> = sigs.iter().map(V::signature_to_bytes).collect();
+    for items in [&sks, msgs, &sigs] {
+        bytes.extend_from_slice(&(items.len() as u64).to_le_bytes());
+        for item in items {
+            bytes.extend_from_slice(&(item.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(item);
+        }
+    }
+    bytes
+}
+
+fn decode_test_data<V: Bls12381Variant>(mut bytes: &[u8]) -> Option<TestData<V, Vec<Vec<u8>>>> {
+    let sks = decode_items(&mut bytes)?
+        .iter()
+        .map(|sk| V::private_key_from_bytes(sk).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let msgs = decode_items(&mut bytes)?;
+    let sigs = decode_items(&mut bytes)?
+        .iter()
+        .map(|sig| {
+            V::signature_from_bytes(sig)
+                .ok()
+                .filter(|sig| V::signature_is_valid(sig))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if !bytes.is_empty() {
+        return None;
+    }
+    let pks = sks.iter().map(V::public_key).collect();
+    Some((sks, pks, msgs, sigs))
+}
+
+fn decode_items(bytes: &mut &[u8]) -> Option<Vec<Vec<u8>>> {
+    let cnt = decode_u64(bytes)?;
+    (0..cnt)
+        .map(|_| {
+            let len = usize::try_from(decode_u64(bytes)?).ok()?;
+            if bytes.len() < len {
+                return None;
+            }
+            let (item, rest) = bytes.split_at(len);
+            *bytes = rest;
+            Some(item.to_vec())
+        })
+        .collect()
+}
+
+fn decode_u64(bytes: &mut &[u8]) -> Option<u64> {
+    if bytes.len() < 8 {
+        return None;
+    }
+    let (value, rest) = bytes.split_at(8);
+    *bytes = rest;
+    Some(u64::from_le_bytes(value.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_cache_in_memory() {
+        let mut cache = TestDataCache::<MinPk>::with_dir(None);
+        let (_sks, pks, msgs, sigs) = cache.aggregate_verify(3, 2, 16);
+        assert_eq!((pks.len(), msgs.len(), sigs.len()), (3, 2, 3));
+        let first = sigs.clone();
+        assert_eq!(cache.aggregate_verify(3, 2, 16).3, first);
+        assert_eq!(cache.aggregate_verify.len(), 1);
+
+        let (_sks, pks, msg, sigs) = cache.fast_aggregate_verify(4, 8);
+        assert_eq!((pks.len(), msg.len(), sigs.len()), (4, 8, 4));
+    }
+
+    #[test]
+    fn test_data_cache_on_disk() {
+        let dir = std::env::temp_dir().join(format!("test_data_cache_{}", std::process::id()));
+
+        let mut cache = TestDataCache::<MinSig>::with_dir(Some(dir.clone()));
+        let (_sks, pks, msg, sigs) = cache.fast_aggregate_verify(3, 32);
+        let (pks, msg, sigs) = (pks.clone(), msg.clone(), sigs.clone());
+
+        // New cache loads the data stored by the previous one
+        let mut cache = TestDataCache::<MinSig>::with_dir(Some(dir.clone()));
+        let (sks, loaded_pks, loaded_msg, loaded_sigs) = cache.fast_aggregate_verify(3, 32);
+        assert_eq!((loaded_pks, loaded_msg, loaded_sigs), (&pks, &msg, &sigs));
+        let agg_sig = MinSig::aggregate_signatures(loaded_sigs).unwrap();
+        assert!(MinSig::fast_aggregate_verify(
+            loaded_msg, loaded_pks, &agg_sig
+        ));
+        assert_eq!(sks.len(), 3);
+
+        // Corrupted file is regenerated
+        let path = cache_file_path::<MinSig>(&dir, "fast_aggregate_verify", 3, 1, 32);
+        fs::write(&path, [1u8, 2, 3]).unwrap();
+        let mut cache = TestDataCache::<MinSig>::with_dir(Some(dir.clone()));
+        assert_eq!(cache.fast_aggregate_verify(3, 32).3, sigs);
+        assert!(decode_test_data::<MinSig>(&fs::read(&path).unwrap()).is_some());
+
+        // Signature outside of the subgroup is rejected
+        let (sks, pks, msg, mut sigs) = MinSig::get_fast_aggregate_verify_test_data(3, 32);
+        sigs[1] = MinSig::non_subgroup_signature();
+        let bytes = encode_test_data::<MinSig>(&(sks, pks, vec![msg], sigs));
+        assert!(decode_test_data::<MinSig>(&bytes).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,646 @@
+use super::*;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Operations covered by the BLS signature test vectors (POP ciphersuite):
+/// IETF draft vectors in the format of https://github.com/ethereum/bls12-381-tests
+/// and Ethereum consensus-spec vectors (tests/general/<fork>/bls), which share the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestVectorOperation {
+    Sign,
+    Verify,
+    Aggregate,
+    AggregateVerify,
+    FastAggregateVerify,
+    /// Ethereum variant accepting no public keys with point at infinity signature
+    EthFastAggregateVerify,
+    /// Ethereum public key aggregation
+    EthAggregatePubkeys,
+}
+
+impl TestVectorOperation {
+    /// Operations of the IETF draft vectors
+    pub const IETF: [TestVectorOperation; 5] = [
+        Self::Sign,
+        Self::Verify,
+        Self::Aggregate,
+        Self::AggregateVerify,
+        Self::FastAggregateVerify,
+    ];
+
+    /// Operations of the Ethereum consensus-spec vectors
+    pub const ETH: [TestVectorOperation; 7] = [
+        Self::Sign,
+        Self::Verify,
+        Self::Aggregate,
+        Self::AggregateVerify,
+        Self::FastAggregateVerify,
+        Self::EthFastAggregateVerify,
+        Self::EthAggregatePubkeys,
+    ];
+
+    /// Directory of the vectors of this operation
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sign => "sign",
+            Self::Verify => "verify",
+            Self::Aggregate => "aggregate",
+            Self::AggregateVerify => "aggregate_verify",
+            Self::FastAggregateVerify => "fast_aggregate_verify",
+            Self::EthFastAggregateVerify => "eth_fast_aggregate_verify",
+            Self::EthAggregatePubkeys => "eth_aggregate_pubkeys",
+        }
+    }
+}
+
+/// Parsed input of the vector. Keys and signatures of invalid encoding are None,
+/// as rejecting them is the expected behavior.
+#[derive(Debug)]
+pub enum TestVectorInput {
+    Sign {
+        private_key: Option<Bls12381G1PrivateKey>,
+        message: Vec<u8>,
+    },
+    Verify {
+        public_key: Option<Bls12381G1PublicKey>,
+        message: Vec<u8>,
+        signature: Option<Bls12381G2Signature>,
+    },
+    Aggregate {
+        signatures: Option<Vec<Bls12381G2Signature>>,
+    },
+    AggregateVerify {
+        pub_keys_and_msgs: Option<Vec<(Bls12381G1PublicKey, Vec<u8>)>>,
+        signature: Option<Bls12381G2Signature>,
+    },
+    FastAggregateVerify {
+        public_keys: Option<Vec<Bls12381G1PublicKey>>,
+        message: Vec<u8>,
+        signature: Option<Bls12381G2Signature>,
+        rules: AggregationRules,
+    },
+    AggregatePubkeys {
+        public_keys: Option<Vec<Bls12381G1PublicKey>>,
+    },
+}
+
+/// Output of the operation, bytes are None if the operation is expected to fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestVectorOutput {
+    Bytes(Option<Vec<u8>>),
+    Bool(bool),
+}
+
+#[derive(Debug)]
+pub struct TestVector {
+    pub name: String,
+    pub input: TestVectorInput,
+    pub expected: TestVectorOutput,
+}
+
+impl TestVector {
+    /// Parses vector, ie. object with "input" and "output"
+    pub fn parse(
+        operation: TestVectorOperation,
+        name: &str,
+        vector: &Value,
+    ) -> Result<Self, TestVectorError> {
+        let input = &vector["input"];
+        let output = &vector["output"];
+        let (input, expected) = match operation {
+            TestVectorOperation::Sign => (
+                TestVectorInput::Sign {
+                    private_key: Bls12381G1PrivateKey::from_bytes(&hex_field(&input["privkey"])?)
+                        .ok(),
+                    message: hex_field(&input["message"])?,
+                },
+                TestVectorOutput::Bytes(optional_hex_field(output)?),
+            ),
+            TestVectorOperation::Verify => (
+                TestVectorInput::Verify {
+                    public_key: public_key_field(&input["pubkey"])?,
+                    message: hex_field(&input["message"])?,
+                    signature: signature_field(&input["signature"])?,
+                },
+                bool_field(output)?,
+            ),
+            TestVectorOperation::Aggregate => (
+                TestVectorInput::Aggregate {
+                    signatures: array_field(input)?
+                        .iter()
+                        .map(signature_field)
+                        .collect::<Result<Option<Vec<_>>, _>>()?,
+                },
+                TestVectorOutput::Bytes(optional_hex_field(output)?),
+            ),
+            TestVectorOperation::AggregateVerify => {
+                let public_keys = array_field(&input["pubkeys"])?
+                    .iter()
+                    .map(public_key_field)
+                    .collect::<Result<Option<Vec<_>>, _>>()?;
+                let messages = array_field(&input["messages"])?
+                    .iter()
+                    .map(hex_field)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let pub_keys_and_msgs = public_keys
+                    .filter(|pks| pks.len() == messages.len())
+                    .map(|pks| pks.into_iter().zip(messages).collect());
+                (
+                    TestVectorInput::AggregateVerify {
+                        pub_keys_and_msgs,
+                        signature: signature_field(&input["signature"])?,
+                    },
+                    bool_field(output)?,
+                )
+            }
+            TestVectorOperation::FastAggregateVerify
+            | TestVectorOperation::EthFastAggregateVerify => (
+                TestVectorInput::FastAggregateVerify {
+                    public_keys: array_field(&input["pubkeys"])?
+                        .iter()
+                        .map(public_key_field)
+                        .collect::<Result<Option<Vec<_>>, _>>()?,
+                    message: hex_field(&input["message"])?,
+                    signature: signature_field(&input["signature"])?,
+                    rules: match operation {
+                        TestVectorOperation::EthFastAggregateVerify => AggregationRules::Eth,
+                        _ => AggregationRules::Ietf,
+                    },
+                },
+                bool_field(output)?,
+            ),
+            TestVectorOperation::EthAggregatePubkeys => (
+                TestVectorInput::AggregatePubkeys {
+                    public_keys: array_field(input)?
+                        .iter()
+                        .map(public_key_field)
+                        .collect::<Result<Option<Vec<_>>, _>>()?,
+                },
+                TestVectorOutput::Bytes(optional_hex_field(output)?),
+            ),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            input,
+            expected,
+        })
+    }
+
+    /// Performs the operation
+    pub fn run(&self) -> TestVectorOutput {
+        match &self.input {
+            TestVectorInput::Sign {
+                private_key,
+                message,
+            } => {
+                TestVectorOutput::Bytes(private_key.as_ref().map(|sk| sk.sign_v1(message).to_vec()))
+            }
+            TestVectorInput::Verify {
+                public_key,
+                message,
+                signature,
+            } => TestVectorOutput::Bool(match (public_key, signature) {
+                (Some(pk), Some(sig)) => verify_bls12381_v1(message, pk, sig),
+                _ => false,
+            }),
+            TestVectorInput::Aggregate { signatures } => TestVectorOutput::Bytes(
+                signatures
+                    .as_ref()
+                    .and_then(|sigs| Bls12381G2Signature::aggregate(sigs, true).ok())
+                    .map(|sig| sig.to_vec()),
+            ),
+            TestVectorInput::AggregateVerify {
+                pub_keys_and_msgs,
+                signature,
+            } => TestVectorOutput::Bool(match (pub_keys_and_msgs, signature) {
+                (Some(pub_keys_and_msgs), Some(sig)) => {
+                    aggregate_verify_bls12381_v1(pub_keys_and_msgs, sig)
+                }
+                _ => false,
+            }),
+            TestVectorInput::FastAggregateVerify {
+                public_keys,
+                message,
+                signature,
+                rules,
+            } => TestVectorOutput::Bool(match (public_keys, signature) {
+                (Some(pks), Some(sig)) => {
+                    fast_aggregate_verify_with_rules_bls12381_v1(message, pks, sig, *rules)
+                }
+                _ => false,
+            }),
+            TestVectorInput::AggregatePubkeys { public_keys } => TestVectorOutput::Bytes(
+                public_keys
+                    .as_ref()
+                    .and_then(|pks| {
+                        aggregate_public_keys_with_rules(pks, AggregationRules::Eth).ok()
+                    })
+                    .map(|pk| pk.to_vec()),
+            ),
+        }
+    }
+
+    pub fn passes(&self) -> bool {
+        self.run() == self.expected
+    }
+}
+
+/// Results of running test vectors of a single operation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestVectorReport {
+    pub passed: usize,
+    /// Names of the vectors, whose output differs from the expected one
+    pub failed: Vec<String>,
+}
+
+/// Loads all vectors (JSON or YAML files) found in `<dir>/<operation>/` and its subdirectories,
+/// eg. `verify/verify_valid_case_195246ee3bd3b6ec.json` (IETF)
+/// or `verify/bls/verify_valid_case_195246ee3bd3b6ec/data.yaml` (Ethereum).
+/// Vectors are named by their path relative to the operation directory.
+pub fn load_test_vectors(
+    dir: &Path,
+    operation: TestVectorOperation,
+) -> Result<Vec<TestVector>, TestVectorError> {
+    let dir = dir.join(operation.name());
+    let mut paths = vec![];
+    collect_test_vector_files(&dir, &mut paths)?;
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .strip_prefix(&dir)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            TestVector::parse(operation, &name, &load_test_vector_file(path)?)
+        })
+        .collect()
+}
+
+/// Runs all vectors of given operation
+pub fn run_test_vectors(
+    dir: &Path,
+    operation: TestVectorOperation,
+) -> Result<TestVectorReport, TestVectorError> {
+    let mut report = TestVectorReport::default();
+    for vector in load_test_vectors(dir, operation)? {
+        if vector.passes() {
+            report.passed += 1;
+        } else {
+            report.failed.push(vector.name);
+        }
+    }
+    Ok(report)
+}
+
+/// Checks hash_to_g2 against the RFC 9380 BLS12381G2_XMD:SHA-256_SSWU_RO_ vectors, ie. suite file
+/// BLS12381G2_XMD_SHA-256_SSWU_RO_.json of https://github.com/cfrg/draft-irtf-cfrg-hash-to-curve
+pub fn run_hash_to_g2_test_vectors(path: &Path) -> Result<TestVectorReport, TestVectorError> {
+    check_hash_to_g2_test_vectors(&load_test_vector_file(path)?)
+}
+
+/// Checks vectors of the suite, ie. object with "ciphersuite", "dst" and "vectors".
+/// Vectors are named by their messages.
+pub fn check_hash_to_g2_test_vectors(suite: &Value) -> Result<TestVectorReport, TestVectorError> {
+    if suite["ciphersuite"].as_str() != Some(HASH_TO_G2_CIPHERSUITE) {
+        return Err(TestVectorError::InvalidField(
+            suite["ciphersuite"].to_string(),
+        ));
+    }
+    let dst = string_field(&suite["dst"])?;
+
+    let mut report = TestVectorReport::default();
+    for vector in array_field(&suite["vectors"])? {
+        let msg = string_field(&vector["msg"])?;
+        let expected = g2_point_field(&vector["P"])?;
+        let q = hash_to_g2_with_dst(msg.as_bytes(), dst.as_bytes());
+        let mut actual = [0u8; 2 * G2_COORDINATE_LENGTH];
+        unsafe {
+            blst::blst_p2_serialize(actual.as_mut_ptr(), &q);
+        }
+        if actual[..] == expected[..] {
+            report.passed += 1;
+        } else {
+            report.failed.push(format!("msg {:?}", msg));
+        }
+    }
+    Ok(report)
+}
+
+const HASH_TO_G2_CIPHERSUITE: &str = "BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+// Uncompressed Fp2 coordinate length
+const G2_COORDINATE_LENGTH: usize = 96;
+
+fn collect_test_vector_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), TestVectorError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| TestVectorError::Io(format!("{}: {}", dir.display(), err)))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|err| TestVectorError::Io(err.to_string()))?
+            .path();
+        if path.is_dir() {
+            collect_test_vector_files(&path, paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "json" || ext == "yaml" || ext == "yml")
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn load_test_vector_file(path: &Path) -> Result<Value, TestVectorError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| TestVectorError::Io(format!("{}: {}", path.display(), err)))?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&text)
+            .map_err(|err| TestVectorError::Json(format!("{}: {}", path.display(), err)))
+    } else {
+        serde_yaml::from_str(&text)
+            .map_err(|err| TestVectorError::Yaml(format!("{}: {}", path.display(), err)))
+    }
+}
+
+fn hex_field(value: &Value) -> Result<Vec<u8>, TestVectorError> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| TestVectorError::InvalidField(value.to_string()))?;
+    hex::decode(s.trim_start_matches("0x"))
+        .map_err(|_| TestVectorError::InvalidField(s.to_string()))
+}
+
+fn string_field(value: &Value) -> Result<&str, TestVectorError> {
+    value
+        .as_str()
+        .ok_or_else(|| TestVectorError::InvalidField(value.to_string()))
+}
+
+// Point given as {"x": "c0,c1", "y": "c0,c1"} in the uncompressed serialization,
+// ie. x.c1 || x.c0 || y.c1 || y.c0
+fn g2_point_field(value: &Value) -> Result<Vec<u8>, TestVectorError> {
+    let mut bytes = Vec::with_capacity(2 * G2_COORDINATE_LENGTH);
+    for coordinate in [&value["x"], &value["y"]] {
+        let s = string_field(coordinate)?;
+        let (c0, c1) = s
+            .split_once(',')
+            .ok_or_else(|| TestVectorError::InvalidField(s.to_string()))?;
+        for c in [c1, c0] {
+            let c = hex::decode(c.trim().trim_start_matches("0x"))
+                .ok()
+                .filter(|c| c.len() <= G2_COORDINATE_LENGTH / 2)
+                .ok_or_else(|| TestVectorError::InvalidField(s.to_string()))?;
+            bytes.resize(bytes.len() + G2_COORDINATE_LENGTH / 2 - c.len(), 0);
+            bytes.extend_from_slice(&c);
+        }
+    }
+    Ok(bytes)
+}
+
+// Null stands for operation being expected to fail
+fn optional_hex_field(value: &Value) -> Result<Option<Vec<u8>>, TestVectorError> {
+    match value {
+        Value::Null => Ok(None),
+        _ => hex_field(value).map(Some),
+    }
+}
+
+fn bool_field(value: &Value) -> Result<TestVectorOutput, TestVectorError> {
+    value
+        .as_bool()
+        .map(TestVectorOutput::Bool)
+        .ok_or_else(|| TestVectorError::InvalidField(value.to_string()))
+}
+
+fn array_field(value: &Value) -> Result<&Vec<Value>, TestVectorError> {
+    value
+        .as_array()
+        .ok_or_else(|| TestVectorError::InvalidField(value.to_string()))
+}
+
+// Bytes of invalid length are valid vector contents, but not a public key
+fn public_key_field(value: &Value) -> Result<Option<Bls12381G1PublicKey>, TestVectorError> {
+    Ok(Bls12381G1PublicKey::try_from(hex_field(value)?.as_slice()).ok())
+}
+
+fn signature_field(value: &Value) -> Result<Option<Bls12381G2Signature>, TestVectorError> {
+    Ok(Bls12381G2Signature::try_from(hex_field(value)?.as_slice()).ok())
+}
+
+/// Represents an error when loading test vectors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestVectorError {
+    Io(String),
+    Json(String),
+    Yaml(String),
+    // Field is missing or is not of expected type
+    InvalidField(String),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for TestVectorError {}
+
+impl fmt::Display for TestVectorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hex(bytes: &[u8]) -> String {
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    fn passes(operation: TestVectorOperation, vector: Value) -> bool {
+        TestVector::parse(operation, "test", &vector)
+            .unwrap()
+            .passes()
+    }
+
+    #[test]
+    fn test_vector_checks() {
+        let (sks, pks, msgs, sigs) = get_aggregate_verify_test_data(2, 2, 32);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+        assert!(passes(
+            TestVectorOperation::Sign,
+            json!({
+                "input": {"privkey": hex(&sks[0].to_bytes()), "message": hex(&msgs[0])},
+                "output": hex(&sigs[0].0),
+            })
+        ));
+        assert!(passes(
+            TestVectorOperation::Sign,
+            json!({
+                "input": {"privkey": hex(&[0u8; 32]), "message": hex(&msgs[0])},
+                "output": null,
+            })
+        ));
+
+        let verify = |pk: &Bls12381G1PublicKey, output: bool| {
+            json!({
+                "input": {"pubkey": hex(&pk.0), "message": hex(&msgs[0]), "signature": hex(&sigs[0].0)},
+                "output": output,
+            })
+        };
+        assert!(passes(TestVectorOperation::Verify, verify(&pks[0], true)));
+        assert!(passes(TestVectorOperation::Verify, verify(&pks[1], false)));
+        assert!(!passes(TestVectorOperation::Verify, verify(&pks[1], true)));
+
+        assert!(passes(
+            TestVectorOperation::Aggregate,
+            json!({
+                "input": [hex(&sigs[0].0), hex(&sigs[1].0)],
+                "output": hex(&agg_sig.0),
+            })
+        ));
+        assert!(passes(
+            TestVectorOperation::Aggregate,
+            json!({"input": [], "output": null})
+        ));
+
+        assert!(passes(
+            TestVectorOperation::AggregateVerify,
+            json!({
+                "input": {
+                    "pubkeys": [hex(&pks[0].0), hex(&pks[1].0)],
+                    "messages": [hex(&msgs[0]), hex(&msgs[1])],
+                    "signature": hex(&agg_sig.0),
+                },
+                "output": true,
+            })
+        ));
+
+        // Invalid public key length
+        assert!(passes(
+            TestVectorOperation::FastAggregateVerify,
+            json!({
+                "input": {"pubkeys": [hex(&pks[0].0[1..])], "message": hex(&msgs[0]), "signature": hex(&sigs[0].0)},
+                "output": false,
+            })
+        ));
+
+        assert!(matches!(
+            TestVector::parse(TestVectorOperation::Verify, "test", &json!({"input": {}})),
+            Err(TestVectorError::InvalidField(_))
+        ));
+    }
+
+    #[test]
+    fn aggregate_infinity_signature_vector() {
+        // aggregate/aggregate_infinity_signature.json of the IETF draft vectors
+        let vector = r#"{
+            "input": ["0xc00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"],
+            "output": "0xc00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        }"#;
+        assert!(passes(
+            TestVectorOperation::Aggregate,
+            serde_json::from_str(vector).unwrap()
+        ));
+    }
+
+    #[test]
+    fn eth_test_vector_checks() {
+        let (_sks, pks, _msgs, _sigs) = get_aggregate_verify_test_data(2, 2, 32);
+        let agg_pk = Bls12381G1PublicKey::aggregate(&pks).unwrap();
+
+        let no_participants = |output: bool| {
+            json!({
+                "input": {"pubkeys": [], "message": hex(&[0u8; 32]), "signature": hex(&G2_POINT_AT_INFINITY)},
+                "output": output,
+            })
+        };
+        assert!(passes(
+            TestVectorOperation::EthFastAggregateVerify,
+            no_participants(true)
+        ));
+        assert!(passes(
+            TestVectorOperation::FastAggregateVerify,
+            no_participants(false)
+        ));
+
+        assert!(passes(
+            TestVectorOperation::EthAggregatePubkeys,
+            json!({"input": [hex(&pks[0].0), hex(&pks[1].0)], "output": hex(&agg_pk.0)})
+        ));
+    }
+
+    #[test]
+    fn hash_to_g2_test_vector_checks() {
+        let dst = "QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        // Point built from the hashing phases, so that hash_to_g2_with_dst is checked against them
+        let uniform_bytes =
+            expand_message_xmd(b"abc", dst.as_bytes(), HASH_TO_G2_UNIFORM_BYTES_LENGTH);
+        let q = map_to_g2(&hash_to_field_fp2(&uniform_bytes));
+        let mut bytes = [0u8; 2 * G2_COORDINATE_LENGTH];
+        unsafe {
+            blst::blst_p2_serialize(bytes.as_mut_ptr(), &q);
+        }
+        let fp2 = |c: &[u8]| format!("{},{}", hex(&c[48..]), hex(&c[..48]));
+        let point = json!({"x": fp2(&bytes[..96]), "y": fp2(&bytes[96..])});
+
+        let suite = json!({
+            "ciphersuite": HASH_TO_G2_CIPHERSUITE,
+            "dst": dst,
+            "vectors": [{"msg": "abc", "P": point}, {"msg": "abcd", "P": point}],
+        });
+        assert_eq!(
+            check_hash_to_g2_test_vectors(&suite),
+            Ok(TestVectorReport {
+                passed: 1,
+                failed: vec!["msg \"abcd\"".to_string()],
+            })
+        );
+        assert!(check_hash_to_g2_test_vectors(
+            &json!({"ciphersuite": "P256_XMD:SHA-256_SSWU_RO_"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn load_test_vectors_from_directories() {
+        let dir = std::env::temp_dir().join(format!("test_vectors_{}", std::process::id()));
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(1, 1, 32);
+
+        // Ethereum layout in YAML, IETF layout in JSON
+        let case_dir = dir.join("verify").join("bls").join("verify_case_valid");
+        std::fs::create_dir_all(&case_dir).unwrap();
+        std::fs::write(
+            case_dir.join("data.yaml"),
+            format!(
+                "input:\n  pubkey: '{}'\n  message: '{}'\n  signature: '{}'\noutput: true\n",
+                hex(&pks[0].0),
+                hex(&msgs[0]),
+                hex(&sigs[0].0)
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("verify").join("verify_case_invalid.json"),
+            json!({
+                "input": {"pubkey": hex(&pks[0].0), "message": hex(&msgs[0][1..]), "signature": hex(&sigs[0].0)},
+                "output": true,
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let report = run_test_vectors(&dir, TestVectorOperation::Verify).unwrap();
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, vec!["verify_case_invalid.json".to_string()]);
+        assert!(matches!(
+            run_test_vectors(&dir, TestVectorOperation::Sign),
+            Err(TestVectorError::Io(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,364 @@
+use super::*;
+use blst::{blst_fr, blst_scalar};
+use rand::RngCore;
+use std::fmt;
+
+/// Share of the private key split with Shamir's secret sharing, ie. evaluation of the
+/// random polynomial of degree `threshold - 1` (with the private key as its constant term)
+/// at point `index`.
+pub struct Bls12381G1KeyShare {
+    /// Evaluation point of the polynomial, must be non-zero
+    pub index: u32,
+    pub private_key: Bls12381G1PrivateKey,
+}
+
+impl Bls12381G1KeyShare {
+    pub fn public_key(&self) -> Bls12381G1PublicKey {
+        self.private_key.public_key()
+    }
+
+    /// Signs message with the key share, producing signature share, which can be
+    /// verified against the public key of the key share.
+    /// Domain specifier tag: BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub fn sign_v1(&self, message: &[u8]) -> Bls12381G2SignatureShare {
+        Bls12381G2SignatureShare {
+            index: self.index,
+            signature: self.private_key.sign_v1(message),
+        }
+    }
+}
+
+/// Signature of the message made with the key share of given index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381G2SignatureShare {
+    pub index: u32,
+    pub signature: Bls12381G2Signature,
+}
+
+/// Splits private key into `shares` key shares, any `threshold` of which
+/// are enough to reconstruct it. Shares are given indexes 1..=shares.
+pub fn split_private_key(
+    private_key: &Bls12381G1PrivateKey,
+    threshold: usize,
+    shares: usize,
+) -> Result<Vec<Bls12381G1KeyShare>, ThresholdError> {
+    if threshold == 0 || threshold > shares || shares > u32::MAX as usize {
+        return Err(ThresholdError::InvalidThreshold(threshold, shares));
+    }
+
+    // Coefficients of the polynomial, the constant term is the private key
+    let coefficients = random_polynomial(fr_from_private_key(private_key), threshold);
+
+    (1..=shares as u32)
+        .map(|index| {
+            Ok(Bls12381G1KeyShare {
+                index,
+                private_key: fr_to_private_key(&evaluate_polynomial(&coefficients, index))?,
+            })
+        })
+        .collect()
+}
+
+/// Reconstructs private key from the first `threshold` of given key shares
+pub fn reconstruct_private_key(
+    shares: &[Bls12381G1KeyShare],
+    threshold: usize,
+) -> Result<Bls12381G1PrivateKey, ThresholdError> {
+    if threshold == 0 {
+        return Err(ThresholdError::InvalidThreshold(threshold, shares.len()));
+    }
+    if shares.len() < threshold {
+        return Err(ThresholdError::NotEnoughShares(shares.len()));
+    }
+    let shares = &shares[..threshold];
+    let indexes: Vec<u32> = shares.iter().map(|share| share.index).collect();
+    let coefficients = lagrange_coefficients(&indexes)?;
+
+    let mut secret = blst_fr::default();
+    for (share, coefficient) in shares.iter().zip(coefficients.iter()) {
+        let term = fr_mul(&fr_from_private_key(&share.private_key), coefficient);
+        secret = fr_add(&secret, &term);
+    }
+    fr_to_private_key(&secret)
+}
+
+/// Combines the first `threshold` of given signature shares into the signature
+/// of the original (split) private key, interpolating them in the exponent:
+/// sig = sum(l_i * sig_i), where l_i are Lagrange coefficients of share indexes.
+pub fn combine_signature_shares(
+    shares: &[Bls12381G2SignatureShare],
+    threshold: usize,
+) -> Result<Bls12381G2Signature, ThresholdError> {
+    if threshold == 0 {
+        return Err(ThresholdError::InvalidThreshold(threshold, shares.len()));
+    }
+    if shares.len() < threshold {
+        return Err(ThresholdError::NotEnoughShares(shares.len()));
+    }
+    let shares = &shares[..threshold];
+    let indexes: Vec<u32> = shares.iter().map(|share| share.index).collect();
+    let coefficients = lagrange_coefficients(&indexes)?;
+
+    // Projective point at infinity
+    let mut signature = blst::blst_p2::default();
+    for (share, coefficient) in shares.iter().zip(coefficients.iter()) {
+        let sig = g2_affine_from_bytes(&share.signature.0)
+            .ok_or(ThresholdError::InvalidSignatureShare(share.index))?;
+        let mut scalar = blst_scalar::default();
+        unsafe {
+            blst::blst_scalar_from_fr(&mut scalar, coefficient);
+            let mut p = blst::blst_p2::default();
+            blst::blst_p2_from_affine(&mut p, &sig);
+            let mut rp = blst::blst_p2::default();
+            blst::blst_p2_mult(&mut rp, &p, scalar.b.as_ptr(), 255);
+            let signature_ptr: *mut blst::blst_p2 = &mut signature;
+            blst::blst_p2_add_or_double(signature_ptr, signature_ptr, &rp);
+        }
+    }
+
+    let mut bytes = [0u8; Bls12381G2Signature::LENGTH];
+    unsafe {
+        blst::blst_p2_compress(bytes.as_mut_ptr(), &signature);
+    }
+    Ok(Bls12381G2Signature(bytes))
+}
+
+/// Coefficients of the random polynomial of degree `threshold - 1` with given constant term
+pub(crate) fn random_polynomial(constant: blst_fr, threshold: usize) -> Vec<blst_fr> {
+    let mut coefficients = vec![constant];
+    for _ in 1..threshold {
+        coefficients.push(random_fr());
+    }
+    coefficients
+}
+
+/// Random scalar, drawn from 64 bytes so that its reduction modulo r is not biased
+pub(crate) fn random_fr() -> blst_fr {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    fr_from_be_bytes(&bytes)
+}
+
+/// Evaluates polynomial at given point using Horner's method
+pub(crate) fn evaluate_polynomial(coefficients: &[blst_fr], index: u32) -> blst_fr {
+    let x = fr_from_u64(index as u64);
+    coefficients
+        .iter()
+        .rev()
+        .fold(blst_fr::default(), |y, coefficient| {
+            fr_add(&fr_mul(&y, &x), coefficient)
+        })
+}
+
+/// Lagrange basis polynomials of given (distinct, non-zero) indexes evaluated at 0:
+/// l_i = prod_{j != i} x_j / (x_j - x_i)
+pub(crate) fn lagrange_coefficients(indexes: &[u32]) -> Result<Vec<blst_fr>, ThresholdError> {
+    for (i, index) in indexes.iter().enumerate() {
+        if *index == 0 {
+            return Err(ThresholdError::InvalidShareIndex(*index));
+        }
+        if indexes[..i].contains(index) {
+            return Err(ThresholdError::DuplicateShareIndex(*index));
+        }
+    }
+
+    let xs: Vec<blst_fr> = indexes.iter().map(|idx| fr_from_u64(*idx as u64)).collect();
+    Ok(xs
+        .iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let mut num = fr_from_u64(1);
+            let mut den = fr_from_u64(1);
+            for (j, x_j) in xs.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                num = fr_mul(&num, x_j);
+                den = fr_mul(&den, &fr_sub(x_j, x_i));
+            }
+            let mut den_inverse = blst_fr::default();
+            unsafe {
+                blst::blst_fr_inverse(&mut den_inverse, &den);
+            }
+            fr_mul(&num, &den_inverse)
+        })
+        .collect())
+}
+
+pub(crate) fn fr_add(a: &blst_fr, b: &blst_fr) -> blst_fr {
+    let mut ret = blst_fr::default();
+    unsafe {
+        blst::blst_fr_add(&mut ret, a, b);
+    }
+    ret
+}
+
+fn fr_sub(a: &blst_fr, b: &blst_fr) -> blst_fr {
+    let mut ret = blst_fr::default();
+    unsafe {
+        blst::blst_fr_sub(&mut ret, a, b);
+    }
+    ret
+}
+
+pub(crate) fn fr_mul(a: &blst_fr, b: &blst_fr) -> blst_fr {
+    let mut ret = blst_fr::default();
+    unsafe {
+        blst::blst_fr_mul(&mut ret, a, b);
+    }
+    ret
+}
+
+pub(crate) fn fr_from_u64(n: u64) -> blst_fr {
+    let limbs = [n, 0, 0, 0];
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_fr_from_uint64(&mut fr, limbs.as_ptr());
+    }
+    fr
+}
+
+// Reduces big-endian bytes modulo the curve order
+pub(crate) fn fr_from_be_bytes(bytes: &[u8]) -> blst_fr {
+    let mut scalar = blst_scalar::default();
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_scalar_from_be_bytes(&mut scalar, bytes.as_ptr(), bytes.len());
+        blst::blst_fr_from_scalar(&mut fr, &scalar);
+    }
+    fr
+}
+
+pub(crate) fn fr_from_private_key(private_key: &Bls12381G1PrivateKey) -> blst_fr {
+    fr_from_be_bytes(&private_key.to_bytes())
+}
+
+pub(crate) fn fr_to_private_key(fr: &blst_fr) -> Result<Bls12381G1PrivateKey, ThresholdError> {
+    let mut scalar = blst_scalar::default();
+    let mut bytes = [0u8; Bls12381G1PrivateKey::LENGTH];
+    unsafe {
+        blst::blst_scalar_from_fr(&mut scalar, fr);
+        blst::blst_bendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+    }
+    Bls12381G1PrivateKey::from_bytes(&bytes).map_err(ThresholdError::BlsError)
+}
+
+/// Represents an error when splitting or reconstructing private key
+/// or combining signature shares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThresholdError {
+    // Threshold must be in range 1..=shares
+    InvalidThreshold(usize, usize),
+    NotEnoughShares(usize),
+    // Share index must be non-zero, as it is the point the polynomial is evaluated at
+    InvalidShareIndex(u32),
+    DuplicateShareIndex(u32),
+    // Signature share of given index is not a valid G2 point
+    InvalidSignatureShare(u32),
+    // DKG dealing of given dealer is malformed or its shares do not match its commitments
+    InvalidDealing(u32),
+    BlsError(BlsError),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ThresholdError {}
+
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_and_reconstruct() {
+        let sk = Bls12381G1PrivateKey::from_u64(1234567).unwrap();
+        for (threshold, shares) in [(1, 1), (1, 3), (2, 3), (3, 5), (5, 5)] {
+            let mut key_shares = split_private_key(&sk, threshold, shares).unwrap();
+            assert_eq!(key_shares.len(), shares);
+            assert!(reconstruct_private_key(&key_shares, threshold).unwrap() == sk);
+
+            // Any subset of threshold size works
+            key_shares.reverse();
+            assert!(reconstruct_private_key(&key_shares, threshold).unwrap() == sk);
+
+            if threshold > 1 {
+                // Fewer shares than threshold reveal nothing about the key
+                assert!(reconstruct_private_key(&key_shares, threshold - 1).unwrap() != sk);
+                assert_eq!(
+                    reconstruct_private_key(&key_shares[..threshold - 1], threshold).err(),
+                    Some(ThresholdError::NotEnoughShares(threshold - 1))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn split_and_reconstruct_errors() {
+        let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+        assert_eq!(
+            split_private_key(&sk, 0, 3).err(),
+            Some(ThresholdError::InvalidThreshold(0, 3))
+        );
+        assert_eq!(
+            split_private_key(&sk, 4, 3).err(),
+            Some(ThresholdError::InvalidThreshold(4, 3))
+        );
+
+        let mut key_shares = split_private_key(&sk, 2, 3).unwrap();
+        key_shares[1].index = key_shares[0].index;
+        assert_eq!(
+            reconstruct_private_key(&key_shares, 2).err(),
+            Some(ThresholdError::DuplicateShareIndex(1))
+        );
+        key_shares[1].index = 0;
+        assert_eq!(
+            reconstruct_private_key(&key_shares, 2).err(),
+            Some(ThresholdError::InvalidShareIndex(0))
+        );
+    }
+
+    #[test]
+    fn sign_and_combine_signature_shares() {
+        let sk = Bls12381G1PrivateKey::from_u64(1234567).unwrap();
+        let pk = sk.public_key();
+        let msg = b"message";
+
+        let key_shares = split_private_key(&sk, 3, 5).unwrap();
+        let mut sig_shares: Vec<Bls12381G2SignatureShare> =
+            key_shares.iter().map(|share| share.sign_v1(msg)).collect();
+        for (key_share, sig_share) in key_shares.iter().zip(sig_shares.iter()) {
+            assert!(verify_bls12381_v1(
+                msg,
+                &key_share.public_key(),
+                &sig_share.signature
+            ));
+        }
+
+        // BLS signatures are deterministic, so combined signature equals the one of the key
+        let sig = combine_signature_shares(&sig_shares, 3).unwrap();
+        assert_eq!(sig, sk.sign_v1(msg));
+        assert!(verify_bls12381_v1(msg, &pk, &sig));
+
+        sig_shares.rotate_left(2);
+        assert_eq!(combine_signature_shares(&sig_shares, 3).unwrap(), sig);
+        assert!(!verify_bls12381_v1(
+            msg,
+            &pk,
+            &combine_signature_shares(&sig_shares, 2).unwrap()
+        ));
+        assert_eq!(
+            combine_signature_shares(&sig_shares[..2], 3),
+            Err(ThresholdError::NotEnoughShares(2))
+        );
+
+        sig_shares[1].signature = Bls12381G2Signature([0xff; 96]);
+        assert_eq!(
+            combine_signature_shares(&sig_shares, 3),
+            Err(ThresholdError::InvalidSignatureShare(sig_shares[1].index))
+        );
+    }
+}
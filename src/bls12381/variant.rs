@@ -0,0 +1,626 @@
+use super::*;
+
+/// Test data returned by generators: private keys, public keys, messages and signatures
+pub type TestData<V, M> = (
+    Vec<<V as Bls12381Variant>::PrivateKey>,
+    Vec<<V as Bls12381Variant>::PublicKey>,
+    M,
+    Vec<<V as Bls12381Variant>::Signature>,
+);
+
+/// Kind of the test data generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "fixtures",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TestDataKind {
+    AggregateVerify,
+    FastAggregateVerify,
+}
+
+/// Generates test data, unless it is replayed from fixtures (see replay_or_generate)
+fn generate_test_data<V: Bls12381Variant>(
+    kind: TestDataKind,
+    cnt: u32,
+    msg_cnt: u32,
+    msg_size: usize,
+    generate: impl FnOnce() -> TestData<V, Vec<Vec<u8>>>,
+) -> TestData<V, Vec<Vec<u8>>> {
+    #[cfg(feature = "fixtures")]
+    return replay_or_generate::<V>(kind, cnt, msg_cnt, msg_size, generate);
+    #[cfg(not(feature = "fixtures"))]
+    {
+        let _ = (kind, cnt, msg_cnt, msg_size);
+        generate()
+    }
+}
+
+/// Decoding, encoding and validation of the native (blst) points, so that these steps
+/// can be measured separately for either variant
+pub trait NativePoint: Sized {
+    type Compressed: AsRef<[u8]>;
+    type Uncompressed: AsRef<[u8]>;
+
+    /// Parses compressed or uncompressed encoding, the point is checked to be on the curve
+    fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError>;
+    /// Checks the point to be in the subgroup (and public keys not to be infinity)
+    fn validate(&self) -> Result<(), BlsError>;
+    fn compress(&self) -> Self::Compressed;
+    fn serialize(&self) -> Self::Uncompressed;
+    fn uncompress(bytes: &[u8]) -> Result<Self, BlsError>;
+    fn deserialize(bytes: &[u8]) -> Result<Self, BlsError>;
+}
+
+macro_rules! impl_native_point {
+    ($point:ty, $compressed:literal, $uncompressed:literal, $validate:expr) => {
+        impl NativePoint for $point {
+            type Compressed = [u8; $compressed];
+            type Uncompressed = [u8; $uncompressed];
+
+            fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+                Ok(<$point>::from_bytes(bytes)?)
+            }
+
+            fn validate(&self) -> Result<(), BlsError> {
+                Ok($validate(self)?)
+            }
+
+            fn compress(&self) -> Self::Compressed {
+                <$point>::compress(self)
+            }
+
+            fn serialize(&self) -> Self::Uncompressed {
+                <$point>::serialize(self)
+            }
+
+            fn uncompress(bytes: &[u8]) -> Result<Self, BlsError> {
+                Ok(<$point>::uncompress(bytes)?)
+            }
+
+            fn deserialize(bytes: &[u8]) -> Result<Self, BlsError> {
+                Ok(<$point>::deserialize(bytes)?)
+            }
+        }
+    };
+}
+
+impl_native_point!(
+    blst::min_pk::PublicKey,
+    48,
+    96,
+    blst::min_pk::PublicKey::validate
+);
+impl_native_point!(
+    blst::min_pk::Signature,
+    96,
+    192,
+    |sig: &blst::min_pk::Signature| sig.validate(false)
+);
+impl_native_point!(
+    blst::min_sig::PublicKey,
+    96,
+    192,
+    blst::min_sig::PublicKey::validate
+);
+impl_native_point!(
+    blst::min_sig::Signature,
+    48,
+    96,
+    |sig: &blst::min_sig::Signature| sig.validate(false)
+);
+
+/// Common interface of the BLS12-381 signature variants (minimal public key size and
+/// minimal signature size), so that benchmarks can target either of them.
+pub trait Bls12381Variant: Sized {
+    type PrivateKey: Sync;
+    type PublicKey: Copy;
+    type Signature: Copy + Send;
+    type NativePublicKey: NativePoint;
+    type NativeSignature: NativePoint;
+
+    /// Whether calc models were fitted for this variant
+    const CALC_MODELS: bool;
+    /// Name used in file names of the cached test data
+    const NAME: &'static str;
+
+    fn get_aggregate_verify_test_data(
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+    ) -> TestData<Self, Vec<Vec<u8>>>;
+    fn get_aggregate_verify_test_data2(msg_sizes: &[usize]) -> TestData<Self, Vec<Vec<u8>>>;
+    fn get_fast_aggregate_verify_test_data(cnt: u32, msg_size: usize) -> TestData<Self, Vec<u8>>;
+
+    fn private_key_from_u64(n: u64) -> Self::PrivateKey;
+    /// KeyGen of the BLS signature draft, input key material must be at least 32 bytes
+    fn private_key_from_ikm(ikm: &[u8]) -> Result<Self::PrivateKey, BlsError>;
+    fn private_key_from_bytes(bytes: &[u8]) -> Result<Self::PrivateKey, BlsError>;
+    fn private_key_to_bytes(sk: &Self::PrivateKey) -> Vec<u8>;
+    fn public_key(sk: &Self::PrivateKey) -> Self::PublicKey;
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, BlsError>;
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
+    fn sign(sk: &Self::PrivateKey, message: &[u8]) -> Self::Signature;
+    fn sign_with_dst(sk: &Self::PrivateKey, message: &[u8], dst: &Bls12381Dst) -> Self::Signature;
+    /// Signs message read in chunks, using the default ciphersuite tag unless dst is given
+    fn sign_reader<R: std::io::Read>(
+        sk: &Self::PrivateKey,
+        reader: R,
+        dst: Option<&Bls12381Dst>,
+    ) -> std::io::Result<Self::Signature>;
+    fn pop_prove(sk: &Self::PrivateKey) -> Self::Signature;
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, BlsError>;
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8>;
+    /// Curve points outside of the prime order subgroup, for negative tests
+    fn non_subgroup_public_key() -> Self::PublicKey;
+    fn non_subgroup_signature() -> Self::Signature;
+    /// All checks done on the points during verification (see Bls12381G1PublicKey::is_valid)
+    fn public_key_is_valid(public_key: &Self::PublicKey) -> bool;
+    fn signature_is_valid(signature: &Self::Signature) -> bool;
+
+    fn pop_verify(public_key: &Self::PublicKey, proof: &Self::Signature) -> bool;
+    fn verify(message: &[u8], public_key: &Self::PublicKey, signature: &Self::Signature) -> bool;
+    /// Verification by the reference (non-blst) implementation
+    #[cfg(feature = "cross-check")]
+    fn reference_verify(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+    ) -> bool;
+    fn verify_with_dst(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+        dst: &Bls12381Dst,
+    ) -> bool;
+    fn aggregate_verify(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool;
+    /// Same as aggregate_verify, measuring its phases separately (where calc models exist)
+    fn aggregate_verify_phases(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool;
+    fn aggregate_verify_threaded(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool;
+    /// Proofs of possession of the keys are assumed verified, as with benchmark test data
+    fn fast_aggregate_verify(
+        message: &[u8],
+        public_keys: &[Self::PublicKey],
+        signature: &Self::Signature,
+    ) -> bool;
+    fn verify_multiple_aggregate_signatures(
+        pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)],
+    ) -> bool;
+    fn batch_verify(pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)]) -> bool;
+    fn batch_verify_msm(pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)])
+        -> bool;
+
+    fn aggregate_signatures(signatures: &[Self::Signature]) -> Result<Self::Signature, BlsError>;
+    fn aggregate_signatures_prevalidated(
+        signatures: &[Self::Signature],
+    ) -> Result<Self::Signature, BlsError>;
+    fn aggregate_public_keys(public_keys: &[Self::PublicKey]) -> Result<Self::PublicKey, BlsError>;
+    fn aggregate_public_keys_prevalidated(
+        public_keys: &[Self::PublicKey],
+    ) -> Result<Self::PublicKey, BlsError>;
+}
+
+/// Variant with 48-byte G1 public keys and 96-byte G2 signatures
+pub struct MinPk;
+
+/// Variant with 96-byte G2 public keys and 48-byte G1 signatures
+pub struct MinSig;
+
+impl Bls12381Variant for MinPk {
+    type PrivateKey = Bls12381G1PrivateKey;
+    type PublicKey = Bls12381G1PublicKey;
+    type Signature = Bls12381G2Signature;
+    type NativePublicKey = blst::min_pk::PublicKey;
+    type NativeSignature = blst::min_pk::Signature;
+
+    const CALC_MODELS: bool = true;
+    const NAME: &'static str = "min-pk";
+
+    fn get_aggregate_verify_test_data(
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+    ) -> TestData<Self, Vec<Vec<u8>>> {
+        generate_test_data::<Self>(
+            TestDataKind::AggregateVerify,
+            cnt,
+            msg_cnt,
+            msg_size,
+            || get_aggregate_verify_test_data(cnt, msg_cnt, msg_size),
+        )
+    }
+
+    fn get_aggregate_verify_test_data2(msg_sizes: &[usize]) -> TestData<Self, Vec<Vec<u8>>> {
+        get_aggregate_verify_test_data2(msg_sizes)
+    }
+
+    fn get_fast_aggregate_verify_test_data(cnt: u32, msg_size: usize) -> TestData<Self, Vec<u8>> {
+        let (sks, pks, mut msgs, sigs) =
+            generate_test_data::<Self>(TestDataKind::FastAggregateVerify, cnt, 1, msg_size, || {
+                let (sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(cnt, msg_size);
+                (sks, pks, vec![msg], sigs)
+            });
+        (sks, pks, msgs.pop().unwrap_or_default(), sigs)
+    }
+
+    fn private_key_from_u64(n: u64) -> Self::PrivateKey {
+        Bls12381G1PrivateKey::from_u64(n).unwrap()
+    }
+
+    fn private_key_from_ikm(ikm: &[u8]) -> Result<Self::PrivateKey, BlsError> {
+        Bls12381G1PrivateKey::key_gen(ikm)
+    }
+
+    fn private_key_from_bytes(bytes: &[u8]) -> Result<Self::PrivateKey, BlsError> {
+        Bls12381G1PrivateKey::from_bytes(bytes)
+    }
+
+    fn private_key_to_bytes(sk: &Self::PrivateKey) -> Vec<u8> {
+        sk.to_bytes()
+    }
+
+    fn public_key(sk: &Self::PrivateKey) -> Self::PublicKey {
+        sk.public_key()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, BlsError> {
+        Self::PublicKey::try_from(bytes)
+    }
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.0.to_vec()
+    }
+
+    fn sign(sk: &Self::PrivateKey, message: &[u8]) -> Self::Signature {
+        sk.sign_v1(message)
+    }
+
+    fn sign_with_dst(sk: &Self::PrivateKey, message: &[u8], dst: &Bls12381Dst) -> Self::Signature {
+        sk.sign_with_dst_v1(message, dst)
+    }
+
+    fn sign_reader<R: std::io::Read>(
+        sk: &Self::PrivateKey,
+        reader: R,
+        dst: Option<&Bls12381Dst>,
+    ) -> std::io::Result<Self::Signature> {
+        match dst {
+            Some(dst) => sk.sign_reader_with_dst_v1(reader, dst.as_bytes()),
+            None => sk.sign_reader_v1(reader),
+        }
+    }
+
+    fn pop_prove(sk: &Self::PrivateKey) -> Self::Signature {
+        sk.pop_prove_v1()
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, BlsError> {
+        Bls12381G2Signature::try_from(bytes)
+    }
+
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+        signature.to_vec()
+    }
+
+    fn non_subgroup_public_key() -> Self::PublicKey {
+        Bls12381G1PublicKey(g1_non_subgroup_point(0))
+    }
+
+    fn non_subgroup_signature() -> Self::Signature {
+        Bls12381G2Signature(g2_non_subgroup_point(0))
+    }
+
+    fn public_key_is_valid(public_key: &Self::PublicKey) -> bool {
+        public_key.is_valid()
+    }
+
+    fn signature_is_valid(signature: &Self::Signature) -> bool {
+        signature.is_valid()
+    }
+
+    fn pop_verify(public_key: &Self::PublicKey, proof: &Self::Signature) -> bool {
+        pop_verify_bls12381_v1(public_key, proof)
+    }
+
+    fn verify(message: &[u8], public_key: &Self::PublicKey, signature: &Self::Signature) -> bool {
+        verify_bls12381_v1(message, public_key, signature)
+    }
+
+    #[cfg(feature = "cross-check")]
+    fn reference_verify(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+    ) -> bool {
+        reference_verify_bls12381_v1(message, public_key, signature)
+    }
+
+    fn verify_with_dst(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+        dst: &Bls12381Dst,
+    ) -> bool {
+        verify_with_dst_bls12381_v1(message, public_key, signature, dst)
+    }
+
+    fn aggregate_verify(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        aggregate_verify_bls12381_v1(pub_keys_and_msgs, signature)
+    }
+
+    fn aggregate_verify_phases(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        aggregate_verify_bls12381_v1_phases(pub_keys_and_msgs, signature)
+    }
+
+    fn aggregate_verify_threaded(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        aggregate_verify_bls12381_v1_threaded(pub_keys_and_msgs, signature)
+    }
+
+    fn fast_aggregate_verify(
+        message: &[u8],
+        public_keys: &[Self::PublicKey],
+        signature: &Self::Signature,
+    ) -> bool {
+        fast_aggregate_verify_bls12381_v1_unchecked(message, public_keys, signature)
+    }
+
+    fn verify_multiple_aggregate_signatures(
+        pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)],
+    ) -> bool {
+        verify_multiple_aggregate_signatures_bls12381_v1(pub_keys_msgs_sigs)
+    }
+
+    fn batch_verify(pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)]) -> bool {
+        batch_verify_bls12381_v1(pub_keys_msgs_sigs)
+    }
+
+    fn batch_verify_msm(
+        pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)],
+    ) -> bool {
+        batch_verify_bls12381_v1_msm(pub_keys_msgs_sigs)
+    }
+
+    fn aggregate_signatures(signatures: &[Self::Signature]) -> Result<Self::Signature, BlsError> {
+        Bls12381G2Signature::aggregate(signatures, true)
+    }
+
+    fn aggregate_signatures_prevalidated(
+        signatures: &[Self::Signature],
+    ) -> Result<Self::Signature, BlsError> {
+        Bls12381G2Signature::aggregate(signatures, false)
+    }
+
+    fn aggregate_public_keys(public_keys: &[Self::PublicKey]) -> Result<Self::PublicKey, BlsError> {
+        Bls12381G1PublicKey::aggregate(public_keys)
+    }
+
+    fn aggregate_public_keys_prevalidated(
+        public_keys: &[Self::PublicKey],
+    ) -> Result<Self::PublicKey, BlsError> {
+        Bls12381G1PublicKey::aggregate_prevalidated(public_keys)
+    }
+}
+
+impl Bls12381Variant for MinSig {
+    type PrivateKey = min_sig::Bls12381G2PrivateKey;
+    type PublicKey = min_sig::Bls12381G2PublicKey;
+    type Signature = min_sig::Bls12381G1Signature;
+    type NativePublicKey = blst::min_sig::PublicKey;
+    type NativeSignature = blst::min_sig::Signature;
+
+    const CALC_MODELS: bool = false;
+    const NAME: &'static str = "min-sig";
+
+    fn get_aggregate_verify_test_data(
+        cnt: u32,
+        msg_cnt: u32,
+        msg_size: usize,
+    ) -> TestData<Self, Vec<Vec<u8>>> {
+        generate_test_data::<Self>(
+            TestDataKind::AggregateVerify,
+            cnt,
+            msg_cnt,
+            msg_size,
+            || min_sig::get_aggregate_verify_test_data(cnt, msg_cnt, msg_size),
+        )
+    }
+
+    fn get_aggregate_verify_test_data2(msg_sizes: &[usize]) -> TestData<Self, Vec<Vec<u8>>> {
+        min_sig::get_aggregate_verify_test_data2(msg_sizes)
+    }
+
+    fn get_fast_aggregate_verify_test_data(cnt: u32, msg_size: usize) -> TestData<Self, Vec<u8>> {
+        let (sks, pks, mut msgs, sigs) =
+            generate_test_data::<Self>(TestDataKind::FastAggregateVerify, cnt, 1, msg_size, || {
+                let (sks, pks, msg, sigs) =
+                    min_sig::get_fast_aggregate_verify_test_data(cnt, msg_size);
+                (sks, pks, vec![msg], sigs)
+            });
+        (sks, pks, msgs.pop().unwrap_or_default(), sigs)
+    }
+
+    fn private_key_from_u64(n: u64) -> Self::PrivateKey {
+        min_sig::Bls12381G2PrivateKey::from_u64(n).unwrap()
+    }
+
+    fn private_key_from_ikm(ikm: &[u8]) -> Result<Self::PrivateKey, BlsError> {
+        min_sig::Bls12381G2PrivateKey::key_gen(ikm)
+    }
+
+    fn private_key_from_bytes(bytes: &[u8]) -> Result<Self::PrivateKey, BlsError> {
+        min_sig::Bls12381G2PrivateKey::from_bytes(bytes)
+    }
+
+    fn private_key_to_bytes(sk: &Self::PrivateKey) -> Vec<u8> {
+        sk.to_bytes()
+    }
+
+    fn public_key(sk: &Self::PrivateKey) -> Self::PublicKey {
+        sk.public_key()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, BlsError> {
+        Self::PublicKey::try_from(bytes)
+    }
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.0.to_vec()
+    }
+
+    fn sign(sk: &Self::PrivateKey, message: &[u8]) -> Self::Signature {
+        sk.sign_v1(message)
+    }
+
+    fn sign_with_dst(sk: &Self::PrivateKey, message: &[u8], dst: &Bls12381Dst) -> Self::Signature {
+        sk.sign_with_dst_v1(message, dst)
+    }
+
+    fn sign_reader<R: std::io::Read>(
+        sk: &Self::PrivateKey,
+        reader: R,
+        dst: Option<&Bls12381Dst>,
+    ) -> std::io::Result<Self::Signature> {
+        match dst {
+            Some(dst) => sk.sign_reader_with_dst_v1(reader, dst.as_bytes()),
+            None => sk.sign_reader_v1(reader),
+        }
+    }
+
+    fn pop_prove(sk: &Self::PrivateKey) -> Self::Signature {
+        sk.pop_prove_v1()
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, BlsError> {
+        min_sig::Bls12381G1Signature::try_from(bytes)
+    }
+
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+        signature.to_vec()
+    }
+
+    fn non_subgroup_public_key() -> Self::PublicKey {
+        min_sig::Bls12381G2PublicKey(g2_non_subgroup_point(0))
+    }
+
+    fn non_subgroup_signature() -> Self::Signature {
+        min_sig::Bls12381G1Signature(g1_non_subgroup_point(0))
+    }
+
+    fn public_key_is_valid(public_key: &Self::PublicKey) -> bool {
+        public_key.is_valid()
+    }
+
+    fn signature_is_valid(signature: &Self::Signature) -> bool {
+        signature.is_valid()
+    }
+
+    fn pop_verify(public_key: &Self::PublicKey, proof: &Self::Signature) -> bool {
+        min_sig::pop_verify_bls12381_v1(public_key, proof)
+    }
+
+    fn verify(message: &[u8], public_key: &Self::PublicKey, signature: &Self::Signature) -> bool {
+        min_sig::verify_bls12381_v1(message, public_key, signature)
+    }
+
+    #[cfg(feature = "cross-check")]
+    fn reference_verify(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+    ) -> bool {
+        reference_verify_min_sig_bls12381_v1(message, public_key, signature)
+    }
+
+    fn verify_with_dst(
+        message: &[u8],
+        public_key: &Self::PublicKey,
+        signature: &Self::Signature,
+        dst: &Bls12381Dst,
+    ) -> bool {
+        min_sig::verify_with_dst_bls12381_v1(message, public_key, signature, dst)
+    }
+
+    fn aggregate_verify(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        min_sig::aggregate_verify_bls12381_v1(pub_keys_and_msgs, signature)
+    }
+
+    fn aggregate_verify_phases(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        Self::aggregate_verify(pub_keys_and_msgs, signature)
+    }
+
+    fn aggregate_verify_threaded(
+        pub_keys_and_msgs: &[(Self::PublicKey, &[u8])],
+        signature: &Self::Signature,
+    ) -> bool {
+        min_sig::aggregate_verify_bls12381_v1_threaded(pub_keys_and_msgs, signature)
+    }
+
+    fn fast_aggregate_verify(
+        message: &[u8],
+        public_keys: &[Self::PublicKey],
+        signature: &Self::Signature,
+    ) -> bool {
+        min_sig::fast_aggregate_verify_bls12381_v1(message, public_keys, signature)
+    }
+
+    fn verify_multiple_aggregate_signatures(
+        pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)],
+    ) -> bool {
+        min_sig::verify_multiple_aggregate_signatures_bls12381_v1(pub_keys_msgs_sigs)
+    }
+
+    fn batch_verify(pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)]) -> bool {
+        min_sig::batch_verify_bls12381_v1(pub_keys_msgs_sigs)
+    }
+
+    fn batch_verify_msm(
+        pub_keys_msgs_sigs: &[(Self::PublicKey, Vec<u8>, Self::Signature)],
+    ) -> bool {
+        min_sig::batch_verify_bls12381_v1_msm(pub_keys_msgs_sigs)
+    }
+
+    fn aggregate_signatures(signatures: &[Self::Signature]) -> Result<Self::Signature, BlsError> {
+        min_sig::Bls12381G1Signature::aggregate(signatures, true)
+    }
+
+    fn aggregate_signatures_prevalidated(
+        signatures: &[Self::Signature],
+    ) -> Result<Self::Signature, BlsError> {
+        min_sig::Bls12381G1Signature::aggregate(signatures, false)
+    }
+
+    fn aggregate_public_keys(public_keys: &[Self::PublicKey]) -> Result<Self::PublicKey, BlsError> {
+        min_sig::Bls12381G2PublicKey::aggregate(public_keys)
+    }
+
+    fn aggregate_public_keys_prevalidated(
+        public_keys: &[Self::PublicKey],
+    ) -> Result<Self::PublicKey, BlsError> {
+        min_sig::Bls12381G2PublicKey::aggregate_prevalidated(public_keys)
+    }
+}
@@ -8,20 +8,20 @@ fn add(a: u32, b: u32) -> u32 {
     a.checked_add(b).unwrap()
 }
 
-#[inline]
-fn sub(a: u32, b: u32) -> u32 {
-    a.checked_sub(b).unwrap()
-}
-
 #[inline]
 fn mul(a: u32, b: u32) -> u32 {
     a.checked_mul(b).unwrap()
 }
 
 pub fn calc_aggregate_verify_instructions_no_threaded(sizes: &[usize]) -> u32 {
-    let mut instructions_cnt = 0;
+    // Public keys parsing and validation
+    let mut instructions_cnt = add(
+        calc_public_key_from_bytes_instructions(cast(sizes.len())),
+        calc_public_key_validate_instructions(cast(sizes.len())),
+    );
+    // Hashing messages and accumulating them in pairing context
     for s in sizes {
-        instructions_cnt = add(add(instructions_cnt, mul(35, cast(*s))), 2620296);
+        instructions_cnt = add(add(instructions_cnt, mul(35, cast(*s))), 2390296);
     }
     let multiplier = cast(sizes.len() / 8);
 
@@ -63,5 +63,308 @@ pub fn calc_fast_aggregate_verify_instructions(cnt: u32, size: usize) -> u32 {
 }
 
 pub fn calc_signature_aggregate_instructions(cnt: u32) -> u32 {
-    sub(mul(cnt, 879554), 500000)
+    // Signature decompression, subgroup check and addition per each signature
+    add(mul(cnt, 879554), 83573)
+}
+
+pub fn calc_sign_instructions(size: usize) -> u32 {
+    // Hash to G2 (per byte cost same as in verify) and G2 scalar multiplication
+    add(mul(cast(size), 36), 5150000)
+}
+
+pub fn calc_pop_prove_instructions() -> u32 {
+    // Signature of the 48-byte public key
+    calc_sign_instructions(48)
+}
+
+pub fn calc_pop_verify_instructions() -> u32 {
+    // Verification of the signature of the 48-byte public key
+    calc_verify_instructions(48)
+}
+
+pub fn calc_verify_aug_instructions(size: usize) -> u32 {
+    // Verification of the message prepended with the 48-byte public key
+    calc_verify_instructions(size + 48)
+}
+
+pub fn calc_aggregate_verify_aug_instructions(sizes: &[usize]) -> u32 {
+    // Each message is prepended with the 48-byte public key
+    let sizes: Vec<usize> = sizes.iter().map(|s| s + 48).collect();
+    calc_aggregate_verify_instructions_no_threaded(&sizes)
+}
+
+pub fn calc_miller_loop_instructions(cnt: u32) -> u32 {
+    // Same as per message pairing commit increment in aggregate verify
+    mul(cnt, 1969343)
+}
+
+pub fn calc_final_exp_instructions() -> u32 {
+    3027639
+}
+
+pub fn calc_signature_from_bytes_instructions(cnt: u32) -> u32 {
+    // Decompression of the G2 point
+    mul(cnt, 281125)
+}
+
+pub fn calc_signature_validate_instructions(cnt: u32) -> u32 {
+    // Infinity and subgroup check of the G2 point
+    mul(cnt, 583573)
+}
+
+pub fn calc_public_key_from_bytes_instructions(cnt: u32) -> u32 {
+    // Decompression of the G1 point
+    mul(cnt, 95000)
+}
+
+pub fn calc_public_key_validate_instructions(cnt: u32) -> u32 {
+    // Infinity and subgroup check of the G1 point
+    mul(cnt, 135000)
+}
+
+pub fn calc_gt_mul_instructions(cnt: u32) -> u32 {
+    mul(cnt, 20000)
+}
+
+pub fn calc_gt_pow_instructions(cnt: u32, exp_bits: u32) -> u32 {
+    // Cyclotomic squaring per bit and multiplication for every other bit on average
+    mul(mul(cnt, exp_bits), 19000)
+}
+
+pub fn calc_expand_message_xmd_instructions(size: usize) -> u32 {
+    // SHA-256 of the message and the fixed number of blocks of the uniform bytes
+    add(mul(cast(size), 35), 45000)
+}
+
+pub fn calc_expand_message_xof_instructions(size: usize) -> u32 {
+    // SHAKE-256 absorbs message with length and DST suffix (48 bytes)
+    // and needs one extra permutation to squeeze 256 uniform bytes
+    add(calc_keccak256_instructions(size + 48), 2900)
+}
+
+pub fn calc_map_to_g2_instructions() -> u32 {
+    // SSWU map of two field elements, point addition and cofactor clearing
+    1150000
+}
+
+pub fn calc_g1_add_instructions(cnt: u32) -> u32 {
+    mul(cnt, 4700)
+}
+
+pub fn calc_g1_add_affine_instructions(cnt: u32) -> u32 {
+    mul(cnt, 3800)
+}
+
+pub fn calc_g1_double_instructions(cnt: u32) -> u32 {
+    mul(cnt, 2300)
+}
+
+pub fn calc_g2_add_instructions(cnt: u32) -> u32 {
+    mul(cnt, 12500)
+}
+
+pub fn calc_g2_add_affine_instructions(cnt: u32) -> u32 {
+    mul(cnt, 10200)
+}
+
+pub fn calc_g2_double_instructions(cnt: u32) -> u32 {
+    mul(cnt, 6100)
+}
+
+pub fn calc_verify_multiple_aggregate_signatures_instructions(cnt: u32, size: usize) -> u32 {
+    // Per triple: public key and signature parsing and validation, hashing message,
+    // multiplication by random scalar and Miller loop
+    let per_triple = add(mul(cast(size), 35), 3600000);
+    // Final exponentiation
+    add(mul(cnt, per_triple), 3100000)
+}
+
+pub fn calc_sequential_verify_instructions(cnt: u32, size: usize) -> u32 {
+    mul(cnt, calc_verify_instructions(size))
+}
+
+pub fn calc_batch_verify_instructions(cnt: u32, size: usize) -> u32 {
+    // Per triple: public key and signature parsing and validation, 64-bit scalar
+    // multiplications in G1 and G2, hashing message and Miller loop
+    let per_triple = add(mul(cast(size), 36), 5200000);
+    // Miller loop of aggregated signature and final exponentiation
+    add(mul(cnt, per_triple), 6500000)
+}
+
+/// Returns the smallest batch size for which batch verification is expected to be
+/// cheaper than verifying each signature separately
+pub fn calc_batch_verify_crossover(size: usize) -> u32 {
+    let mut cnt = 1;
+    while calc_batch_verify_instructions(cnt, size)
+        >= calc_sequential_verify_instructions(cnt, size)
+    {
+        cnt = add(cnt, 1);
+    }
+    cnt
+}
+
+pub fn calc_g2_decompress_instructions(cnt: u32) -> u32 {
+    // Square root in Fp2
+    mul(cnt, 278000)
+}
+
+pub fn calc_signature_aggregate_prevalidated_instructions(cnt: u32) -> u32 {
+    // Subgroup checks skipped, signature decompression and addition remain
+    mul(cnt, 296000)
+}
+
+pub fn calc_public_key_aggregate_instructions(cnt: u32) -> u32 {
+    mul(cnt, 234000)
+}
+
+pub fn calc_public_key_aggregate_prevalidated_instructions(cnt: u32) -> u32 {
+    // Subgroup checks skipped, public key decompression and addition remain
+    mul(cnt, 99000)
+}
+
+pub fn calc_precompute_lines_instructions() -> u32 {
+    // Lines of the public key and of the negated G2 generator
+    1400000
+}
+
+pub fn calc_precomputed_verify_instructions(cnt: u32, size: usize) -> u32 {
+    // Signature decompression and subgroup check, hash to G1, two Miller loops
+    // with precomputed lines and final exponentiation
+    mul(cnt, add(mul(cast(size), 36), 6900000))
+}
+
+// EIP-2537 precompiles
+// Decoding of the point includes field element range checks and on-curve check,
+// subgroup checks are same as in public key and signature validation
+const PRECOMPILE_G1_DECODE: u32 = 6000;
+const PRECOMPILE_G2_DECODE: u32 = 12000;
+// Scalar multiplication by 256-bit scalar, G2 one derived from sign model
+// (sign without hash to G2)
+const PRECOMPILE_G1_MUL: u32 = 1500000;
+const PRECOMPILE_G2_MUL: u32 = 3950000;
+
+pub fn calc_precompile_g1_add_instructions() -> u32 {
+    add(
+        mul(2, PRECOMPILE_G1_DECODE),
+        calc_g1_add_affine_instructions(1),
+    )
+}
+
+pub fn calc_precompile_g2_add_instructions() -> u32 {
+    add(
+        mul(2, PRECOMPILE_G2_DECODE),
+        calc_g2_add_affine_instructions(1),
+    )
+}
+
+pub fn calc_precompile_g1_msm_instructions(cnt: u32) -> u32 {
+    // Linear upper bound, Pippenger's algorithm amortizes multiplications for more points
+    let per_point = add(
+        add(
+            PRECOMPILE_G1_DECODE,
+            calc_public_key_validate_instructions(1),
+        ),
+        PRECOMPILE_G1_MUL,
+    );
+    mul(cnt, per_point)
+}
+
+pub fn calc_precompile_g2_msm_instructions(cnt: u32) -> u32 {
+    // Linear upper bound, Pippenger's algorithm amortizes multiplications for more points
+    let per_point = add(
+        add(
+            PRECOMPILE_G2_DECODE,
+            calc_signature_validate_instructions(1),
+        ),
+        PRECOMPILE_G2_MUL,
+    );
+    mul(cnt, per_point)
+}
+
+pub fn calc_precompile_pairing_instructions(cnt: u32) -> u32 {
+    // Per pair: decoding, subgroup checks and Miller loop
+    let per_pair = add(
+        add(PRECOMPILE_G1_DECODE, PRECOMPILE_G2_DECODE),
+        add(
+            add(
+                calc_public_key_validate_instructions(1),
+                calc_signature_validate_instructions(1),
+            ),
+            calc_miller_loop_instructions(1),
+        ),
+    );
+    add(mul(cnt, per_pair), calc_final_exp_instructions())
+}
+
+pub fn calc_precompile_map_fp_to_g1_instructions() -> u32 {
+    // SSWU map of single field element and cofactor clearing
+    300000
+}
+
+pub fn calc_precompile_map_fp2_to_g2_instructions() -> u32 {
+    // SSWU map of single field element and cofactor clearing,
+    // map_to_g2 maps two of them and adds the points
+    900000
+}
+
+pub fn calc_keccak256_instructions(size: usize) -> u32 {
+    // Keccak-f permutation per each 136-byte block (including padding block)
+    let blocks = add(cast(size / 136), 1);
+    add(mul(blocks, 2900), 1200)
+}
+
+pub fn calc_keccak256_chain_instructions(n: usize) -> u32 {
+    // Each iteration hashes 32-byte digest (single block)
+    mul(cast(n), calc_keccak256_instructions(32))
+}
+
+pub fn calc_hmac_keccak256_instructions(size: usize) -> u32 {
+    // Inner hash of the key block and data, outer hash of the key block and inner hash
+    add(
+        calc_keccak256_instructions(136 + size),
+        calc_keccak256_instructions(136 + 32),
+    )
+}
+
+pub fn calc_merkle_build_instructions(leaf_cnt: usize, leaf_size: usize, arity: usize) -> u32 {
+    // Hash of each leaf, then hash of the children hashes of each node level by level
+    let mut instructions_cnt = mul(cast(leaf_cnt), calc_keccak256_instructions(leaf_size));
+    let mut level = leaf_cnt;
+    while level > 1 {
+        let full_nodes = level / arity;
+        instructions_cnt = add(
+            instructions_cnt,
+            mul(cast(full_nodes), calc_keccak256_instructions(arity * 32)),
+        );
+        if !level.is_multiple_of(arity) {
+            instructions_cnt = add(
+                instructions_cnt,
+                calc_keccak256_instructions((level % arity) * 32),
+            );
+        }
+        level = level.div_ceil(arity);
+    }
+    instructions_cnt
+}
+
+pub fn calc_merkle_verify_instructions(depth: usize) -> u32 {
+    // Binary tree of 32-byte leaves: hash of the leaf, then hash of two children per level
+    add(
+        calc_keccak256_instructions(32),
+        mul(cast(depth), calc_keccak256_instructions(64)),
+    )
+}
+
+pub fn calc_keccak256_sign_instructions(size: usize) -> u32 {
+    add(
+        calc_keccak256_instructions(size),
+        calc_sign_instructions(32),
+    )
+}
+
+pub fn calc_keccak256_verify_instructions(size: usize) -> u32 {
+    add(
+        calc_keccak256_instructions(size),
+        calc_verify_instructions(32),
+    )
 }
@@ -1,47 +1,387 @@
 use crate::bls12381::*;
 use crate::calc;
-use crate::keccak256_hash;
-use crate::perf;
-use clap::{Parser, Subcommand};
+use crate::get_ecdsa_secp256k1_verify_test_data;
+use crate::get_ed25519_verify_test_data;
+use crate::sensitivity::{report_marginal_cost, SweepPoint};
+use crate::verify_ecdsa_secp256k1;
+use crate::verify_ed25519;
+use crate::{blake2b_256_hash, sha256_hash, sha3_256_hash, Hash};
+use crate::{hmac_keccak256, hmac_sha256};
+use crate::{keccak256_hash, keccak256_hash_chain, keccak256_hash_iter};
+use crate::{keccak256_hash_parallel, keccak256_hash_reader};
+use crate::{keccak512_hash, keccak_f1600, keccak_permutation_count};
+use crate::{measures_time, perf};
+use crate::{merkle_internal_node_count, MerkleTree};
+use crate::{KECCAK256_RATE, KECCAK512_RATE};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::{Parser, Subcommand, ValueEnum};
 use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+use std::io::Read;
 use std::sync::Mutex;
+use std::time::Instant;
 
 const MEASURE_METHOD_DFLT: &str = "perf";
 
 pub static MEASURE_METHOD: OnceCell<Mutex<String>> = OnceCell::new();
 
+static ENCODING: OnceCell<Encoding> = OnceCell::new();
+
 #[derive(Parser)]
 #[command(author, version, about, long_about, verbatim_doc_comment)]
 #[command(propagate_version = true)]
 /// Measure number of instructions of below commands
 struct Cli {
     #[arg(long, short = 'm', default_value_t = MEASURE_METHOD_DFLT.to_string())]
-    /// available methods: perf, count, none
+    /// available methods: perf, count, time (wall time in ns), none
     /// for 'perf' method following command shall be issued:
     ///   sudo bash -c "echo -1 > /proc/sys/kernel/perf_event_paranoid"
     measure_method: String,
+    #[arg(long, value_enum, default_value_t = Variant::MinPk, global = true)]
+    /// BLS12-381 signature variant
+    ///   min-pk: 48-byte G1 public keys, 96-byte G2 signatures
+    ///   min-sig: 96-byte G2 public keys, 48-byte G1 signatures
+    /// (commands implemented for min-pk only reject min-sig)
+    variant: Variant,
+    #[arg(long, global = true)]
+    /// Seed of the test data (keys), to get the same data across runs and machines
+    seed: Option<u64>,
+    #[arg(long, global = true)]
+    /// Fill test messages with random bytes (from the seeded RNG)
+    /// instead of a single repeated byte
+    random_messages: bool,
+    #[arg(long, global = true)]
+    /// Directory, where generated keys and signatures are stored
+    /// and reused by subsequent runs of sweeping commands
+    test_data_cache_dir: Option<std::path::PathBuf>,
+    #[cfg(feature = "fixtures")]
+    #[arg(long, global = true, conflicts_with_all = ["fixtures", "test_data_cache_dir"])]
+    /// Save generated keys, messages and signatures to JSON file, to replay them later
+    export_fixtures: Option<std::path::PathBuf>,
+    #[cfg(feature = "fixtures")]
+    #[arg(long, global = true, conflicts_with = "test_data_cache_dir")]
+    /// Replay keys, messages and signatures saved with --export-fixtures
+    /// instead of generating them
+    fixtures: Option<std::path::PathBuf>,
+    #[arg(long, value_enum, default_value_t = Encoding::Hex, global = true)]
+    /// Encoding of printed keys, signatures and hashes
+    /// (inputs are accepted in either of them)
+    encoding: Encoding,
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Variant {
+    MinPk,
+    MinSig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Encoding {
+    Hex,
+    Base64,
+}
+
+/// Encodes bytes in the encoding selected with --encoding
+fn encode(bytes: impl AsRef<[u8]>) -> String {
+    match ENCODING.get().copied().unwrap_or(Encoding::Hex) {
+        Encoding::Hex => hex::encode(bytes),
+        Encoding::Base64 => BASE64.encode(bytes),
+    }
+}
+
+/// Decodes hex (optionally 0x prefixed) or base64, trying the encoding selected
+/// with --encoding first
+fn decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let hex = || hex::decode(s.trim_start_matches("0x")).ok();
+    let base64 = || BASE64.decode(s).ok();
+    match ENCODING.get().copied().unwrap_or(Encoding::Hex) {
+        Encoding::Hex => hex().or_else(base64),
+        Encoding::Base64 => base64().or_else(hex),
+    }
+}
+
 #[derive(Debug, Parser)]
 struct Verify {
     #[arg(long, short = 's', default_value_t = 1024)]
     msg_size: usize,
 }
 
+#[derive(Debug, Parser)]
+struct VerifyWithDst {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    /// Domain separation tag (defaults to ciphersuite tag of the selected variant)
+    #[arg(long, short = 'd')]
+    dst: Option<Bls12381Dst>,
+    #[cfg(feature = "keystore")]
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+    #[command(flatten)]
+    msg_files: MessageFiles,
+}
+
+// Real payloads (eg. transactions, blocks) to measure instead of synthetic messages.
+// Not a doc comment, it would become the about text of the commands flattening it.
+#[derive(Debug, Parser)]
+struct MessageFiles {
+    /// File with the message
+    #[arg(long, conflicts_with = "msg_dir")]
+    msg_file: Option<std::path::PathBuf>,
+    /// Directory with messages, one per file (in file name order)
+    #[arg(long)]
+    msg_dir: Option<std::path::PathBuf>,
+}
+
+impl MessageFiles {
+    /// Same as load, exits with usage error if the messages cannot be loaded
+    fn load_or_exit(&self) -> Option<Vec<Vec<u8>>> {
+        self.load()
+            .unwrap_or_else(|err| input_error(format!("cannot load messages: {}", err)))
+    }
+
+    /// Returns messages loaded from the file or directory, if given.
+    /// Fails if they cannot be read or the directory has no messages.
+    fn load(&self) -> std::io::Result<Option<Vec<Vec<u8>>>> {
+        if let Some(path) = &self.msg_file {
+            return Ok(Some(vec![std::fs::read(path)?]));
+        }
+        let Some(dir) = &self.msg_dir else {
+            return Ok(None);
+        };
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<std::path::PathBuf>>>()?;
+        paths.retain(|path| path.is_file());
+        paths.sort();
+        if paths.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("no messages in {}", dir.display()),
+            ));
+        }
+        paths
+            .iter()
+            .map(std::fs::read)
+            .collect::<std::io::Result<_>>()
+            .map(Some)
+    }
+}
+
+/// Private key to sign with given as EIP-2335 keystore (instead of the test key)
+#[cfg(feature = "keystore")]
+#[derive(Debug, Parser)]
+struct KeystoreArgs {
+    /// EIP-2335 keystore with the private key
+    #[arg(long, requires = "password_file")]
+    keystore: Option<std::path::PathBuf>,
+    /// File with the password of the keystore, - for stdin (trailing newline is ignored)
+    #[arg(long, requires = "keystore")]
+    password_file: Option<std::path::PathBuf>,
+}
+
+#[cfg(feature = "keystore")]
+#[derive(Debug, Parser)]
+struct KeystoreDecrypt {
+    /// log2 of the scrypt cost parameter N (EIP-2335 recommends 18)
+    #[arg(long, short = 'n', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![8, 10, 12])]
+    scrypt_log_ns: Vec<u8>,
+    /// PBKDF2 iteration counts (EIP-2335 recommends 262144)
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![256, 1024, 4096])]
+    pbkdf2_iterations: Vec<u32>,
+}
+
 #[derive(Debug, Parser)]
 struct AggregateVerify {
     #[arg(long, short = 's', default_value_t = 1024)]
     msg_size: usize,
     #[arg(long, short = 'c', default_value_t = 10)]
     msg_cnt: u32,
+    /// Number of keys signing each message (aggregate-verify and aggregate-verify-threaded)
+    #[arg(long, short = 'k', default_value_t = 1)]
+    signers_per_msg: u32,
+    /// Messages to verify, one key per message (aggregate-verify and aggregate-verify-threaded)
+    #[command(flatten)]
+    msg_files: MessageFiles,
+    /// JSON manifest with (public_key, message) pairs and aggregate signature to verify
+    /// instead of generated data (aggregate-verify and aggregate-verify-threaded, min-pk variant)
+    #[cfg(feature = "manifest")]
+    #[arg(long, conflicts_with_all = ["msg_file", "msg_dir"])]
+    manifest: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+struct Keccak256 {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[command(flatten)]
+    msg_files: MessageFiles,
+    /// Generate and hash the message in chunks, so that multi-gigabyte sizes fit in memory
+    #[arg(long, conflicts_with_all = ["msg_file", "msg_dir"])]
+    stream: bool,
+    /// Message to hash (hex, base64, @file or - for stdin), read in chunks instead of generated
+    #[arg(long, conflicts_with_all = ["msg_file", "msg_dir", "stream"])]
+    msg: Option<String>,
+    /// File to hash (read into memory, or in chunks with --stream), its hash is printed
+    #[arg(long, conflicts_with_all = ["msg_file", "msg_dir", "msg"])]
+    file: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+struct HashSizes {
+    #[arg(long, short = 's', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![32, 1024, 10240, 102400])]
+    msg_sizes: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct MerkleBuild {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![16, 256, 4096])]
+    leaf_cnts: Vec<usize>,
+    #[arg(long, short = 's', default_value_t = 32)]
+    leaf_size: usize,
+    /// Number of children of each node
+    #[arg(long, short = 'a', default_value_t = 2)]
+    arity: usize,
+}
+
+/// Verification of inclusion proofs in binary trees of 32-byte leaves
+#[derive(Debug, Parser)]
+struct MerkleVerify {
+    #[arg(long, short = 'd', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![4, 8, 12, 16])]
+    depths: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct KeccakF {
+    /// Number of permutations measured at once (cost of one is the average)
+    #[arg(long, short = 'n', default_value_t = 1000)]
+    iterations: usize,
+    #[arg(long, short = 's', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![32, 136, 1024, 10240])]
+    msg_sizes: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct HashChain {
+    /// Numbers of sequential keccak256 invocations
+    #[arg(long, short = 'n', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 10, 100, 1000])]
+    lengths: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct Keccak256Parallel {
+    #[arg(long, short = 's', default_value_t = 16 << 20)]
+    msg_size: usize,
+    /// Sizes of the chunks hashed in parallel
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![64 << 10, 1 << 20])]
+    chunk_sizes: Vec<usize>,
+    #[arg(long, short = 't', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 2, 4, 8])]
+    threads: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct Keccak256Chunked {
+    #[arg(long, short = 's', default_value_t = 1048576)]
+    msg_size: usize,
+    /// Sizes of the chunks the message is hashed in
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![64, 1024, 65536])]
+    chunk_sizes: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct MessagesDistinct {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![10, 100, 1000])]
+    msg_cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct AggregateVerifyChunked {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[arg(long, short = 'c', default_value_t = 1000)]
+    msg_cnt: u32,
+    #[arg(long, short = 'k', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 8, 64, 256, 1000])]
+    chunk_sizes: Vec<usize>,
 }
 
 #[derive(Debug, Parser)]
 struct AggregateVerifySizes {
     #[arg(long, short, use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![100, 100, 100, 100000, 100000])]
     msg_sizes: Vec<usize>,
+    /// Draw message sizes from log-normal distribution with given mu and sigma of ln(size),
+    /// eg. "6.9,1.0" (instead of msg_sizes)
+    #[arg(
+        long,
+        value_parser = MessageSizeDistribution::parse_log_normal,
+        conflicts_with = "size_histogram"
+    )]
+    size_log_normal: Option<MessageSizeDistribution>,
+    /// Draw message sizes from empirical histogram file with "<size> <weight>" lines
+    /// (instead of msg_sizes)
+    #[arg(long, value_name = "FILE", value_parser = parse_size_histogram_file)]
+    size_histogram: Option<MessageSizeDistribution>,
+    /// Number of message sizes drawn from the distribution (seeded with --seed)
+    #[arg(long, default_value_t = 100)]
+    size_cnt: usize,
+}
+
+fn parse_size_histogram_file(path: &str) -> Result<MessageSizeDistribution, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    MessageSizeDistribution::parse_histogram(&text)
+}
+
+impl AggregateVerifySizes {
+    fn msg_sizes(&self) -> Vec<usize> {
+        let Some(distribution) = self
+            .size_log_normal
+            .as_ref()
+            .or(self.size_histogram.as_ref())
+        else {
+            return self.msg_sizes.clone();
+        };
+        let sizes = distribution.sample(self.size_cnt, test_data_seed().unwrap_or_default());
+        println!(
+            "{:30}: {} bytes in {} messages",
+            "msg_sizes",
+            sizes.iter().sum::<usize>(),
+            sizes.len()
+        );
+        sizes
+    }
+}
+
+#[derive(Debug, Parser)]
+struct VerifyMultipleAggregateSignatures {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 2, 4, 8, 16, 32])]
+    batch_sizes: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct VerifyNegative {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    /// Size of the batch with one invalid (public key, message, signature) triple
+    #[arg(long, short = 'c', default_value_t = 8)]
+    batch_size: u32,
+    /// Invalid inputs: corrupted-signature, non-subgroup-signature, non-subgroup-public-key,
+    /// mismatched-key, truncated-message
+    #[arg(long, short = 'n', use_value_delimiter = true, value_delimiter = ',', default_values_t = NegativeTestCase::ALL.to_vec())]
+    cases: Vec<NegativeTestCase>,
+}
+
+#[cfg(feature = "cross-check")]
+#[derive(Debug, Parser)]
+struct CrossCheck {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    /// Number of signatures verified by both implementations
+    #[arg(long, short = 'c', default_value_t = 16)]
+    sig_cnt: u32,
 }
 
 #[derive(Debug, Parser)]
@@ -50,126 +390,2688 @@ struct SignatureAggregate {
     sig_cnt: u32,
 }
 
+#[derive(Debug, Parser)]
+struct SignatureAggregateParallel {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![16, 64, 256, 1024, 4096])]
+    sig_cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct CommitteeVerify {
+    #[arg(long, short = 's', default_value_t = 32)]
+    msg_size: usize,
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![64, 256, 1024], value_parser = clap::value_parser!(u32).range(1..))]
+    committee_sizes: Vec<u32>,
+    /// Percentage of committee members that signed the message
+    #[arg(long, short = 'p', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![25, 50, 67, 100], value_parser = clap::value_parser!(u32).range(1..=100))]
+    participation: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct ThresholdSplit {
+    /// Minimal number of shares needed to reconstruct the key
+    #[arg(long, short = 't', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![2, 5, 10])]
+    thresholds: Vec<usize>,
+    /// Total number of shares
+    #[arg(long, short = 'n', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![10, 20, 50])]
+    shares: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct ThresholdCombine {
+    #[arg(long, short = 's', default_value_t = 32)]
+    msg_size: usize,
+    /// Number of signature shares combined (threshold)
+    #[arg(long, short = 't', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![2, 5, 10, 20, 50])]
+    thresholds: Vec<usize>,
+}
+
+#[derive(Debug, Parser)]
+struct DkgSimulate {
+    /// Number of DKG participants, threshold is 2/3 of them plus one
+    #[arg(long, short = 'n', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![4, 16, 64])]
+    participants: Vec<usize>,
+}
+
+#[cfg(feature = "mnemonic")]
+#[derive(Debug, Parser)]
+struct Derive {
+    /// Derivation path (EIP-2334), "m" stands for the master key
+    #[arg(long, default_value = "m/12381/3600/0/0/0")]
+    path: String,
+    /// File with the seed (hex) to derive the master key from, - for stdin
+    #[arg(long, conflicts_with = "mnemonic_file")]
+    seed_file: Option<std::path::PathBuf>,
+    /// File with BIP-39 mnemonic to derive the master key from, - for stdin
+    #[arg(long)]
+    mnemonic_file: Option<std::path::PathBuf>,
+    /// File with BIP-39 passphrase, - for stdin (empty passphrase if not given)
+    #[arg(long, requires = "mnemonic_file")]
+    passphrase_file: Option<std::path::PathBuf>,
+    /// EIP-2335 keystore with the master key
+    #[cfg(feature = "keystore")]
+    #[arg(long, requires = "password_file", conflicts_with_all = ["seed_file", "mnemonic_file"])]
+    keystore: Option<std::path::PathBuf>,
+    /// File with the password of the keystore, - for stdin
+    #[cfg(feature = "keystore")]
+    #[arg(long, requires = "keystore")]
+    password_file: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+struct PopRegistryPopulate {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 10, 100])]
+    key_cnts: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Group {
+    G1,
+    G2,
+}
+
 #[derive(Debug, Parser)]
 struct HashToPoint {
     #[arg(long, short = 's', default_value_t = 1024)]
     msg_size: usize,
+    #[arg(long, short = 'g', value_enum, default_value_t = Group::G2)]
+    group: Group,
+    /// Generate and hash the message in chunks, so that multi-gigabyte sizes fit in memory
+    #[arg(long)]
+    stream: bool,
+    /// Message to hash (hex, base64, @file or - for stdin), read in chunks instead of generated
+    #[arg(long, conflicts_with = "stream")]
+    msg: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExpandMode {
+    /// expand_message_xmd with SHA-256
+    Xmd,
+    /// expand_message_xof with SHAKE-256
+    Xof,
+}
+
+#[derive(Debug, Parser)]
+struct HashToG2Phases {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[arg(long, short = 'e', value_enum, default_value_t = ExpandMode::Xmd)]
+    expand: ExpandMode,
+}
+
+#[derive(Debug, Parser)]
+struct Kzg {
+    /// Degrees of the committed polynomials
+    #[arg(long, short = 'd', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 16, 256, 4095])]
+    degrees: Vec<usize>,
+    /// Trusted setup in the c-kzg text format (insecure setup of the known secret is used otherwise)
+    #[arg(long)]
+    trusted_setup: Option<std::path::PathBuf>,
+}
+
+#[cfg(feature = "attestation-dump")]
+#[derive(Debug, Parser)]
+struct VerifyAttestationDump {
+    /// JSON array or SSZ list (.ssz extension) of {pubkeys, message, signature} records
+    #[arg(long, short = 'f')]
+    file: std::path::PathBuf,
+}
+
+#[cfg(feature = "serve")]
+#[derive(Debug, Parser)]
+struct Serve {
+    /// Address to listen on
+    #[arg(long, short = 'l', default_value = "127.0.0.1:8080")]
+    listen: String,
+}
+
+#[derive(Debug, Parser)]
+struct PrecomputedVerify {
+    #[arg(long, short = 's', default_value_t = 1024)]
+    msg_size: usize,
+    #[arg(long, short = 'c', default_value_t = 10)]
+    msg_cnt: u32,
+}
+
+#[derive(Debug, Parser)]
+struct HashToG2Batch {
+    #[arg(long, short = 's', default_value_t = 32)]
+    msg_size: usize,
+    #[arg(long, short = 'c', default_value_t = 100)]
+    msg_cnt: u32,
+}
+
+#[derive(Debug, Parser)]
+struct PairingPool {
+    #[arg(long, short = 's', default_value_t = 32)]
+    msg_size: usize,
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 2, 10, 100])]
+    msg_cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct Sign {
+    #[arg(long, short, use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![32, 1024, 10240, 102400])]
+    msg_sizes: Vec<usize>,
+    /// Domain separation tag (defaults to ciphersuite tag of the selected variant)
+    #[arg(long, short = 'd')]
+    dst: Option<Bls12381Dst>,
+    /// Private key (hex, base64 or file with either) to sign with instead of the test key
+    #[arg(long, short = 'k')]
+    key: Option<String>,
+    /// Message to sign (hex, base64, @file or - for stdin) instead of measuring, prints the signature
+    #[arg(long)]
+    msg: Option<String>,
+    /// Sign keccak256 hash of the message
+    #[arg(long, requires = "msg")]
+    keccak: bool,
+    #[cfg(feature = "keystore")]
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+}
+
+#[derive(Debug, Parser)]
+struct Pop {
+    /// Private key (hex, base64 or file with either)
+    #[arg(long, short = 'k')]
+    key: String,
+}
+
+/// Verification of given proof of possession instead of measuring it with the test key
+#[derive(Debug, Parser)]
+struct PopVerify {
+    /// Public key (hex or base64)
+    #[arg(long, requires = "proof")]
+    pk: Option<String>,
+    /// Proof of possession (hex or base64)
+    #[arg(long, requires = "pk")]
+    proof: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+struct SignBatch {
+    /// Directory with messages, one per file (in file name order)
+    #[arg(
+        long,
+        conflicts_with = "msg_list",
+        required_unless_present = "msg_list"
+    )]
+    msg_dir: Option<std::path::PathBuf>,
+    /// File with messages (hex or base64), one per line
+    #[arg(long)]
+    msg_list: Option<std::path::PathBuf>,
+    /// Private key (hex, base64 or file with either) to sign with instead of the test key
+    #[arg(long, short = 'k')]
+    key: Option<String>,
+    /// Number of threads (defaults to number of CPUs)
+    #[arg(long, short = 't')]
+    threads: Option<usize>,
+}
+
+/// Verification of user-provided min-pk signature
+#[derive(Debug, Parser)]
+struct VerifySig {
+    /// Public key (hex or base64)
+    #[arg(long)]
+    pk: String,
+    /// Signature (hex or base64)
+    #[arg(long)]
+    sig: String,
+    /// Message (hex, base64, @file or - for stdin)
+    #[arg(long)]
+    msg: String,
+    /// Print measured instructions too
+    #[arg(long)]
+    measure: bool,
+}
+
+#[derive(Debug, Parser)]
+struct Keygen {
+    /// Input key material (hex or base64, at least 32 bytes), securely random if not given
+    #[arg(long, short = 's')]
+    seed: Option<String>,
+    /// Print proof of possession too
+    #[arg(long)]
+    pop: bool,
+}
+
+/// Ethereum deposit (signed with the test key unless keystore is given)
+#[derive(Debug, Parser)]
+struct Deposit {
+    /// Withdrawal credentials (32 bytes hex)
+    #[arg(
+        long,
+        short = 'w',
+        default_value = "0000000000000000000000000000000000000000000000000000000000000000"
+    )]
+    withdrawal_credentials: String,
+    /// Amount in Gwei
+    #[arg(long, short = 'a', default_value_t = MAX_EFFECTIVE_BALANCE)]
+    amount: u64,
+    /// Genesis fork version of the network (4 bytes hex)
+    #[arg(long, short = 'f', default_value = "00000000")]
+    fork_version: String,
+    #[cfg(feature = "keystore")]
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+}
+
+#[derive(Debug, Parser)]
+struct SignatureValidate {
+    #[arg(long, short = 'c', default_value_t = 10)]
+    sig_cnt: u32,
+}
+
+#[derive(Debug, Parser)]
+struct PublicKeyValidate {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 10, 100])]
+    key_cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct Serialization {
+    #[arg(long, short = 'c', default_value_t = 10)]
+    cnt: u32,
+}
+
+#[derive(Debug, Parser)]
+struct G2Decompress {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 10, 100])]
+    cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct Pairing {
+    #[arg(long, short = 'c', default_value_t = 10)]
+    pair_cnt: u32,
+}
+
+#[derive(Debug, Parser)]
+struct Gt {
+    #[arg(long, short = 'c', default_value_t = 10)]
+    cnt: u32,
+    /// Exponent size in bits (eg. 64 for random scalars in batch verification)
+    #[arg(long, short = 'b', default_value_t = 64)]
+    exp_bits: u32,
+}
+
+#[derive(Debug, Parser)]
+struct PointOps {
+    #[arg(long, short = 'c', default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    cnt: u32,
+    #[arg(long, short = 'g', value_enum, default_value_t = Group::G2)]
+    group: Group,
+}
+
+#[derive(Debug, Parser)]
+struct PrecompileMsm {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 2, 4, 8, 16, 32, 64, 128])]
+    cnts: Vec<u32>,
+}
+
+#[derive(Debug, Parser)]
+struct PrecompilePairing {
+    #[arg(long, short = 'c', use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![1, 2, 4, 8])]
+    pair_cnts: Vec<u32>,
+}
+
+/// EIP-2537 precompile operations
+#[derive(Subcommand)]
+enum Precompile {
+    G1Add,
+    G1Mul,
+    G1Msm(PrecompileMsm),
+    G2Add,
+    G2Mul,
+    G2Msm(PrecompileMsm),
+    Pairing(PrecompilePairing),
+    MapFpToG1,
+    MapFp2ToG2,
+}
+
+/// Aggregation of user-provided hex or base64 values (min-pk variant), given one per line
+/// or as JSON list
+#[derive(Subcommand)]
+enum Aggregate {
+    Signatures(AggregateArgs),
+    PublicKeys(AggregateArgs),
+}
+
+#[derive(Debug, Parser)]
+struct AggregateArgs {
+    /// File with the values or - for stdin
+    #[arg(default_value = "-")]
+    input: String,
+}
+
+/// Test vectors checked against the public functions
+#[cfg(feature = "test-vectors")]
+#[derive(Subcommand)]
+enum TestVectors {
+    /// BLS signature draft vectors of the POP ciphersuite (ethereum/bls12-381-tests JSON format)
+    Ietf(TestVectorsArgs),
+    /// Ethereum consensus-spec bls vectors (tests/general/<fork>/bls, YAML or JSON format)
+    Eth(TestVectorsArgs),
+    /// RFC 9380 BLS12381G2_XMD:SHA-256_SSWU_RO_ hash-to-curve vectors of hash_to_g2
+    HashToG2(HashToG2TestVectorsArgs),
+}
+
+#[cfg(feature = "test-vectors")]
+#[derive(Debug, Parser)]
+struct TestVectorsArgs {
+    /// Directory with subdirectories of vectors per operation (eg. verify, aggregate_verify),
+    /// vectors are searched recursively
+    #[arg(long, short = 'd')]
+    dir: std::path::PathBuf,
+    /// Measure each vector
+    #[arg(long, short = 'm')]
+    measure: bool,
+}
+
+#[cfg(feature = "test-vectors")]
+#[derive(Debug, Parser)]
+struct HashToG2TestVectorsArgs {
+    /// Suite file (BLS12381G2_XMD_SHA-256_SSWU_RO_.json)
+    #[arg(long, short = 'f')]
+    file: std::path::PathBuf,
+}
+
+#[derive(Debug, Parser)]
+struct Sensitivity {
+    /// The first size is used when sweeping message counts
+    #[arg(long, short = 's', num_args = 1.., use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![100, 1000, 10000])]
+    msg_sizes: Vec<usize>,
+    /// The first count is used when sweeping message sizes
+    #[arg(long, short = 'c', num_args = 1.., use_value_delimiter = true, value_delimiter = ',', default_values_t = vec![2, 4, 8, 16])]
+    msg_cnts: Vec<u32>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    Verify(Verify),
+    Verify(VerifyWithDst),
     AggregateVerify(AggregateVerify),
     AggregateVerifySizes(AggregateVerifySizes),
     AggregateVerifyThreaded(AggregateVerify),
+    /// Compare aggregate verification of owned (copied) and borrowed messages
+    AggregateVerifyBorrowed(AggregateVerify),
+    /// Measure aggregate verification committing the pairing after each chunk of given size
+    AggregateVerifyChunked(AggregateVerifyChunked),
     FastAggregateVerify(AggregateVerify),
+    /// Measure verification with each combination of hash_or_encode, pk_validate and
+    /// sig_groupcheck flags
+    VerifyFlags(Verify),
+    /// Measure verification in message augmentation scheme (public key prepended to message)
+    VerifyAug(Verify),
+    /// Measure aggregate verification in message augmentation scheme
+    AggregateVerifyAug(AggregateVerify),
+    /// Measure aggregate verification in basic scheme (distinct messages required)
+    AggregateVerifyBasic(AggregateVerify),
+    /// Measure cost of checking that messages are distinct (basic scheme)
+    MessagesDistinct(MessagesDistinct),
+    /// Measure repeated verification against fixed G2 public key with precomputed lines
+    PrecomputedVerify(PrecomputedVerify),
+    /// Measure repeated verification against fixed G1 public key decoded once
+    DecodedVerify(PrecomputedVerify),
+    /// Measure verification reusing pairing contexts from thread-local pool
+    PairingPool(PairingPool),
+    /// Measure hashing many messages to G2 at once against hashing each of them
+    HashToG2Batch(HashToG2Batch),
+    /// Measure KZG polynomial commitment, evaluation proof and its verification
+    Kzg(Kzg),
+    /// Verify all records of the attestation dump (min-pk variant)
+    #[cfg(feature = "attestation-dump")]
+    VerifyAttestationDump(VerifyAttestationDump),
+    /// Measure randomized batch verification against sequential verification
+    BatchVerify(VerifyMultipleAggregateSignatures),
+    /// Measure randomized batch verification with signatures combined by multi-scalar
+    /// multiplication against aggregate verification of the aggregated signatures
+    BatchVerifyMsm(VerifyMultipleAggregateSignatures),
+    /// Measure blst batch verification of multiple (public key, message, signature) triples
+    VerifyMultipleAggregateSignatures(VerifyMultipleAggregateSignatures),
+    /// Measure rejection of invalid inputs by verification and batch verification
+    VerifyNegative(VerifyNegative),
+    /// Compare verification results and instructions of blst and the reference implementation
+    #[cfg(feature = "cross-check")]
+    CrossCheck(CrossCheck),
     SignatureAggregate(SignatureAggregate),
+    /// Compare serial and parallel signature aggregation (instructions and wall time)
+    SignatureAggregateParallel(SignatureAggregateParallel),
+    PublicKeyAggregate(SignatureAggregate),
+    /// Measure incremental public key aggregation (add, remove and finalize)
+    PublicKeyAggregateIncremental(SignatureAggregate),
+    /// Measure EIP-2335 keystore decryption (scrypt and PBKDF2 key derivation)
+    #[cfg(feature = "keystore")]
+    KeystoreDecrypt(KeystoreDecrypt),
+    /// Measure EIP-2333 master key derivation from BIP-39 mnemonic
+    #[cfg(feature = "mnemonic")]
+    MnemonicDerive,
+    /// Measure committee multi-signature verification (participants given as bitmap)
+    /// across committee sizes and participation rates
+    CommitteeVerify(CommitteeVerify),
+    /// Measure Shamir secret sharing of the private key (split into n shares
+    /// and reconstruction from t of them)
+    ThresholdSplit(ThresholdSplit),
+    /// Measure combination of threshold signature shares (Lagrange interpolation
+    /// in G2) across thresholds
+    ThresholdCombine(ThresholdCombine),
+    /// Simulate Pedersen DKG among n participants and measure per-participant cost
+    /// of dealing, share verification and aggregation
+    DkgSimulate(DkgSimulate),
+    /// Measure population of the proof of possession registry (verification of proofs)
+    PopRegistryPopulate(PopRegistryPopulate),
+    /// Derive EIP-2333 child key of given path from seed, mnemonic or keystore
+    /// and print it in hex
+    #[cfg(feature = "mnemonic")]
+    Derive(Derive),
     HashToPoint(HashToPoint),
-    Keccak256(Verify),
+    /// Measure expand_message (xmd or xof), hash-to-field and map-to-curve phases of hash to G2
+    HashToG2Phases(HashToG2Phases),
+    Keccak256(Keccak256),
+    /// Compare one-shot keccak256 with incremental hashing of the message in chunks
+    Keccak256Chunked(Keccak256Chunked),
+    /// Measure chains of dependent keccak256 invocations (hash of hash)
+    HashChain(HashChain),
+    /// Compare keccak256 with tree hashing of chunks in parallel (instructions and wall time)
+    Keccak256Parallel(Keccak256Parallel),
+    /// Measure raw keccak-f[1600] permutation and decompose keccak256 and keccak512
+    /// into permutations and padding overhead
+    KeccakF(KeccakF),
+    /// Measure keccak256 Merkle tree construction across leaf counts
+    MerkleBuild(MerkleBuild),
+    /// Measure Merkle proof verification across tree depths
+    MerkleVerify(MerkleVerify),
+    /// Measure HMAC-Keccak256 and HMAC-SHA256 and their overhead over plain hashing
+    Hmac(HashSizes),
+    /// Measure SHA-256 across message sizes
+    Sha256(HashSizes),
+    /// Measure SHA3-256 across message sizes
+    #[command(name = "sha3-256")]
+    Sha3_256(HashSizes),
+    /// Measure Blake2b-256 across message sizes
+    #[command(name = "blake2b-256")]
+    Blake2b256(HashSizes),
+    /// Measure keccak256 of the payload followed by sign and verify of the 32-byte digest
+    Keccak256SignVerify(Verify),
+    /// Sign all messages of the directory or list in parallel, print the signatures
+    /// and throughput
+    SignBatch(SignBatch),
+    /// Verify given signature (min-pk variant), exits with 1 if it is invalid
+    VerifySig(VerifySig),
+    /// Aggregate given signatures or public keys (min-pk variant) and print the aggregate
+    #[command(subcommand)]
+    Aggregate(Aggregate),
+    /// Generate key pair and print it in hex
+    Keygen(Keygen),
+    Sign(Sign),
+    /// Sign and verify Ethereum deposit message (min-pk variant)
+    Deposit(Deposit),
+    PopProve,
+    /// Measure proof of possession verification, or verify given proof
+    /// (exits with 1 if it is invalid)
+    PopVerify(PopVerify),
+    /// Generate proof of possession of given key
+    Pop(Pop),
+    /// Measure signature deserialization and subgroup check
+    SignatureValidate(SignatureValidate),
+    /// Measure public key deserialization and validation
+    PublicKeyValidate(PublicKeyValidate),
+    /// Measure compressed and uncompressed (de)serialization of keys and signatures
+    Serialization(Serialization),
+    /// Measure G2 compressed point decoding
+    G2Decompress(G2Decompress),
+    /// Measure Miller loop and final exponentiation separately
+    Pairing(Pairing),
+    /// Measure GT (Fp12) multiplication and exponentiation
+    Gt(Gt),
+    /// Measure point addition and doubling
+    PointOps(PointOps),
+    /// Report marginal cost per extra byte and per extra message/signer
+    Sensitivity(Sensitivity),
+    /// Measure Ed25519 signature verification
+    Ed25519Verify(Verify),
+    /// Measure ECDSA secp256k1 signature verification (including keccak256 of the message)
+    Secp256k1Verify(Verify),
+    /// Compare BLS, Ed25519 and ECDSA secp256k1 signature verification
+    CompareVerify(Verify),
+    /// Measure EIP-2537 precompile operations and compare them with the gas schedule
+    #[command(subcommand)]
+    Precompile(Precompile),
+    /// Check public functions against test vectors
+    #[cfg(feature = "test-vectors")]
+    #[command(subcommand)]
+    TestVectors(TestVectors),
+    /// Serve measurements and verification over HTTP/JSON API
+    #[cfg(feature = "serve")]
+    Serve(Serve),
 }
 
-fn cli_measure_verify(cmd: &Verify) {
-    let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+impl Commands {
+    /// Commands implemented for min-pk variant only. They reject --variant min-sig,
+    /// so that min-pk results are not reported as min-sig ones. Commands that do not
+    /// depend on the variant (hashing, pairing, key derivation, keystores) accept both.
+    fn min_pk_only(&self) -> bool {
+        match self {
+            Commands::AggregateVerifyBorrowed(_)
+            | Commands::AggregateVerifyChunked(_)
+            | Commands::VerifyFlags(_)
+            | Commands::VerifyAug(_)
+            | Commands::AggregateVerifyAug(_)
+            | Commands::AggregateVerifyBasic(_)
+            | Commands::DecodedVerify(_)
+            | Commands::PairingPool(_)
+            | Commands::HashToG2Batch(_)
+            | Commands::SignatureAggregateParallel(_)
+            | Commands::PublicKeyAggregateIncremental(_)
+            | Commands::CommitteeVerify(_)
+            | Commands::ThresholdSplit(_)
+            | Commands::ThresholdCombine(_)
+            | Commands::DkgSimulate(_)
+            | Commands::PopRegistryPopulate(_)
+            | Commands::VerifySig(_)
+            | Commands::Aggregate(_)
+            | Commands::Deposit(_)
+            | Commands::MessagesDistinct(_) => true,
+            #[cfg(feature = "attestation-dump")]
+            Commands::VerifyAttestationDump(_) => true,
+            #[cfg(feature = "test-vectors")]
+            Commands::TestVectors(_) => true,
+            _ => false,
+        }
+    }
+}
+
+fn cli_measure_verify<V: Bls12381Variant>(cmd: &VerifyWithDst) {
+    #[allow(unused_mut)]
+    let (mut sks, mut pks, mut msgs, mut sigs) =
+        V::get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+    let from_files = match cmd.msg_files.load_or_exit() {
+        Some(file_msgs) => {
+            sigs = file_msgs.iter().map(|msg| V::sign(&sks[0], msg)).collect();
+            msgs = file_msgs;
+            true
+        }
+        None => false,
+    };
+    #[cfg(feature = "keystore")]
+    if let Some(sk) = keystore_private_key::<V>(&cmd.keystore) {
+        pks[0] = V::public_key(&sk);
+        sigs = msgs.iter().map(|msg| V::sign(&sk, msg)).collect();
+        sks[0] = sk;
+    }
 
     println!("verify");
-    let (_, _) = perf!(
-        "total instructions",
-        verify_bls12381_v1(&msgs[0], &pks[0], &sigs[0])
-    );
-    println!(
-        "{:30}: {}",
-        "calc_instructions",
-        calc::calc_verify_instructions(cmd.msg_size)
-    );
+    if let Some(dst) = &cmd.dst {
+        println!("{:30}: {:?}", "dst", dst);
+    }
+    for (msg, sig) in msgs.iter().zip(sigs.iter()) {
+        if from_files {
+            println!("{:30}: {}", "msg_size", msg.len());
+        }
+        let (result, count) = match &cmd.dst {
+            Some(dst) => {
+                let sig = V::sign_with_dst(&sks[0], msg, dst);
+                perf!(
+                    "total instructions",
+                    V::verify_with_dst(msg, &pks[0], &sig, dst)
+                )
+            }
+            None => perf!("total instructions", V::verify(msg, &pks[0], sig)),
+        };
+        assert!(result);
+        print_variant_calc_instructions::<V>(calc::calc_verify_instructions(msg.len()), count);
+    }
 }
 
-fn cli_measure_fast_aggregate_verify(cmd: &AggregateVerify) {
-    let (_sks, pks, msg, sigs) = get_fast_aggregate_verify_test_data(cmd.msg_cnt, cmd.msg_size);
+fn cli_measure_fast_aggregate_verify<V: Bls12381Variant>(cmd: &AggregateVerify) {
+    let (_sks, pks, msg, sigs) = V::get_fast_aggregate_verify_test_data(cmd.msg_cnt, cmd.msg_size);
 
     // Aggregate the signature
-    let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+    let agg_sig = V::aggregate_signatures(&sigs).unwrap();
 
     println!("fast_aggregate_verify");
     let (_, count) = perf!(
         "total_instructions",
-        fast_aggregate_verify_bls12381_v1(&msg, &pks, &agg_sig)
+        V::fast_aggregate_verify(&msg, &pks, &agg_sig)
     );
-    let calc_instructions =
-        calc::calc_fast_aggregate_verify_instructions(cmd.msg_cnt, cmd.msg_size);
-    println!(
-        "{:30}: {} diff: {}",
-        "calc_instructions",
-        calc_instructions,
-        calc_instructions as i64 - count as i64
+    print_variant_calc_instructions::<V>(
+        calc::calc_fast_aggregate_verify_instructions(cmd.msg_cnt, cmd.msg_size),
+        count,
     );
 }
 
-fn cli_measure_aggregate_verify(
+fn cli_measure_aggregate_verify<V: Bls12381Variant>(
     threaded: bool,
-    pub_keys_msgs: &[(Bls12381G1PublicKey, Vec<u8>)],
-    agg_sig: &Bls12381G2Signature,
-) {
+    pub_keys_msgs: &[(V::PublicKey, &[u8])],
+    agg_sig: &V::Signature,
+) -> bool {
     let sizes: Vec<usize> = pub_keys_msgs.iter().map(|(_, msg)| msg.len()).collect();
 
     let mut calc_instructions =
         calc::calc_aggregate_verify_instructions_no_threaded(sizes.as_slice());
 
-    let (_, count) = if threaded {
+    let (result, count) = if threaded {
         println!("aggregate_verify threaded");
         calc_instructions = calc::calc_aggregate_verify_instructions_threaded(calc_instructions);
 
         perf!(
             "total_instructions",
-            aggregate_verify_bls12381_v1_threaded(pub_keys_msgs, agg_sig)
+            V::aggregate_verify_threaded(pub_keys_msgs, agg_sig)
         )
     } else {
         println!("aggregate_verify");
         perf!(
             "total_instructions",
-            aggregate_verify_bls12381_v1(pub_keys_msgs, agg_sig)
+            V::aggregate_verify_phases(pub_keys_msgs, agg_sig)
         )
     };
 
-    let diff = if count != 0 {
-        format!(" diff : {}", calc_instructions as i64 - count as i64)
-    } else {
-        "".to_string()
-    };
-    println!("{:30}: {}{}", "calc_instructions", calc_instructions, diff);
+    if V::CALC_MODELS && !measures_time() {
+        let diff = if count != 0 {
+            format!(" diff : {}", calc_instructions as i64 - count as i64)
+        } else {
+            "".to_string()
+        };
+        println!("{:30}: {}{}", "calc_instructions", calc_instructions, diff);
+    }
+    result
 }
 
-fn cli_cmd_measure_aggregate_verify(threaded: bool, cmd: &AggregateVerify) {
+#[cfg(feature = "manifest")]
+fn cli_measure_aggregate_verify_manifest(threaded: bool, path: &std::path::Path) {
+    let manifest = AggregateManifest::load(path).unwrap();
+    println!("{:30}: {}", "pairs", manifest.pairs.len());
+
+    let result = cli_measure_aggregate_verify::<MinPk>(
+        threaded,
+        &manifest.pub_keys_and_msgs(),
+        &manifest.signature,
+    );
+    println!("{:30}: {}", "valid", result);
+
+    if !result {
+        std::process::exit(1);
+    }
+}
+
+fn cli_cmd_measure_aggregate_verify<V: Bls12381Variant>(threaded: bool, cmd: &AggregateVerify) {
+    #[cfg(feature = "manifest")]
+    if let Some(path) = &cmd.manifest {
+        cli_measure_aggregate_verify_manifest(threaded, path);
+        return;
+    }
+
+    let cnt = cmd
+        .msg_cnt
+        .checked_mul(cmd.signers_per_msg.max(1))
+        .unwrap_or_else(|| input_error("too many signers (msg_cnt * signers_per_msg)"));
+    let (_sks, pks, msgs, sigs) = match cmd.msg_files.load_or_exit() {
+        Some(msgs) => {
+            let sks: Vec<V::PrivateKey> = (1..=msgs.len() as u64)
+                .map(V::private_key_from_u64)
+                .collect();
+            let pks = sks.iter().map(V::public_key).collect();
+            let sigs = sks
+                .iter()
+                .zip(msgs.iter())
+                .map(|(sk, msg)| V::sign(sk, msg))
+                .collect();
+            (sks, pks, msgs, sigs)
+        }
+        None => V::get_aggregate_verify_test_data(cnt, cmd.msg_cnt, cmd.msg_size),
+    };
+
+    // Aggregate the signature
+    let agg_sig = V::aggregate_signatures(&sigs).unwrap();
+
+    // Messages are assigned to the keys round-robin
+    let pub_keys_msgs: Vec<(V::PublicKey, &[u8])> = pks
+        .iter()
+        .enumerate()
+        .map(|(i, pk)| (*pk, msgs[i % msgs.len()].as_slice()))
+        .collect();
+
+    cli_measure_aggregate_verify::<V>(threaded, &pub_keys_msgs, &agg_sig);
+}
+
+fn cli_measure_aggregate_verify_borrowed(cmd: &AggregateVerify) {
+    let (_sks, pks, msgs, sigs) =
+        get_aggregate_verify_test_data(cmd.msg_cnt, cmd.msg_cnt, cmd.msg_size);
+    let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+    println!("aggregate_verify_owned");
+    let (pub_keys_msgs, build_count) = perf!(
+        "build_input",
+        pks.iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.clone()))
+            .collect::<Vec<(Bls12381G1PublicKey, Vec<u8>)>>()
+    );
+    let (result, verify_count) = perf!(
+        "verify",
+        aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    let owned_count = build_count + verify_count;
+
+    println!("aggregate_verify_borrowed");
+    let (pub_keys_msgs, build_count) = perf!(
+        "build_input",
+        pks.iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect::<Vec<(Bls12381G1PublicKey, &[u8])>>()
+    );
+    let (result, verify_count) = perf!(
+        "verify",
+        aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    let borrowed_count = build_count + verify_count;
+
+    let copied_bytes: usize = msgs.iter().map(|msg| msg.len()).sum();
+    println!("{:30}: {}", "copied_bytes", copied_bytes);
+    println!(
+        "{:30}: {}",
+        "savings",
+        owned_count as i64 - borrowed_count as i64
+    );
+}
+
+fn cli_measure_aggregate_verify_chunked(cmd: &AggregateVerifyChunked) {
     let (_sks, pks, msgs, sigs) =
         get_aggregate_verify_test_data(cmd.msg_cnt, cmd.msg_cnt, cmd.msg_size);
+    let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = pks.into_iter().zip(msgs).collect();
+
+    println!("aggregate_verify");
+    let (result, count) = perf!(
+        "not chunked",
+        aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+
+    println!("aggregate_verify_chunked");
+    for chunk_size in &cmd.chunk_sizes {
+        let (result, chunked_count) = perf!(
+            format!("chunk size {}", chunk_size),
+            aggregate_verify_bls12381_v1_chunked(&pub_keys_msgs, &agg_sig, *chunk_size)
+        );
+        assert!(result);
+        println!("{:30}: {}", "overhead", chunked_count as i64 - count as i64);
+    }
+}
+
+fn cli_measure_aggregate_verify_sizes<V: Bls12381Variant>(
+    threaded: bool,
+    cmd: &AggregateVerifySizes,
+) {
+    let (_sks, pks, msgs, sigs) = V::get_aggregate_verify_test_data2(&cmd.msg_sizes());
+
+    // Aggregate the signature
+    let agg_sig = V::aggregate_signatures(&sigs).unwrap();
+
+    let pub_keys_msgs: Vec<(V::PublicKey, &[u8])> = pks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(pk, msg)| (*pk, msg.as_slice()))
+        .collect();
+
+    cli_measure_aggregate_verify::<V>(threaded, &pub_keys_msgs, &agg_sig);
+}
+
+fn cli_measure_verify_flags(cmd: &Verify) {
+    let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+
+    println!("verify_flags");
+    let (_, default_count) = perf!(
+        "default",
+        verify_with_flags_bls12381_v1(&msgs[0], &pks[0], &sigs[0], VerifyFlags::default())
+    );
+    for flags in VerifyFlags::all() {
+        // Signature is valid only if message was hashed (not encoded) to curve
+        let (result, count) = perf!(
+            format!(
+                "hash {} pk {} sig {}",
+                flags.hash_or_encode, flags.pk_validate, flags.sig_groupcheck
+            ),
+            verify_with_flags_bls12381_v1(&msgs[0], &pks[0], &sigs[0], flags)
+        );
+        assert_eq!(result, flags.hash_or_encode);
+        println!("{:30}: {}", "savings", default_count as i64 - count as i64);
+    }
+}
+
+fn cli_measure_verify_aug(cmd: &Verify) {
+    let (_sks, pks, msgs, sigs) = get_aggregate_verify_aug_test_data(1, 1, cmd.msg_size);
+
+    println!("verify_aug");
+    let (result, count) = perf!(
+        "total instructions",
+        verify_aug_bls12381_v1(&msgs[0], &pks[0], &sigs[0])
+    );
+    assert!(result);
+    print_calc_instructions(calc::calc_verify_aug_instructions(cmd.msg_size), count);
+}
+
+fn cli_measure_aggregate_verify_aug(cmd: &AggregateVerify) {
+    let (_sks, pks, msgs, sigs) =
+        get_aggregate_verify_aug_test_data(cmd.msg_cnt, cmd.msg_cnt, cmd.msg_size);
+
+    let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = pks.into_iter().zip(msgs).collect();
+    let sizes: Vec<usize> = pub_keys_msgs.iter().map(|(_, msg)| msg.len()).collect();
+
+    println!("aggregate_verify_aug");
+    let (result, count) = perf!(
+        "total_instructions",
+        aggregate_verify_aug_bls12381_v1(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    print_calc_instructions(calc::calc_aggregate_verify_aug_instructions(&sizes), count);
+}
+
+fn cli_measure_aggregate_verify_basic(cmd: &AggregateVerify) {
+    let (_sks, pks, msgs, sigs) = get_aggregate_verify_basic_test_data(cmd.msg_cnt, cmd.msg_size);
+
+    let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+
+    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> = pks.into_iter().zip(msgs).collect();
+    let sizes: Vec<usize> = pub_keys_msgs.iter().map(|(_, msg)| msg.len()).collect();
+
+    println!("aggregate_verify_basic");
+    let (result, count) = perf!(
+        "total_instructions",
+        aggregate_verify_basic_bls12381_v1(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    print_calc_instructions(
+        calc::calc_aggregate_verify_instructions_no_threaded(&sizes),
+        count,
+    );
+}
+
+fn cli_measure_messages_distinct(cmd: &MessagesDistinct) {
+    println!("messages_distinct");
+    for cnt in &cmd.msg_cnts {
+        let (_sks, pks, msgs, _sigs) = get_aggregate_verify_basic_test_data(*cnt, cmd.msg_size);
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
+            pks.into_iter().zip(msgs).collect();
+
+        let (result, count) = perf!(
+            format!("msg_cnt {}", cnt),
+            messages_distinct(&pub_keys_msgs)
+        );
+        assert!(result);
+        if *cnt > 0 {
+            println!("{:30}: {}", "instructions_per_msg", count / u64::from(*cnt));
+        }
+    }
+}
+
+fn cli_measure_verify_multiple_aggregate_signatures<V: Bls12381Variant>(
+    cmd: &VerifyMultipleAggregateSignatures,
+) {
+    println!("verify_multiple_aggregate_signatures");
+    let mut cache = TestDataCache::<V>::new();
+    for cnt in &cmd.batch_sizes {
+        let (_sks, pks, msgs, sigs) = cache.aggregate_verify(*cnt, *cnt, cmd.msg_size);
+
+        let pub_keys_msgs_sigs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg.clone(), *sig))
+            .collect();
+
+        let (result, count) = perf!(
+            format!("batch_size {}", cnt),
+            V::verify_multiple_aggregate_signatures(&pub_keys_msgs_sigs)
+        );
+        assert!(result);
+        print_variant_calc_instructions::<V>(
+            calc::calc_verify_multiple_aggregate_signatures_instructions(*cnt, cmd.msg_size),
+            count,
+        );
+    }
+}
+
+fn cli_measure_verify_negative<V: Bls12381Variant>(cmd: &VerifyNegative) {
+    println!("verify_negative");
+    let (_sks, pks, msgs, sigs) = V::get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+    let (result, count) = perf!("valid", V::verify(&msgs[0], &pks[0], &sigs[0]));
+    assert!(result);
+    print_variant_calc_instructions::<V>(calc::calc_verify_instructions(cmd.msg_size), count);
+
+    for case in &cmd.cases {
+        let (pk, msg, sig) = get_negative_verify_test_data::<V>(*case, cmd.msg_size);
+        let (result, count) = perf!(case.to_string(), V::verify(&msg, &pk, &sig));
+        assert!(!result);
+        print_variant_calc_instructions::<V>(calc::calc_verify_instructions(cmd.msg_size), count);
+
+        let (pks, msgs, sigs) =
+            get_negative_aggregate_verify_test_data::<V>(*case, cmd.batch_size, cmd.msg_size);
+        let pub_keys_msgs_sigs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = pks
+            .into_iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (pk, msg, sig))
+            .collect();
+        let (result, count) = perf!(
+            format!("{} batch_size {}", case, cmd.batch_size),
+            V::verify_multiple_aggregate_signatures(&pub_keys_msgs_sigs)
+        );
+        assert!(!result);
+        print_variant_calc_instructions::<V>(
+            calc::calc_verify_multiple_aggregate_signatures_instructions(
+                cmd.batch_size,
+                cmd.msg_size,
+            ),
+            count,
+        );
+    }
+}
+
+#[cfg(feature = "cross-check")]
+fn cli_cross_check<V: Bls12381Variant>(cmd: &CrossCheck) {
+    println!("cross_check");
+    let (_sks, pks, msgs, sigs) =
+        V::get_aggregate_verify_test_data(cmd.sig_cnt, cmd.sig_cnt, cmd.msg_size);
+
+    // Valid signatures, the same signatures against other messages and the negative cases
+    let mut inputs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = vec![];
+    for (i, (pk, sig)) in pks.iter().zip(sigs.iter()).enumerate() {
+        inputs.push((*pk, msgs[i].clone(), *sig));
+        inputs.push((*pk, msgs[(i + 1) % msgs.len()].clone(), *sig));
+    }
+    for case in NegativeTestCase::ALL {
+        let (pk, msg, sig) = get_negative_verify_test_data::<V>(case, cmd.msg_size);
+        inputs.push((pk, msg, sig));
+    }
+
+    let (results, count) = perf!(
+        "blst",
+        inputs
+            .iter()
+            .map(|(pk, msg, sig)| V::verify(msg, pk, sig))
+            .collect::<Vec<_>>()
+    );
+    let (reference_results, reference_count) = perf!(
+        "reference",
+        inputs
+            .iter()
+            .map(|(pk, msg, sig)| V::reference_verify(msg, pk, sig))
+            .collect::<Vec<_>>()
+    );
+    let mismatches = results
+        .iter()
+        .zip(reference_results.iter())
+        .filter(|(result, reference_result)| result != reference_result)
+        .count();
+
+    println!("{:30}: {}", "inputs", inputs.len());
+    println!(
+        "{:30}: {}",
+        "accepted",
+        results.iter().filter(|result| **result).count()
+    );
+    println!("{:30}: {}", "mismatches", mismatches);
+    println!(
+        "{:30}: {:.2}",
+        "reference/blst instructions",
+        reference_count as f64 / count as f64
+    );
+    assert_eq!(mismatches, 0, "implementations disagree");
+}
+
+fn cli_measure_kzg(cmd: &Kzg) {
+    let max_degree = *cmd.degrees.iter().max().unwrap();
+    let setup = match &cmd.trusted_setup {
+        Some(path) => KzgTrustedSetup::load(path).unwrap(),
+        None => KzgTrustedSetup::insecure(1234567, max_degree),
+    };
+    assert!(
+        max_degree <= setup.max_degree(),
+        "trusted setup supports degrees up to {}",
+        setup.max_degree()
+    );
+
+    println!("kzg");
+    let mut commit_points = vec![];
+    let mut prove_points = vec![];
+    for degree in &cmd.degrees {
+        let (polynomial, z) = get_kzg_test_data(*degree);
+
+        let (commitment, commit_count) = perf!(
+            format!("commit degree {}", degree),
+            setup.commit(&polynomial)
+        );
+        let (result, prove_count) = perf!(
+            format!("prove degree {}", degree),
+            setup.prove(&polynomial, &z)
+        );
+        let (y, proof) = result.unwrap();
+        let (result, _) = perf!(
+            format!("verify degree {}", degree),
+            setup.verify(&commitment.unwrap(), &z, &y, &proof)
+        );
+        assert!(result);
+
+        commit_points.push(SweepPoint {
+            x: *degree as u64,
+            measured: commit_count,
+            calculated: None,
+        });
+        prove_points.push(SweepPoint {
+            x: *degree as u64,
+            measured: prove_count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost("kzg_commit", "degree", &commit_points);
+    report_marginal_cost("kzg_prove", "degree", &prove_points);
+}
+
+#[cfg(feature = "attestation-dump")]
+fn cli_measure_verify_attestation_dump(cmd: &VerifyAttestationDump) {
+    let dump = AttestationDump::load(&cmd.file).unwrap();
+
+    println!("verify_attestation_dump");
+    println!("{:30}: {}", "records", dump.records.len());
+    let mut calc_total = 0;
+    for (i, record) in dump.records.iter().enumerate() {
+        let (result, count) = perf!(
+            format!("record {} pubkeys {}", i, record.pubkeys.len()),
+            record.verify()
+        );
+        assert!(result, "record {} does not verify", i);
+        let calc_instructions =
+            calc::calc_fast_aggregate_verify_instructions(record.pubkeys.len() as u32, 32);
+        print_calc_instructions(calc_instructions, count);
+        calc_total += calc_instructions as u64;
+    }
+
+    let (result, count) = perf!("total", dump.records.iter().all(AttestationRecord::verify));
+    assert!(result);
+    if !measures_time() {
+        println!(
+            "{:30}: {} diff: {}",
+            "calc_instructions",
+            calc_total,
+            calc_total as i64 - count as i64
+        );
+    }
+}
+
+fn cli_measure_precomputed_verify(cmd: &PrecomputedVerify) {
+    let (pk, msgs, sigs) = get_precomputed_verify_test_data(cmd.msg_cnt, cmd.msg_size);
+
+    println!("precomputed_verify");
+    let (precomputed_pk, count) = perf!("precompute_lines", PrecomputedG2PublicKey::new(&pk));
+    let precomputed_pk = precomputed_pk.unwrap();
+    print_calc_instructions(calc::calc_precompute_lines_instructions(), count);
+
+    let (result, precomputed_count) = perf!(
+        "verify_precomputed",
+        msgs.iter()
+            .zip(sigs.iter())
+            .all(|(msg, sig)| precomputed_pk.verify(msg, sig))
+    );
+    assert!(result);
+    print_calc_instructions(
+        calc::calc_precomputed_verify_instructions(cmd.msg_cnt, cmd.msg_size),
+        precomputed_count,
+    );
+
+    let (result, count) = perf!(
+        "verify",
+        msgs.iter()
+            .zip(sigs.iter())
+            .all(|(msg, sig)| min_sig::verify_bls12381_v1(msg, &pk, sig))
+    );
+    assert!(result);
+    if cmd.msg_cnt > 0 {
+        println!(
+            "{:30}: {}",
+            "savings_per_verify",
+            (count as i64 - precomputed_count as i64) / i64::from(cmd.msg_cnt)
+        );
+    }
+}
+
+fn cli_measure_decoded_verify(cmd: &PrecomputedVerify) {
+    let (pk, msgs, sigs) = get_decoded_verify_test_data(cmd.msg_cnt, cmd.msg_size);
+
+    println!("decoded_verify");
+    let (decoded_pk, decode_count) = perf!("decode", DecodedG1PublicKey::new(&pk));
+    let decoded_pk = decoded_pk.unwrap();
+
+    let (result, decoded_count) = perf!(
+        "verify_decoded",
+        msgs.iter()
+            .zip(sigs.iter())
+            .all(|(msg, sig)| decoded_pk.verify(msg, sig))
+    );
+    assert!(result);
+
+    let (result, count) = perf!(
+        "verify",
+        msgs.iter()
+            .zip(sigs.iter())
+            .all(|(msg, sig)| verify_bls12381_v1(msg, &pk, sig))
+    );
+    assert!(result);
+    if cmd.msg_cnt > 0 {
+        println!(
+            "{:30}: {}",
+            "savings_per_verify",
+            (count as i64 - decoded_count as i64) / i64::from(cmd.msg_cnt)
+        );
+        println!(
+            "{:30}: {}",
+            "total_savings",
+            count as i64 - decoded_count as i64 - decode_count as i64
+        );
+    }
+}
+
+fn cli_measure_pairing_pool(cmd: &PairingPool) {
+    println!("pairing_pool");
+    let _ = perf!(
+        "pairing_new",
+        blst::Pairing::new(true, BLS12381_CIPHERSITE_V1)
+    );
+
+    let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+    // Warm up the pool, so the measurements below reuse the context
+    assert!(verify_bls12381_v1_pooled(&msgs[0], &pks[0], &sigs[0]));
+
+    let (result, count) = perf!("verify", verify_bls12381_v1(&msgs[0], &pks[0], &sigs[0]));
+    assert!(result);
+    let (result, pooled_count) = perf!(
+        "verify_pooled",
+        verify_bls12381_v1_pooled(&msgs[0], &pks[0], &sigs[0])
+    );
+    assert!(result);
+    println!("{:30}: {}", "savings", count as i64 - pooled_count as i64);
+    println!(
+        "{:30}: {}",
+        "verify_constant",
+        calc::calc_verify_instructions(0)
+    );
+
+    for cnt in &cmd.msg_cnts {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(*cnt, *cnt, cmd.msg_size);
+        let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+        let pub_keys_msgs: Vec<(Bls12381G1PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+
+        let (result, count) = perf!(
+            format!("aggregate_verify cnt {}", cnt),
+            aggregate_verify_bls12381_v1(&pub_keys_msgs, &agg_sig)
+        );
+        assert!(result);
+        let (result, pooled_count) = perf!(
+            format!("aggregate_verify_pooled cnt {}", cnt),
+            aggregate_verify_bls12381_v1_pooled(&pub_keys_msgs, &agg_sig)
+        );
+        assert!(result);
+        println!("{:30}: {}", "savings", count as i64 - pooled_count as i64);
+    }
+}
+
+fn cli_measure_hash_to_g2_batch(cmd: &HashToG2Batch) {
+    let (_sks, pks, msgs, sigs) =
+        get_aggregate_verify_test_data(cmd.msg_cnt, cmd.msg_cnt, cmd.msg_size);
+    let agg_sig = Bls12381G2Signature::aggregate(&sigs, true).unwrap();
+    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, &[u8])> = pks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(pk, msg)| (*pk, msg.as_slice()))
+        .collect();
+
+    println!("hash_to_g2_batch");
+    let (_, count) = perf!(
+        "hash_to_g2",
+        msgs.iter().map(|msg| hash_to_g2(msg)).collect::<Vec<_>>()
+    );
+    let (_, batch_count) = perf!(
+        "hash_to_g2_batch",
+        hash_to_g2_batch(&msgs, BLS12381_CIPHERSITE_V1)
+    );
+    if cmd.msg_cnt > 0 {
+        println!(
+            "{:30}: {}",
+            "savings_per_msg",
+            (count as i64 - batch_count as i64) / i64::from(cmd.msg_cnt)
+        );
+    }
+
+    println!("aggregate_verify");
+    let (result, count) = perf!(
+        "hash_each",
+        aggregate_verify_bls12381_v1_hash_each(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    let (result, batch_count) = perf!(
+        "batch_hash",
+        aggregate_verify_bls12381_v1_batch_hash(&pub_keys_msgs, &agg_sig)
+    );
+    assert!(result);
+    println!("{:30}: {}", "savings", count as i64 - batch_count as i64);
+}
+
+fn cli_measure_batch_verify<V: Bls12381Variant>(cmd: &VerifyMultipleAggregateSignatures) {
+    println!("batch_verify");
+    let mut cache = TestDataCache::<V>::new();
+    for cnt in &cmd.batch_sizes {
+        let (_sks, pks, msgs, sigs) = cache.aggregate_verify(*cnt, *cnt, cmd.msg_size);
+
+        let pub_keys_msgs_sigs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg.clone(), *sig))
+            .collect();
+
+        let (result, count) = perf!(
+            format!("batch_size {}", cnt),
+            V::batch_verify(&pub_keys_msgs_sigs)
+        );
+        assert!(result);
+        print_variant_calc_instructions::<V>(
+            calc::calc_batch_verify_instructions(*cnt, cmd.msg_size),
+            count,
+        );
+
+        let (result, count) = perf!(
+            format!("sequential {}", cnt),
+            pub_keys_msgs_sigs
+                .iter()
+                .all(|(pk, msg, sig)| V::verify(msg, pk, sig))
+        );
+        assert!(result);
+        print_variant_calc_instructions::<V>(
+            calc::calc_sequential_verify_instructions(*cnt, cmd.msg_size),
+            count,
+        );
+    }
+    if V::CALC_MODELS {
+        println!(
+            "{:30}: {}",
+            "calc_crossover_batch_size",
+            calc::calc_batch_verify_crossover(cmd.msg_size)
+        );
+    }
+}
+
+fn cli_measure_batch_verify_msm<V: Bls12381Variant>(cmd: &VerifyMultipleAggregateSignatures) {
+    println!("batch_verify_msm");
+    let mut cache = TestDataCache::<V>::new();
+    let mut crossover = None;
+    for cnt in &cmd.batch_sizes {
+        let (_sks, pks, msgs, sigs) = cache.aggregate_verify(*cnt, *cnt, cmd.msg_size);
+        let pub_keys_msgs_sigs: Vec<(V::PublicKey, Vec<u8>, V::Signature)> = pks
+            .iter()
+            .zip(msgs)
+            .zip(sigs)
+            .map(|((pk, msg), sig)| (*pk, msg.clone(), *sig))
+            .collect();
+
+        let (result, msm_count) = perf!(
+            format!("msm {}", cnt),
+            V::batch_verify_msm(&pub_keys_msgs_sigs)
+        );
+        assert!(result);
+        let (result, batch_count) = perf!(
+            format!("batch_size {}", cnt),
+            V::batch_verify(&pub_keys_msgs_sigs)
+        );
+        assert!(result);
+        println!(
+            "{:30}: {}",
+            "msm_savings",
+            batch_count as i64 - msm_count as i64
+        );
+
+        // Per-pair Miller loop accumulation without random scalars,
+        // given signatures are aggregated first
+        let pub_keys_msgs: Vec<(V::PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+        let (result, aggregate_count) = perf!(
+            format!("aggregate_verify {}", cnt),
+            V::aggregate_signatures(sigs)
+                .is_ok_and(|agg_sig| V::aggregate_verify(&pub_keys_msgs, &agg_sig))
+        );
+        assert!(result);
+        println!(
+            "{:30}: {}",
+            "msm_overhead",
+            msm_count as i64 - aggregate_count as i64
+        );
+        if crossover.is_none() && msm_count < aggregate_count {
+            crossover = Some(*cnt);
+        }
+    }
+    match crossover {
+        Some(cnt) => println!("{:30}: {}", "crossover_batch_size", cnt),
+        None => println!("{:30}: none", "crossover_batch_size"),
+    }
+}
+
+fn print_validation_savings(validated: u64, prevalidated: u64) {
+    println!(
+        "{:30}: {}",
+        "prevalidated_savings",
+        validated as i64 - prevalidated as i64
+    );
+}
+
+fn cli_measure_signature_aggregate<V: Bls12381Variant>(cmd: &SignatureAggregate) {
+    let (_sks, _pks, _msg, sigs) = V::get_fast_aggregate_verify_test_data(cmd.sig_cnt, 100);
+
+    println!("signature_aggregate");
+    let (_, count) = perf!("measured_sig_aggr", V::aggregate_signatures(&sigs));
+    print_variant_calc_instructions::<V>(
+        calc::calc_signature_aggregate_instructions(cmd.sig_cnt),
+        count,
+    );
+
+    let (_, prevalidated_count) = perf!(
+        "measured_sig_aggr_prevalidated",
+        V::aggregate_signatures_prevalidated(&sigs)
+    );
+    print_variant_calc_instructions::<V>(
+        calc::calc_signature_aggregate_prevalidated_instructions(cmd.sig_cnt),
+        prevalidated_count,
+    );
+    print_validation_savings(count, prevalidated_count);
+}
+
+fn cli_measure_signature_aggregate_parallel(cmd: &SignatureAggregateParallel) {
+    println!("signature_aggregate_parallel");
+    let mut crossover = None;
+    for cnt in &cmd.sig_cnts {
+        let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(*cnt, 100);
+
+        // Parallel aggregation is not expected to save instructions, but wall time
+        let start = Instant::now();
+        let (serial, _) = perf!(
+            format!("serial cnt {}", cnt),
+            Bls12381G2Signature::aggregate(&sigs, true)
+        );
+        let serial_time = start.elapsed();
+
+        let start = Instant::now();
+        let (parallel, _) = perf!(
+            format!("parallel cnt {}", cnt),
+            Bls12381G2Signature::aggregate_parallel(&sigs, true)
+        );
+        let parallel_time = start.elapsed();
+        assert_eq!(serial, parallel);
+
+        println!("{:30}: {:?}", "serial_time", serial_time);
+        println!("{:30}: {:?}", "parallel_time", parallel_time);
+        if crossover.is_none() && parallel_time < serial_time {
+            crossover = Some(*cnt);
+        }
+    }
+    match crossover {
+        Some(cnt) => println!("{:30}: {}", "crossover_sig_cnt", cnt),
+        None => println!("{:30}: not reached", "crossover_sig_cnt"),
+    }
+}
+
+fn cli_measure_public_key_aggregate<V: Bls12381Variant>(cmd: &SignatureAggregate) {
+    let (_sks, pks, _msg, _sigs) = V::get_fast_aggregate_verify_test_data(cmd.sig_cnt, 100);
+
+    println!("public_key_aggregate");
+    let (_, count) = perf!("measured_pk_aggr", V::aggregate_public_keys(&pks));
+    print_variant_calc_instructions::<V>(
+        calc::calc_public_key_aggregate_instructions(cmd.sig_cnt),
+        count,
+    );
+
+    let (_, prevalidated_count) = perf!(
+        "measured_pk_aggr_prevalidated",
+        V::aggregate_public_keys_prevalidated(&pks)
+    );
+    print_variant_calc_instructions::<V>(
+        calc::calc_public_key_aggregate_prevalidated_instructions(cmd.sig_cnt),
+        prevalidated_count,
+    );
+    print_validation_savings(count, prevalidated_count);
+}
+
+fn cli_measure_hash_to_point(cmd: &HashToPoint) {
+    if let Some(msg) = &cmd.msg {
+        let reader = message_reader_from_arg(msg);
+        let (result, _) = match cmd.group {
+            Group::G1 => {
+                println!("hash_to_point g1 msg");
+                perf!(
+                    "total_instructions",
+                    hash_to_g1_reader(reader, BLS12381G1_CIPHERSITE_V1).map(|_| ())
+                )
+            }
+            Group::G2 => {
+                println!("hash_to_point g2 msg");
+                perf!(
+                    "total_instructions",
+                    hash_to_g2_reader(reader, BLS12381_CIPHERSITE_V1).map(|_| ())
+                )
+            }
+        };
+        result.unwrap();
+        return;
+    }
+
+    // Instructions include generation of the message (filling the chunks)
+    if cmd.stream {
+        let msg =
+            || TestMessageReader::new((cmd.msg_size % u8::MAX as usize) as u8, cmd.msg_size as u64);
+        let (result, _) = match cmd.group {
+            Group::G1 => {
+                println!("hash_to_point g1 stream");
+                perf!(
+                    "total_instructions",
+                    hash_to_g1_reader(msg(), BLS12381G1_CIPHERSITE_V1).map(|_| ())
+                )
+            }
+            Group::G2 => {
+                println!("hash_to_point g2 stream");
+                perf!(
+                    "total_instructions",
+                    hash_to_g2_reader(msg(), BLS12381_CIPHERSITE_V1).map(|_| ())
+                )
+            }
+        };
+        result.unwrap();
+        return;
+    }
+
+    let msg: Vec<u8> = vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size];
+
+    match cmd.group {
+        Group::G1 => {
+            println!("hash_to_point g1");
+            perf!("total_instructions", hash_to_g1(&msg));
+        }
+        Group::G2 => {
+            println!("hash_to_point g2");
+            perf!("total_instructions", hash_to_g2(&msg));
+        }
+    }
+}
+
+fn cli_measure_hash_to_g2_phases(cmd: &HashToG2Phases) {
+    let msg: Vec<u8> = vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size];
+
+    println!("hash_to_g2_phases");
+    let (uniform_bytes, count, calc_instructions) = match cmd.expand {
+        ExpandMode::Xmd => {
+            let (uniform_bytes, count) = perf!(
+                "expand_message_xmd",
+                expand_message_xmd(
+                    &msg,
+                    BLS12381_CIPHERSITE_V1,
+                    HASH_TO_G2_UNIFORM_BYTES_LENGTH
+                )
+            );
+            let calc_instructions = calc::calc_expand_message_xmd_instructions(cmd.msg_size);
+            (uniform_bytes, count, calc_instructions)
+        }
+        ExpandMode::Xof => {
+            let (uniform_bytes, count) = perf!(
+                "expand_message_xof",
+                expand_message_xof(
+                    &msg,
+                    BLS12381G2_XOF_CIPHERSITE_V1,
+                    HASH_TO_G2_UNIFORM_BYTES_LENGTH
+                )
+            );
+            let calc_instructions = calc::calc_expand_message_xof_instructions(cmd.msg_size);
+            (uniform_bytes, count, calc_instructions)
+        }
+    };
+    print_calc_instructions(calc_instructions, count);
+
+    let (u, _) = perf!("hash_to_field", hash_to_field_fp2(&uniform_bytes));
+
+    let (_, count) = perf!("map_to_g2", map_to_g2(&u));
+    let calc_instructions = calc::calc_map_to_g2_instructions();
+    print_calc_instructions(calc_instructions, count);
+}
+
+fn cli_measure_keccak256(cmd: &Keccak256) {
+    if let Some(msg) = &cmd.msg {
+        println!("keccak25 msg");
+        let (result, _) = perf!(
+            "total_instructions",
+            keccak256_hash_reader(message_reader_from_arg(msg))
+        );
+        let hash =
+            result.unwrap_or_else(|err| input_error(format!("cannot read message: {}", err)));
+        println!("{:30}: {}", "hash", encode(hash.0));
+        return;
+    }
+
+    if let Some(path) = &cmd.file {
+        let read_error = |err: std::io::Error| -> ! {
+            input_error(format!("cannot read {}: {}", path.display(), err))
+        };
+        let size = std::fs::metadata(path)
+            .unwrap_or_else(|err| read_error(err))
+            .len() as usize;
+        let (result, count) = if cmd.stream {
+            println!("keccak25 file stream");
+            let file = std::fs::File::open(path).unwrap_or_else(|err| read_error(err));
+            perf!("total_instructions", keccak256_hash_reader(file))
+        } else {
+            println!("keccak25 file");
+            let msg = std::fs::read(path).unwrap_or_else(|err| read_error(err));
+            perf!("total_instructions", Ok(keccak256_hash(&msg)))
+        };
+        let hash = result.unwrap_or_else(|err| read_error(err));
+        println!("{:30}: {}", "msg_size", size);
+        print_calc_instructions(calc::calc_keccak256_instructions(size), count);
+        print_hash_throughput(size, count);
+        println!("{:30}: {}", "hash", encode(hash.0));
+        return;
+    }
+
+    // Instructions include generation of the message (filling the chunks)
+    if cmd.stream {
+        println!("keccak25 stream");
+        let msg =
+            TestMessageReader::new((cmd.msg_size % u8::MAX as usize) as u8, cmd.msg_size as u64);
+        let (result, count) = perf!("total_instructions", keccak256_hash_reader(msg));
+        result.unwrap();
+        print_calc_instructions(calc::calc_keccak256_instructions(cmd.msg_size), count);
+        print_hash_throughput(cmd.msg_size, count);
+        return;
+    }
+
+    let (msgs, from_files) = match cmd.msg_files.load_or_exit() {
+        Some(msgs) => (msgs, true),
+        None => (
+            vec![vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size]],
+            false,
+        ),
+    };
+
+    println!("keccak25");
+    for msg in &msgs {
+        if from_files {
+            println!("{:30}: {}", "msg_size", msg.len());
+        }
+        let (_, count) = perf!("total_instructions", keccak256_hash(msg));
+        print_calc_instructions(calc::calc_keccak256_instructions(msg.len()), count);
+        print_hash_throughput(msg.len(), count);
+    }
+}
+
+fn cli_measure_public_key_aggregate_incremental(cmd: &SignatureAggregate) {
+    let (_sks, pks, _msg, _sigs) = get_fast_aggregate_verify_test_data(cmd.sig_cnt + 1, 100);
+    let (extra_pk, pks) = pks.split_last().unwrap();
+
+    let mut agg_pk = AggregatePublicKey::new();
+    for pk in pks {
+        agg_pk.add(pk).unwrap();
+    }
+
+    println!("public_key_aggregate_incremental");
+    let (_, count) = perf!("aggregate", Bls12381G1PublicKey::aggregate(pks));
+    print_calc_instructions(
+        calc::calc_public_key_aggregate_instructions(cmd.sig_cnt),
+        count,
+    );
+    let (result, _) = perf!("add", agg_pk.add(extra_pk));
+    assert!(result.is_ok());
+    let (result, _) = perf!("remove", agg_pk.remove(extra_pk));
+    assert_eq!(result, Ok(true));
+    let (result, _) = perf!("finalize", agg_pk.finalize());
+    assert!(result.is_ok());
+}
+
+fn cli_measure_committee_verify(cmd: &CommitteeVerify) {
+    println!("committee_verify");
+    for size in &cmd.committee_sizes {
+        let mut points = vec![];
+        for percent in &cmd.participation {
+            let (committee, bitmap, msg, sig) =
+                get_committee_test_data(*size, *percent, cmd.msg_size);
+            let participants = bitmap.iter().map(|b| b.count_ones() as u64).sum();
+
+            let (result, aggregate_count) = perf!(
+                format!("aggregate size {} {}%", size, percent),
+                committee.aggregate_public_key(&bitmap)
+            );
+            assert!(result.is_ok());
+            let (result, count) = perf!(
+                format!("verify size {} {}%", size, percent),
+                committee.verify_bls12381_v1(&msg, &bitmap, &sig)
+            );
+            assert!(result);
+            // No count when not measuring
+            if let Some(share) = (aggregate_count * 100).checked_div(count) {
+                println!("{:30}: {}", "aggregate share %", share);
+            }
+
+            points.push(SweepPoint {
+                x: participants,
+                measured: count,
+                calculated: None,
+            });
+        }
+        report_marginal_cost(
+            &format!("committee_verify size {}", size),
+            "participant",
+            &points,
+        );
+    }
+}
+
+fn cli_measure_threshold_split(cmd: &ThresholdSplit) {
+    let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+
+    println!("threshold_split");
+    for t in &cmd.thresholds {
+        let mut points = vec![];
+        for n in cmd.shares.iter().filter(|n| *n >= t) {
+            let (result, count) = perf!(format!("t {} n {}", t, n), split_private_key(&sk, *t, *n));
+            assert!(result.is_ok());
+            points.push(SweepPoint {
+                x: *n as u64,
+                measured: count,
+                calculated: None,
+            });
+        }
+        report_marginal_cost(&format!("threshold_split t {}", t), "share", &points);
+    }
+
+    println!("threshold_reconstruct");
+    let n = *cmd.shares.iter().max().unwrap();
+    let mut points = vec![];
+    for t in cmd.thresholds.iter().filter(|t| **t <= n) {
+        let shares = split_private_key(&sk, *t, n).unwrap();
+        let (result, count) = perf!(format!("t {}", t), reconstruct_private_key(&shares, *t));
+        assert!(result.unwrap() == sk);
+        points.push(SweepPoint {
+            x: *t as u64,
+            measured: count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost("threshold_reconstruct", "share", &points);
+}
+
+fn cli_measure_threshold_combine(cmd: &ThresholdCombine) {
+    let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+    let msg = vec![0x5au8; cmd.msg_size];
+
+    println!("threshold_combine");
+    let mut points = vec![];
+    for t in &cmd.thresholds {
+        let sig_shares: Vec<Bls12381G2SignatureShare> = split_private_key(&sk, *t, *t)
+            .unwrap()
+            .iter()
+            .map(|share| share.sign_v1(&msg))
+            .collect();
+        let (result, count) = perf!(
+            format!("t {}", t),
+            combine_signature_shares(&sig_shares, *t)
+        );
+        assert!(verify_bls12381_v1(&msg, &sk.public_key(), &result.unwrap()));
+        points.push(SweepPoint {
+            x: *t as u64,
+            measured: count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost("threshold_combine", "share", &points);
+}
+
+#[cfg(feature = "mnemonic")]
+fn derive_master_private_key_from_args(cmd: &Derive) -> Bls12381G1PrivateKey {
+    let secret = |path: &std::path::Path| read_secret(path).unwrap_or_else(|err| input_error(err));
+    #[cfg(feature = "keystore")]
+    if let (Some(path), Some(password_file)) = (&cmd.keystore, &cmd.password_file) {
+        let keystore = Keystore::load(path).unwrap_or_else(|err| input_error(err));
+        return keystore
+            .decrypt(&secret(password_file))
+            .unwrap_or_else(|err| input_error(err));
+    }
+    let result = match (&cmd.seed_file, &cmd.mnemonic_file) {
+        (Some(seed_file), _) => {
+            let seed = hex::decode(secret(seed_file).trim().trim_start_matches("0x"))
+                .unwrap_or_else(|err| input_error(format!("invalid seed: {}", err)));
+            derive_master_private_key(&seed)
+        }
+        (None, Some(mnemonic_file)) => {
+            let passphrase = cmd
+                .passphrase_file
+                .as_deref()
+                .map(secret)
+                .unwrap_or_default();
+            master_private_key_from_mnemonic(secret(mnemonic_file).trim(), &passphrase)
+        }
+        (None, None) => input_error("seed, mnemonic or keystore file must be given"),
+    };
+    result.unwrap_or_else(|err| input_error(err))
+}
+
+#[cfg(feature = "mnemonic")]
+fn cli_derive<V: Bls12381Variant>(cmd: &Derive) {
+    let master = derive_master_private_key_from_args(cmd);
+
+    println!("derive");
+    let (sk, _) = perf!(
+        "derive_private_key_from_path",
+        derive_private_key_from_path(&master, &cmd.path)
+    );
+    // Derived scalar is the same for both variants, only the public key differs
+    let sk = sk
+        .and_then(|sk| {
+            V::private_key_from_bytes(&sk.to_bytes()).map_err(KeyDerivationError::BlsError)
+        })
+        .unwrap_or_else(|err| input_error(err));
+    println!("{:30}: {}", "path", cmd.path);
+    println!(
+        "{:30}: {}",
+        "private key",
+        encode(V::private_key_to_bytes(&sk))
+    );
+    println!(
+        "{:30}: {}",
+        "public key",
+        encode(V::public_key_to_bytes(&V::public_key(&sk)))
+    );
+}
+
+fn cli_measure_dkg_simulate(cmd: &DkgSimulate) {
+    println!("dkg_simulate");
+    let mut points = vec![];
+    for n in &cmd.participants {
+        let t = n * 2 / 3 + 1;
+        // Participant 1 deals, other dealings are generated up front
+        let (dealing, deal_count) = perf!(
+            format!("deal n {} t {}", n, t),
+            DkgDealing::generate(1, t, *n)
+        );
+        let mut dealings = vec![dealing.unwrap()];
+        for dealer in 2..=*n as u32 {
+            dealings.push(DkgDealing::generate(dealer, t, *n).unwrap());
+        }
+
+        // Participant 1 verifies shares received from all dealers
+        let (result, verify_count) = perf!(
+            format!("verify n {} t {}", n, t),
+            dealings
+                .iter()
+                .all(|dealing| dealing.verify_share(dealing.share(1).unwrap()))
+        );
+        assert!(result);
+
+        let (result, aggregate_count) = perf!(format!("aggregate n {} t {}", n, t), {
+            let commitments = aggregate_dkg_commitments(&dealings).unwrap();
+            let key_share = aggregate_dkg_key_share(1, &dealings).unwrap();
+            evaluate_commitments(&commitments, 1) == Some(key_share.public_key())
+        });
+        assert!(result);
+
+        let count = deal_count + verify_count + aggregate_count;
+        println!("{:30}: {}", "per participant", count);
+        points.push(SweepPoint {
+            x: *n as u64,
+            measured: count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost("dkg_simulate per participant", "participant", &points);
+}
+
+#[cfg(feature = "keystore")]
+fn keystore_private_key<V: Bls12381Variant>(args: &KeystoreArgs) -> Option<V::PrivateKey> {
+    let path = args.keystore.as_ref()?;
+    // Required by clap along with the keystore
+    let password_file = args.password_file.as_ref()?;
+    let password = read_secret(password_file).unwrap_or_else(|err| input_error(err));
+
+    let keystore = Keystore::load(path).unwrap_or_else(|err| input_error(err));
+    let sk = keystore
+        .decrypt(&password)
+        .unwrap_or_else(|err| input_error(err));
+    println!("{:30}: {}", "pubkey", keystore.pubkey);
+    Some(V::private_key_from_bytes(&sk.to_bytes()).unwrap())
+}
+
+/// Reads secret (eg. password) from file or stdin given as -, so that it does not
+/// appear in shell history or process list. Trailing newline is ignored.
+#[cfg(any(feature = "keystore", feature = "mnemonic"))]
+fn read_secret(path: &std::path::Path) -> std::io::Result<String> {
+    let text = if path == std::path::Path::new("-") {
+        std::io::read_to_string(std::io::stdin().lock())?
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    Ok(text.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Exits with usage error, as clap does for invalid arguments
+fn input_error(err: impl std::fmt::Display) -> ! {
+    <Cli as clap::CommandFactory>::command()
+        .error(clap::error::ErrorKind::InvalidValue, err)
+        .exit()
+}
+
+/// Reads private key given in hex or base64, or as file with either
+fn private_key_from_arg<V: Bls12381Variant>(arg: &str) -> V::PrivateKey {
+    let text = match std::path::Path::new(arg).is_file() {
+        true => std::fs::read_to_string(arg)
+            .unwrap_or_else(|err| input_error(format!("cannot read key {}: {}", arg, err))),
+        false => arg.to_string(),
+    };
+    let bytes = decode(&text).unwrap_or_else(|| input_error("key must be hex or base64"));
+    V::private_key_from_bytes(&bytes)
+        .unwrap_or_else(|err| input_error(format!("invalid private key: {}", err)))
+}
+
+/// Returns reader of the message given in hex or base64, as @file or - for stdin,
+/// so that files and pipes are streamed instead of read into memory
+fn message_reader_from_arg(arg: &str) -> Box<dyn std::io::Read> {
+    if arg == "-" {
+        Box::new(std::io::stdin().lock())
+    } else if let Some(path) = arg.strip_prefix('@') {
+        Box::new(
+            std::fs::File::open(path)
+                .unwrap_or_else(|err| input_error(format!("cannot open {}: {}", path, err))),
+        )
+    } else {
+        let msg = decode(arg).unwrap_or_else(|| input_error("message must be hex or base64"));
+        Box::new(std::io::Cursor::new(msg))
+    }
+}
+
+/// Reads message given in hex or base64, as @file or - for stdin
+fn message_from_arg(arg: &str) -> Vec<u8> {
+    let mut msg = vec![];
+    message_reader_from_arg(arg)
+        .read_to_end(&mut msg)
+        .unwrap_or_else(|err| input_error(format!("cannot read message: {}", err)));
+    msg
+}
+
+fn cli_measure_sign<V: Bls12381Variant>(cmd: &Sign) {
+    let sk = match &cmd.key {
+        Some(key) => Some(private_key_from_arg::<V>(key)),
+        #[cfg(feature = "keystore")]
+        None => keystore_private_key::<V>(&cmd.keystore),
+        #[cfg(not(feature = "keystore"))]
+        None => None,
+    };
+
+    // Signing given message, the test key is never used for it
+    if let Some(msg) = &cmd.msg {
+        let sk =
+            sk.unwrap_or_else(|| input_error("key or keystore must be given to sign the message"));
+        let reader = message_reader_from_arg(msg);
+        let signature = if cmd.keccak {
+            keccak256_hash_reader(reader)
+                .and_then(|digest| V::sign_reader(&sk, &digest.0[..], cmd.dst.as_ref()))
+        } else {
+            V::sign_reader(&sk, reader, cmd.dst.as_ref())
+        }
+        .unwrap_or_else(|err| input_error(format!("cannot read message: {}", err)));
+        println!(
+            "{:30}: {}",
+            "public key",
+            encode(V::public_key_to_bytes(&V::public_key(&sk)))
+        );
+        println!(
+            "{:30}: {}",
+            "signature",
+            encode(V::signature_to_bytes(&signature))
+        );
+        return;
+    }
+
+    let sk = sk.unwrap_or_else(|| V::private_key_from_u64(1));
+    println!("sign");
+    if let Some(dst) = &cmd.dst {
+        println!("{:30}: {:?}", "dst", dst);
+    }
+    for size in &cmd.msg_sizes {
+        let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+
+        let (_, count) = match &cmd.dst {
+            Some(dst) => perf!(format!("size {}", size), V::sign_with_dst(&sk, &msg, dst)),
+            None => perf!(format!("size {}", size), V::sign(&sk, &msg)),
+        };
+        print_variant_calc_instructions::<V>(calc::calc_sign_instructions(*size), count);
+    }
+}
+
+/// Returns messages named by file name (directory) or line number (list)
+fn sign_batch_messages(cmd: &SignBatch) -> Vec<(String, Vec<u8>)> {
+    let read_error = |path: &std::path::Path, err: std::io::Error| -> ! {
+        input_error(format!("cannot read {}: {}", path.display(), err))
+    };
+    if let Some(path) = &cmd.msg_list {
+        return std::fs::read_to_string(path)
+            .unwrap_or_else(|err| read_error(path, err))
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let msg = decode(line).unwrap_or_else(|| {
+                    input_error(format!("invalid message {} at line {}", line, i + 1))
+                });
+                (format!("line {}", i + 1), msg)
+            })
+            .collect();
+    }
+    let dir = cmd.msg_dir.as_ref().unwrap();
+    let mut paths: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| read_error(dir, err))
+        .map(|entry| entry.unwrap_or_else(|err| read_error(dir, err)).path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let msg = std::fs::read(path).unwrap_or_else(|err| read_error(path, err));
+            (name, msg)
+        })
+        .collect()
+}
+
+fn cli_sign_batch<V: Bls12381Variant>(cmd: &SignBatch) {
+    let sk = match &cmd.key {
+        Some(key) => private_key_from_arg::<V>(key),
+        None => V::private_key_from_u64(1),
+    };
+    let msgs = sign_batch_messages(cmd);
+    let mut pool = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = cmd.threads {
+        pool = pool.num_threads(threads);
+    }
+    let pool = pool.build().unwrap();
+
+    println!("sign_batch");
+    // Instructions of all threads are counted (perf method)
+    let start = Instant::now();
+    let (sigs, _) = perf!(
+        "total_instructions",
+        pool.install(|| {
+            msgs.par_iter()
+                .map(|(_, msg)| V::sign(&sk, msg))
+                .collect::<Vec<V::Signature>>()
+        })
+    );
+    let time = start.elapsed();
+
+    for ((name, _), sig) in msgs.iter().zip(sigs.iter()) {
+        println!("{:30}: {}", name, encode(V::signature_to_bytes(sig)));
+    }
+    let bytes: usize = msgs.iter().map(|(_, msg)| msg.len()).sum();
+    let secs = time.as_secs_f64();
+    println!("{:30}: {}", "threads", pool.current_num_threads());
+    println!("{:30}: {}", "messages", msgs.len());
+    println!("{:30}: {}", "bytes", bytes);
+    println!("{:30}: {:?}", "time", time);
+    println!("{:30}: {:.1}", "messages_per_sec", msgs.len() as f64 / secs);
+    println!("{:30}: {:.1}", "bytes_per_sec", bytes as f64 / secs);
+}
+
+fn cli_verify_sig(cmd: &VerifySig) {
+    let msg = message_from_arg(&cmd.msg);
+    let pk = decode(&cmd.pk)
+        .and_then(|bytes| Bls12381G1PublicKey::try_from(bytes.as_slice()).ok())
+        .unwrap_or_else(|| input_error(format!("invalid public key {}", cmd.pk)));
+    let sig = decode(&cmd.sig)
+        .and_then(|bytes| Bls12381G2Signature::try_from(bytes.as_slice()).ok())
+        .unwrap_or_else(|| input_error(format!("invalid signature {}", cmd.sig)));
+
+    let result = if cmd.measure {
+        println!("verify_sig");
+        let (result, count) = perf!("total_instructions", verify_bls12381_v1(&msg, &pk, &sig));
+        print_calc_instructions(calc::calc_verify_instructions(msg.len()), count);
+        result
+    } else {
+        verify_bls12381_v1(&msg, &pk, &sig)
+    };
+    println!("{:30}: {}", "valid", result);
+
+    if !result {
+        std::process::exit(1);
+    }
+}
+
+/// Splits hex or base64 values given one per line or as JSON list of strings
+fn hex_values_from_text(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '[' | ']' | ',' | '"'))
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn cli_aggregate(cmd: &Aggregate) {
+    let args = match cmd {
+        Aggregate::Signatures(args) | Aggregate::PublicKeys(args) => args,
+    };
+    let text = match args.input.as_str() {
+        "-" => std::io::read_to_string(std::io::stdin()),
+        path => std::fs::read_to_string(path),
+    }
+    .unwrap_or_else(|err| input_error(format!("cannot read {}: {}", args.input, err)));
+    let values = hex_values_from_text(&text);
+
+    match cmd {
+        Aggregate::Signatures(_) => {
+            let signatures = values
+                .iter()
+                .map(|value| {
+                    decode(value)
+                        .and_then(|bytes| Bls12381G2Signature::try_from(bytes.as_slice()).ok())
+                        .unwrap_or_else(|| input_error(format!("invalid signature {}", value)))
+                })
+                .collect::<Vec<Bls12381G2Signature>>();
+            let signature = Bls12381G2Signature::aggregate(&signatures, true)
+                .unwrap_or_else(|err| input_error(format!("cannot aggregate signatures: {}", err)));
+            println!("{:30}: {}", "count", signatures.len());
+            println!("{:30}: {}", "signature", encode(signature.0));
+        }
+        Aggregate::PublicKeys(_) => {
+            let public_keys = values
+                .iter()
+                .map(|value| {
+                    decode(value)
+                        .and_then(|bytes| Bls12381G1PublicKey::try_from(bytes.as_slice()).ok())
+                        .unwrap_or_else(|| input_error(format!("invalid public key {}", value)))
+                })
+                .collect::<Vec<Bls12381G1PublicKey>>();
+            let public_key = Bls12381G1PublicKey::aggregate(&public_keys).unwrap_or_else(|err| {
+                input_error(format!("cannot aggregate public keys: {}", err))
+            });
+            println!("{:30}: {}", "count", public_keys.len());
+            println!("{:30}: {}", "public key", encode(public_key.0));
+        }
+    }
+}
+
+fn cli_keygen<V: Bls12381Variant>(cmd: &Keygen) {
+    let ikm = match &cmd.seed {
+        Some(seed) => decode(seed).expect("seed must be hex or base64"),
+        None => rand::random::<[u8; 32]>().to_vec(),
+    };
+    let sk = V::private_key_from_ikm(&ikm).expect("seed must be at least 32 bytes");
+
+    println!("keygen");
+    println!(
+        "{:30}: {}",
+        "private key",
+        encode(V::private_key_to_bytes(&sk))
+    );
+    println!(
+        "{:30}: {}",
+        "public key",
+        encode(V::public_key_to_bytes(&V::public_key(&sk)))
+    );
+    if cmd.pop {
+        let proof = V::pop_prove(&sk);
+        println!(
+            "{:30}: {}",
+            "proof of possession",
+            encode(V::signature_to_bytes(&proof))
+        );
+    }
+}
+
+fn cli_measure_deposit(cmd: &Deposit) {
+    #[cfg(feature = "keystore")]
+    let sk = keystore_private_key::<MinPk>(&cmd.keystore)
+        .unwrap_or_else(|| Bls12381G1PrivateKey::from_u64(1).unwrap());
+    #[cfg(not(feature = "keystore"))]
+    let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+
+    let deposit = DepositMessage {
+        public_key: sk.public_key(),
+        withdrawal_credentials: hex::decode(cmd.withdrawal_credentials.trim_start_matches("0x"))
+            .unwrap()
+            .try_into()
+            .expect("withdrawal credentials must be 32 bytes"),
+        amount: cmd.amount,
+    };
+    let fork_version: [u8; 4] = hex::decode(cmd.fork_version.trim_start_matches("0x"))
+        .unwrap()
+        .try_into()
+        .expect("fork version must be 4 bytes");
+
+    println!("deposit");
+    println!("{:30}: {}", "pubkey", encode(deposit.public_key.0));
+    println!(
+        "{:30}: {}",
+        "withdrawal_credentials",
+        hex::encode(deposit.withdrawal_credentials)
+    );
+    println!("{:30}: {}", "amount", deposit.amount);
+    println!(
+        "{:30}: {}",
+        "signing_root",
+        hex::encode(deposit.signing_root(fork_version))
+    );
+
+    // Instructions include computation of the signing root
+    let (signature, _) = perf!("sign", deposit.sign(&sk, fork_version));
+    let (result, _) = perf!("verify", deposit.verify(&signature, fork_version));
+    assert!(result);
+    println!("{:30}: {}", "signature", encode(signature.0));
+}
+
+#[cfg(feature = "keystore")]
+fn cli_measure_keystore_decrypt(cmd: &KeystoreDecrypt) {
+    let sk = Bls12381G1PrivateKey::from_u64(1).unwrap();
+    let password = "password";
+
+    println!("keystore_decrypt scrypt");
+    for log_n in &cmd.scrypt_log_ns {
+        let kdf = KeystoreKdf::Scrypt {
+            log_n: *log_n,
+            r: 8,
+            p: 1,
+        };
+        let keystore = Keystore::encrypt(&sk, password, kdf, "").unwrap();
+        let (result, _) = perf!(format!("n 2^{}", log_n), keystore.decrypt(password));
+        assert!(result.is_ok());
+    }
+
+    println!("keystore_decrypt pbkdf2");
+    let mut points = vec![];
+    for c in &cmd.pbkdf2_iterations {
+        let keystore = Keystore::encrypt(&sk, password, KeystoreKdf::Pbkdf2 { c: *c }, "").unwrap();
+        let (result, count) = perf!(format!("c {}", c), keystore.decrypt(password));
+        assert!(result.is_ok());
+        points.push(SweepPoint {
+            x: *c as u64,
+            measured: count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost("keystore_decrypt pbkdf2", "iteration", &points);
+}
+
+#[cfg(feature = "mnemonic")]
+fn cli_measure_mnemonic_derive() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    println!("mnemonic_derive");
+    let (seed, _) = perf!("mnemonic_to_seed", mnemonic_to_seed(mnemonic, "TREZOR"));
+    let seed = seed.unwrap();
+    let (result, _) = perf!(
+        "derive_master_private_key",
+        derive_master_private_key(&seed)
+    );
+    assert!(result.is_ok());
+    let (result, _) = perf!(
+        "master_private_key_from_mnemonic",
+        master_private_key_from_mnemonic(mnemonic, "TREZOR")
+    );
+    assert!(result.is_ok());
+}
+
+fn cli_measure_pop_prove<V: Bls12381Variant>() {
+    let sk = V::private_key_from_u64(1);
+
+    println!("pop_prove");
+    let (_, count) = perf!("total_instructions", V::pop_prove(&sk));
+    print_variant_calc_instructions::<V>(calc::calc_pop_prove_instructions(), count);
+}
 
-    // Aggregate the signature
-    let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+fn cli_pop<V: Bls12381Variant>(cmd: &Pop) {
+    let sk = private_key_from_arg::<V>(&cmd.key);
+    let proof = V::pop_prove(&sk);
+
+    println!(
+        "{:30}: {}",
+        "public key",
+        encode(V::public_key_to_bytes(&V::public_key(&sk)))
+    );
+    println!("{:30}: {}", "proof", encode(V::signature_to_bytes(&proof)));
+}
+
+fn cli_pop_verify<V: Bls12381Variant>(pk: &str, proof: &str) {
+    let pk = decode(pk)
+        .and_then(|bytes| V::public_key_from_bytes(&bytes).ok())
+        .expect("invalid public key");
+    let proof = decode(proof)
+        .and_then(|bytes| V::signature_from_bytes(&bytes).ok())
+        .expect("invalid proof");
 
-    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
-        pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
+    let result = V::pop_verify(&pk, &proof);
+    println!("{:30}: {}", "valid", result);
 
-    cli_measure_aggregate_verify(threaded, &pub_keys_msgs, &agg_sig);
+    if !result {
+        std::process::exit(1);
+    }
 }
 
-fn cli_measure_aggregate_verify_sizes(threaded: bool, cmd: &AggregateVerifySizes) {
-    let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data2(&cmd.msg_sizes);
+fn cli_measure_pop_verify<V: Bls12381Variant>() {
+    let sk = V::private_key_from_u64(1);
+    let pk = V::public_key(&sk);
+    let proof = V::pop_prove(&sk);
 
-    // Aggregate the signature
-    let agg_sig = Bls12381G2Signature::aggregate(&sigs).unwrap();
+    println!("pop_verify");
+    let (result, count) = perf!("total_instructions", V::pop_verify(&pk, &proof));
+    assert!(result);
+    print_variant_calc_instructions::<V>(calc::calc_pop_verify_instructions(), count);
+}
+
+fn cli_measure_pop_registry_populate(cmd: &PopRegistryPopulate) {
+    println!("pop_registry_populate");
+    let mut points = vec![];
+    for cnt in &cmd.key_cnts {
+        let (pks, proofs) = get_pop_registry_test_data(*cnt);
+        let (registry, count) = perf!(format!("cnt {}", cnt), {
+            let mut registry = PopRegistry::new();
+            for (pk, proof) in pks.iter().zip(proofs.iter()) {
+                registry.register(pk, proof).unwrap();
+            }
+            registry
+        });
+        assert_eq!(registry.len(), *cnt as usize);
+        let calc_instructions = *cnt as u64 * calc::calc_pop_verify_instructions() as u64;
+        if !measures_time() {
+            println!(
+                "{:30}: {} diff: {}",
+                "calc_instructions",
+                calc_instructions,
+                count as i64 - calc_instructions as i64
+            );
+        }
+        points.push(SweepPoint {
+            x: *cnt as u64,
+            measured: count,
+            calculated: Some(calc_instructions),
+        });
+    }
+    report_marginal_cost("pop_registry_populate", "key", &points);
+}
+
+fn cli_measure_signature_validate<V: Bls12381Variant>(cmd: &SignatureValidate) {
+    let (_sks, _pks, _msgs, sigs) = V::get_aggregate_verify_test_data(cmd.sig_cnt, cmd.sig_cnt, 32);
+    let sigs_bytes: Vec<Vec<u8>> = sigs.iter().map(V::signature_to_bytes).collect();
 
-    let pub_keys_msgs: Vec<(Bls12381G1PublicKey, Vec<u8>)> =
-        pks.iter().zip(msgs).map(|(pk, sk)| (*pk, sk)).collect();
+    println!("signature_validate");
+    let (native_sigs, count) = perf!(
+        "sig_from_bytes",
+        sigs_bytes
+            .iter()
+            .map(|sig| V::NativeSignature::from_bytes(sig).unwrap())
+            .collect::<Vec<V::NativeSignature>>()
+    );
+    let calc_instructions = calc::calc_signature_from_bytes_instructions(cmd.sig_cnt);
+    print_variant_calc_instructions::<V>(calc_instructions, count);
+
+    let (result, count) = perf!(
+        "sig_validate",
+        native_sigs.iter().all(|sig| sig.validate().is_ok())
+    );
+    assert!(result);
+    let calc_instructions = calc::calc_signature_validate_instructions(cmd.sig_cnt);
+    print_variant_calc_instructions::<V>(calc_instructions, count);
 
-    cli_measure_aggregate_verify(threaded, &pub_keys_msgs, &agg_sig);
+    let (result, count) = perf!(
+        "sig_is_valid",
+        sigs.iter().all(|sig| V::signature_is_valid(sig))
+    );
+    assert!(result);
+    let calc_instructions = calc::calc_signature_from_bytes_instructions(cmd.sig_cnt)
+        + calc::calc_signature_validate_instructions(cmd.sig_cnt);
+    print_variant_calc_instructions::<V>(calc_instructions, count);
 }
 
-fn cli_measure_signature_aggregate(cmd: &SignatureAggregate) {
-    let (_sks, _pks, _msg, sigs) = get_fast_aggregate_verify_test_data(cmd.sig_cnt, 100);
+fn cli_measure_public_key_validate<V: Bls12381Variant>(cmd: &PublicKeyValidate) {
+    println!("public_key_validate");
+    for cnt in &cmd.key_cnts {
+        let (_sks, pks, _msg, _sigs) = V::get_fast_aggregate_verify_test_data(*cnt, 32);
+        let pks_bytes: Vec<Vec<u8>> = pks.iter().map(V::public_key_to_bytes).collect();
 
-    println!("signature_aggregate");
-    let (_, count) = perf!("measured_sig_aggr", Bls12381G2Signature::aggregate(&sigs));
-    let calc_instructions = calc::calc_signature_aggregate_instructions(cmd.sig_cnt);
+        println!("key_cnt {}", cnt);
+        let (native_pks, count) = perf!(
+            "pk_from_bytes",
+            pks_bytes
+                .iter()
+                .map(|pk| V::NativePublicKey::from_bytes(pk).unwrap())
+                .collect::<Vec<V::NativePublicKey>>()
+        );
+        let calc_instructions = calc::calc_public_key_from_bytes_instructions(*cnt);
+        print_variant_calc_instructions::<V>(calc_instructions, count);
+
+        let (result, count) = perf!(
+            "pk_validate",
+            native_pks.iter().all(|pk| pk.validate().is_ok())
+        );
+        assert!(result);
+        let calc_instructions = calc::calc_public_key_validate_instructions(*cnt);
+        print_variant_calc_instructions::<V>(calc_instructions, count);
+
+        let (result, count) = perf!(
+            "pk_is_valid",
+            pks.iter().all(|pk| V::public_key_is_valid(pk))
+        );
+        assert!(result);
+        let calc_instructions = calc::calc_public_key_from_bytes_instructions(*cnt)
+            + calc::calc_public_key_validate_instructions(*cnt);
+        print_variant_calc_instructions::<V>(calc_instructions, count);
+    }
+}
+
+fn print_encoding_delta(compressed: u64, uncompressed: u64) {
+    println!(
+        "{:30}: {}",
+        "uncompressed_delta",
+        uncompressed as i64 - compressed as i64
+    );
+}
+
+fn cli_measure_serialization<V: Bls12381Variant>(cmd: &Serialization) {
+    let (_sks, pks, _msgs, sigs) = V::get_aggregate_verify_test_data(cmd.cnt, cmd.cnt, 32);
+    let pks: Vec<V::NativePublicKey> = pks
+        .iter()
+        .map(|pk| V::NativePublicKey::from_bytes(&V::public_key_to_bytes(pk)).unwrap())
+        .collect();
+    let sigs: Vec<V::NativeSignature> = sigs
+        .iter()
+        .map(|sig| V::NativeSignature::from_bytes(&V::signature_to_bytes(sig)).unwrap())
+        .collect();
+
+    println!("serialization public_key");
+    let (pks_compressed, compressed) = perf!(
+        "pk_compress",
+        pks.iter().map(|pk| pk.compress()).collect::<Vec<_>>()
+    );
+    let (pks_uncompressed, uncompressed) = perf!(
+        "pk_serialize",
+        pks.iter().map(|pk| pk.serialize()).collect::<Vec<_>>()
+    );
+    print_encoding_delta(compressed, uncompressed);
+
+    let (_, compressed) = perf!(
+        "pk_uncompress",
+        pks_compressed
+            .iter()
+            .map(|pk| V::NativePublicKey::uncompress(pk.as_ref()).unwrap())
+            .collect::<Vec<V::NativePublicKey>>()
+    );
+    let (_, uncompressed) = perf!(
+        "pk_deserialize",
+        pks_uncompressed
+            .iter()
+            .map(|pk| V::NativePublicKey::deserialize(pk.as_ref()).unwrap())
+            .collect::<Vec<V::NativePublicKey>>()
+    );
+    print_encoding_delta(compressed, uncompressed);
+
+    println!("serialization signature");
+    let (sigs_compressed, compressed) = perf!(
+        "sig_compress",
+        sigs.iter().map(|sig| sig.compress()).collect::<Vec<_>>()
+    );
+    let (sigs_uncompressed, uncompressed) = perf!(
+        "sig_serialize",
+        sigs.iter().map(|sig| sig.serialize()).collect::<Vec<_>>()
+    );
+    print_encoding_delta(compressed, uncompressed);
+
+    let (_, compressed) = perf!(
+        "sig_uncompress",
+        sigs_compressed
+            .iter()
+            .map(|sig| V::NativeSignature::uncompress(sig.as_ref()).unwrap())
+            .collect::<Vec<V::NativeSignature>>()
+    );
+    let (_, uncompressed) = perf!(
+        "sig_deserialize",
+        sigs_uncompressed
+            .iter()
+            .map(|sig| V::NativeSignature::deserialize(sig.as_ref()).unwrap())
+            .collect::<Vec<V::NativeSignature>>()
+    );
+    print_encoding_delta(compressed, uncompressed);
+}
+
+fn cli_measure_g2_decompress(cmd: &G2Decompress) {
+    println!("g2_decompress");
+    for cnt in &cmd.cnts {
+        let (_sks, _pks, _msgs, sigs) = get_aggregate_verify_test_data(*cnt, *cnt, 32);
+
+        let (points, count) = perf!(
+            format!("cnt {}", cnt),
+            sigs.iter()
+                .map(|sig| g2_decompress(&sig.0))
+                .collect::<Vec<Option<blst::blst_p2_affine>>>()
+        );
+        assert!(points.iter().all(|p| p.is_some()));
+        print_calc_instructions(calc::calc_g2_decompress_instructions(*cnt), count);
+    }
+}
+
+fn cli_measure_pairing(cmd: &Pairing) {
+    let (p1s, p2s) = get_pairing_test_data(cmd.pair_cnt);
+
+    println!("pairing");
+    let (f, count) = perf!("miller_loop", miller_loop(&p1s, &p2s));
+    if cmd.pair_cnt > 0 {
+        println!(
+            "{:30}: {}",
+            "miller_loop_per_pair",
+            count / u64::from(cmd.pair_cnt)
+        );
+    }
+    let calc_instructions = calc::calc_miller_loop_instructions(cmd.pair_cnt);
+    print_calc_instructions(calc_instructions, count);
+
+    let (_, count) = perf!("final_exp", final_exp(&f));
+    let calc_instructions = calc::calc_final_exp_instructions();
+    print_calc_instructions(calc_instructions, count);
+}
+
+fn cli_measure_gt(cmd: &Gt) {
+    let elems = get_gt_test_data(cmd.cnt);
+    let exp: Vec<u8> = vec![0xa5; (cmd.exp_bits as usize).div_ceil(8)];
+
+    println!("gt");
+    let (_, count) = perf!(
+        "gt_mul",
+        elems
+            .iter()
+            .zip(elems.iter().rev())
+            .map(|(a, b)| fp12_mul(a, b))
+            .collect::<Vec<blst::blst_fp12>>()
+    );
+    let calc_instructions = calc::calc_gt_mul_instructions(cmd.cnt);
+    print_calc_instructions(calc_instructions, count);
+
+    let (_, count) = perf!(
+        "gt_pow",
+        elems
+            .iter()
+            .map(|a| gt_pow(a, &exp))
+            .collect::<Vec<blst::blst_fp12>>()
+    );
+    let calc_instructions = calc::calc_gt_pow_instructions(cmd.cnt, 8 * exp.len() as u32);
+    print_calc_instructions(calc_instructions, count);
+}
+
+fn cli_measure_ed25519_verify(cmd: &Verify) -> u64 {
+    let (pk, msg, sig) = get_ed25519_verify_test_data(cmd.msg_size);
+
+    println!("ed25519_verify");
+    let (result, count) = perf!("total_instructions", verify_ed25519(&msg, &pk, &sig));
+    assert!(result);
+    count
+}
 
+fn cli_measure_secp256k1_verify(cmd: &Verify) -> u64 {
+    let (pk, msg, sig) = get_ecdsa_secp256k1_verify_test_data(cmd.msg_size);
+
+    println!("secp256k1_verify");
+    let (result, count) = perf!(
+        "total_instructions",
+        verify_ecdsa_secp256k1(&msg, &pk, &sig)
+    );
+    assert!(result);
+    count
+}
+
+fn cli_measure_compare_verify<V: Bls12381Variant>(cmd: &Verify) {
+    let (_sks, pks, msgs, sigs) = V::get_aggregate_verify_test_data(1, 1, cmd.msg_size);
+
+    println!("bls_verify");
+    let (result, bls_count) = perf!("total_instructions", V::verify(&msgs[0], &pks[0], &sigs[0]));
+    assert!(result);
+
+    let ed25519_count = cli_measure_ed25519_verify(cmd);
+    let secp256k1_count = cli_measure_secp256k1_verify(cmd);
+
+    // Cost of BLS verification relative to the other schemes
+    for (scheme, count) in [("ed25519", ed25519_count), ("secp256k1", secp256k1_count)] {
+        if count != 0 {
+            println!(
+                "{:30}: {:.2}",
+                format!("bls_to_{}_ratio", scheme),
+                bls_count as f64 / count as f64
+            );
+        }
+    }
+}
+
+/// Prints number of instructions per unit of gas of the precompile
+fn print_instructions_per_gas(count: u64, gas: u64) {
+    println!("{:30}: {}", "gas", gas);
+    println!("{:30}: {}", "instructions_per_gas", count / gas);
+}
+
+fn cli_measure_precompile(cmd: &Precompile) {
+    match cmd {
+        Precompile::G1Add => {
+            let input = get_precompile_g1_add_input();
+            println!("precompile_g1_add");
+            let (result, count) = perf!("total_instructions", precompile_g1_add(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_g1_add_instructions(), count);
+            print_instructions_per_gas(count, EIP2537_G1ADD_GAS);
+        }
+        Precompile::G1Mul => {
+            let input = get_precompile_g1_msm_input(1);
+            println!("precompile_g1_mul");
+            let (result, count) = perf!("total_instructions", precompile_g1_mul(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_g1_msm_instructions(1), count);
+            print_instructions_per_gas(count, EIP2537_G1MUL_GAS);
+        }
+        Precompile::G1Msm(args) => {
+            println!("precompile_g1_msm");
+            for cnt in &args.cnts {
+                let input = get_precompile_g1_msm_input(*cnt);
+                let (result, count) = perf!(format!("cnt {}", cnt), precompile_g1_msm(&input));
+                assert!(result.is_ok());
+                print_calc_instructions(calc::calc_precompile_g1_msm_instructions(*cnt), count);
+                // Gas without MSM discount, ie. cost of separate multiplications
+                print_instructions_per_gas(count, EIP2537_G1MUL_GAS * u64::from(*cnt));
+            }
+        }
+        Precompile::G2Add => {
+            let input = get_precompile_g2_add_input();
+            println!("precompile_g2_add");
+            let (result, count) = perf!("total_instructions", precompile_g2_add(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_g2_add_instructions(), count);
+            print_instructions_per_gas(count, EIP2537_G2ADD_GAS);
+        }
+        Precompile::G2Mul => {
+            let input = get_precompile_g2_msm_input(1);
+            println!("precompile_g2_mul");
+            let (result, count) = perf!("total_instructions", precompile_g2_mul(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_g2_msm_instructions(1), count);
+            print_instructions_per_gas(count, EIP2537_G2MUL_GAS);
+        }
+        Precompile::G2Msm(args) => {
+            println!("precompile_g2_msm");
+            for cnt in &args.cnts {
+                let input = get_precompile_g2_msm_input(*cnt);
+                let (result, count) = perf!(format!("cnt {}", cnt), precompile_g2_msm(&input));
+                assert!(result.is_ok());
+                print_calc_instructions(calc::calc_precompile_g2_msm_instructions(*cnt), count);
+                // Gas without MSM discount, ie. cost of separate multiplications
+                print_instructions_per_gas(count, EIP2537_G2MUL_GAS * u64::from(*cnt));
+            }
+        }
+        Precompile::Pairing(args) => {
+            println!("precompile_pairing");
+            for cnt in &args.pair_cnts {
+                let input = get_precompile_pairing_input(*cnt);
+                let (result, count) =
+                    perf!(format!("pair_cnt {}", cnt), precompile_pairing(&input));
+                assert!(result.is_ok());
+                print_calc_instructions(calc::calc_precompile_pairing_instructions(*cnt), count);
+                print_instructions_per_gas(count, eip2537_pairing_gas(*cnt));
+            }
+        }
+        Precompile::MapFpToG1 => {
+            let input = get_precompile_map_fp_to_g1_input();
+            println!("precompile_map_fp_to_g1");
+            let (result, count) = perf!("total_instructions", precompile_map_fp_to_g1(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_map_fp_to_g1_instructions(), count);
+            print_instructions_per_gas(count, EIP2537_MAP_FP_TO_G1_GAS);
+        }
+        Precompile::MapFp2ToG2 => {
+            let input = get_precompile_map_fp2_to_g2_input();
+            println!("precompile_map_fp2_to_g2");
+            let (result, count) = perf!("total_instructions", precompile_map_fp2_to_g2(&input));
+            assert!(result.is_ok());
+            print_calc_instructions(calc::calc_precompile_map_fp2_to_g2_instructions(), count);
+            print_instructions_per_gas(count, EIP2537_MAP_FP2_TO_G2_GAS);
+        }
+    }
+}
+
+/// Prints bytes per instruction, or MB/s if wall time is measured (time method)
+fn print_hash_throughput(size: usize, count: u64) {
+    if count == 0 {
+        return;
+    }
+    match measures_time() {
+        true => println!(
+            "{:30}: {:.1}",
+            "mb_per_sec",
+            size as f64 * 1000.0 / count as f64
+        ),
+        false => println!(
+            "{:30}: {:.3}",
+            "bytes_per_instruction",
+            size as f64 / count as f64
+        ),
+    }
+}
+
+/// Prints calculated instructions and their difference from the measured ones,
+/// unless wall time is measured
+fn print_calc_instructions(calc_instructions: u32, count: u64) {
+    if measures_time() {
+        return;
+    }
     println!(
         "{:30}: {} diff: {}",
         "calc_instructions",
@@ -178,18 +3080,522 @@ fn cli_measure_signature_aggregate(cmd: &SignatureAggregate) {
     );
 }
 
-fn cli_measure_hash_to_point(cmd: &HashToPoint) {
+/// Prints calculated instructions only if calc models were fitted for given variant
+fn print_variant_calc_instructions<V: Bls12381Variant>(calc_instructions: u32, count: u64) {
+    if V::CALC_MODELS {
+        print_calc_instructions(calc_instructions, count);
+    }
+}
+
+fn cli_measure_point_ops(cmd: &PointOps) {
+    match cmd.group {
+        Group::G1 => {
+            let (affine, projective) = get_g1_test_data(cmd.cnt);
+
+            println!("point_ops g1");
+            let (_, count) = perf!(
+                "add",
+                projective
+                    .iter()
+                    .fold(projective[0], |acc, p| g1_add(&acc, p))
+            );
+            print_calc_instructions(calc::calc_g1_add_instructions(cmd.cnt), count);
+
+            let (_, count) = perf!(
+                "add_affine",
+                affine
+                    .iter()
+                    .fold(projective[0], |acc, p| g1_add_affine(&acc, p))
+            );
+            print_calc_instructions(calc::calc_g1_add_affine_instructions(cmd.cnt), count);
+
+            let (_, count) = perf!(
+                "double",
+                projective.iter().map(g1_double).collect::<Vec<_>>()
+            );
+            print_calc_instructions(calc::calc_g1_double_instructions(cmd.cnt), count);
+        }
+        Group::G2 => {
+            let (affine, projective) = get_g2_test_data(cmd.cnt);
+
+            println!("point_ops g2");
+            let (_, count) = perf!(
+                "add",
+                projective
+                    .iter()
+                    .fold(projective[0], |acc, p| g2_add(&acc, p))
+            );
+            print_calc_instructions(calc::calc_g2_add_instructions(cmd.cnt), count);
+
+            let (_, count) = perf!(
+                "add_affine",
+                affine
+                    .iter()
+                    .fold(projective[0], |acc, p| g2_add_affine(&acc, p))
+            );
+            print_calc_instructions(calc::calc_g2_add_affine_instructions(cmd.cnt), count);
+
+            let (_, count) = perf!(
+                "double",
+                projective.iter().map(g2_double).collect::<Vec<_>>()
+            );
+            print_calc_instructions(calc::calc_g2_double_instructions(cmd.cnt), count);
+        }
+    }
+}
+
+fn cli_measure_sensitivity<V: Bls12381Variant>(cmd: &Sensitivity) {
+    // Fixed values of the parameter that is not swept
+    let msg_size = cmd.msg_sizes[0];
+    let msg_cnt = cmd.msg_cnts[0];
+    // The same data is used by several operations and both sweeps
+    let mut cache = TestDataCache::<V>::new();
+
+    println!("sensitivity verify");
+    let verify_per_byte: Vec<SweepPoint> = cmd
+        .msg_sizes
+        .iter()
+        .map(|size| {
+            let (_sks, pks, msgs, sigs) = cache.aggregate_verify(1, 1, *size);
+            let (_, count) = perf!(
+                format!("size {}", size),
+                V::verify(&msgs[0], &pks[0], &sigs[0])
+            );
+            SweepPoint {
+                x: *size as u64,
+                measured: count,
+                calculated: V::CALC_MODELS.then(|| calc::calc_verify_instructions(*size).into()),
+            }
+        })
+        .collect();
+
+    println!("sensitivity sign");
+    let sk = V::private_key_from_u64(1);
+    let sign_per_byte: Vec<SweepPoint> = cmd
+        .msg_sizes
+        .iter()
+        .map(|size| {
+            let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+            let (_, count) = perf!(format!("size {}", size), V::sign(&sk, &msg));
+            SweepPoint {
+                x: *size as u64,
+                measured: count,
+                calculated: V::CALC_MODELS.then(|| calc::calc_sign_instructions(*size).into()),
+            }
+        })
+        .collect();
+
+    println!("sensitivity fast_aggregate_verify");
+    let mut fast_aggregate_verify = |cnt: u32, size: usize| {
+        let (_sks, pks, msg, sigs) = cache.fast_aggregate_verify(cnt, size);
+        let agg_sig = V::aggregate_signatures(sigs).unwrap();
+        let (_, count) = perf!(
+            format!("cnt {} size {}", cnt, size),
+            V::fast_aggregate_verify(msg, pks, &agg_sig)
+        );
+        SweepPoint {
+            x: 0,
+            measured: count,
+            calculated: V::CALC_MODELS
+                .then(|| calc::calc_fast_aggregate_verify_instructions(cnt, size).into()),
+        }
+    };
+    let fast_aggregate_verify_per_byte: Vec<SweepPoint> = cmd
+        .msg_sizes
+        .iter()
+        .map(|size| SweepPoint {
+            x: *size as u64,
+            ..fast_aggregate_verify(msg_cnt, *size)
+        })
+        .collect();
+    let fast_aggregate_verify_per_signer: Vec<SweepPoint> = cmd
+        .msg_cnts
+        .iter()
+        .map(|cnt| SweepPoint {
+            x: (*cnt).into(),
+            ..fast_aggregate_verify(*cnt, msg_size)
+        })
+        .collect();
+
+    println!("sensitivity aggregate_verify");
+    let mut aggregate_verify = |cnt: u32, size: usize| {
+        let (_sks, pks, msgs, sigs) = cache.aggregate_verify(cnt, cnt, size);
+        let agg_sig = V::aggregate_signatures(sigs).unwrap();
+        let pub_keys_msgs: Vec<(V::PublicKey, &[u8])> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| (*pk, msg.as_slice()))
+            .collect();
+        let (_, count) = perf!(
+            format!("cnt {} size {}", cnt, size),
+            V::aggregate_verify(&pub_keys_msgs, &agg_sig)
+        );
+        let sizes = vec![size; cnt as usize];
+        SweepPoint {
+            x: 0,
+            measured: count,
+            calculated: V::CALC_MODELS
+                .then(|| calc::calc_aggregate_verify_instructions_no_threaded(&sizes).into()),
+        }
+    };
+    let aggregate_verify_per_byte: Vec<SweepPoint> = cmd
+        .msg_sizes
+        .iter()
+        .map(|size| SweepPoint {
+            x: *size as u64,
+            ..aggregate_verify(msg_cnt, *size)
+        })
+        .collect();
+    let aggregate_verify_per_msg: Vec<SweepPoint> = cmd
+        .msg_cnts
+        .iter()
+        .map(|cnt| SweepPoint {
+            x: (*cnt).into(),
+            ..aggregate_verify(*cnt, msg_size)
+        })
+        .collect();
+
+    println!("sensitivity signature_aggregate");
+    let signature_aggregate_per_sig: Vec<SweepPoint> = cmd
+        .msg_cnts
+        .iter()
+        .map(|cnt| {
+            let (_sks, _pks, _msg, sigs) = cache.fast_aggregate_verify(*cnt, 100);
+            let (_, count) = perf!(format!("cnt {}", cnt), V::aggregate_signatures(sigs));
+            SweepPoint {
+                x: (*cnt).into(),
+                measured: count,
+                calculated: V::CALC_MODELS
+                    .then(|| calc::calc_signature_aggregate_instructions(*cnt).into()),
+            }
+        })
+        .collect();
+
+    println!("sensitivity keccak256");
+    let keccak256_per_byte: Vec<SweepPoint> = cmd
+        .msg_sizes
+        .iter()
+        .map(|size| {
+            let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+            let (_, count) = perf!(format!("size {}", size), keccak256_hash(&msg));
+            SweepPoint {
+                x: *size as u64,
+                measured: count,
+                calculated: Some(calc::calc_keccak256_instructions(*size).into()),
+            }
+        })
+        .collect();
+
+    let unit = if measures_time() {
+        "ns"
+    } else {
+        "instructions"
+    };
+    println!("marginal costs ({})", unit);
+    report_marginal_cost("verify", "byte", &verify_per_byte);
+    report_marginal_cost("sign", "byte", &sign_per_byte);
+    report_marginal_cost(
+        "fast_aggregate_verify",
+        "byte",
+        &fast_aggregate_verify_per_byte,
+    );
+    report_marginal_cost(
+        "fast_aggregate_verify",
+        "signer",
+        &fast_aggregate_verify_per_signer,
+    );
+    report_marginal_cost("aggregate_verify", "byte", &aggregate_verify_per_byte);
+    report_marginal_cost("aggregate_verify", "msg", &aggregate_verify_per_msg);
+    report_marginal_cost("signature_aggregate", "sig", &signature_aggregate_per_sig);
+    report_marginal_cost("keccak256", "byte", &keccak256_per_byte);
+}
+
+fn cli_measure_hash_chain(cmd: &HashChain) {
+    let start = keccak256_hash([]);
+
+    println!("hash_chain");
+    let mut points = vec![];
+    for n in &cmd.lengths {
+        let (_, count) = perf!(format!("n {}", n), keccak256_hash_chain(start, *n));
+        let calculated = calc::calc_keccak256_chain_instructions(*n);
+        print_calc_instructions(calculated, count);
+        points.push(SweepPoint {
+            x: *n as u64,
+            measured: count,
+            calculated: Some(calculated as u64),
+        });
+    }
+    report_marginal_cost("hash_chain", "iteration", &points);
+}
+
+fn cli_measure_keccak256_parallel(cmd: &Keccak256Parallel) {
     let msg: Vec<u8> = vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size];
 
-    println!("hash_to_point");
-    perf!("total_instructions", hash_to_g2(&msg));
+    println!("keccak256_parallel");
+    let start = Instant::now();
+    perf!("sequential", keccak256_hash(&msg));
+    let sequential_time = start.elapsed();
+    println!("{:30}: {:?}", "sequential_time", sequential_time);
+
+    for chunk_size in &cmd.chunk_sizes {
+        for threads in &cmd.threads {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(*threads)
+                .build()
+                .unwrap();
+            // Instructions of all threads are counted (perf method)
+            let start = Instant::now();
+            perf!(
+                format!("chunk {} threads {}", chunk_size, threads),
+                pool.install(|| keccak256_hash_parallel(&msg, *chunk_size))
+            );
+            let time = start.elapsed();
+            println!("{:30}: {:?}", "time", time);
+            println!(
+                "{:30}: {:.2}",
+                "speedup",
+                sequential_time.as_secs_f64() / time.as_secs_f64()
+            );
+        }
+    }
+}
+
+fn cli_measure_keccak_f(cmd: &KeccakF) {
+    println!("keccak_f1600");
+    let mut state = [0u64; 25];
+    let (_, count) = perf!(format!("iterations {}", cmd.iterations), {
+        for _ in 0..cmd.iterations {
+            keccak_f1600(&mut state);
+        }
+    });
+    let permutation_count = count / cmd.iterations.max(1) as u64;
+    println!("{:30}: {}", "per_permutation", permutation_count);
+
+    type HashFn = fn(&[u8]);
+    let hashes: [(&str, usize, HashFn); 2] = [
+        ("keccak256", KECCAK256_RATE, |msg| {
+            keccak256_hash(msg);
+        }),
+        ("keccak512", KECCAK512_RATE, |msg| {
+            keccak512_hash(msg);
+        }),
+    ];
+    for (name, rate, hash) in hashes {
+        println!("{}", name);
+        for size in &cmd.msg_sizes {
+            let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+            let (_, count) = perf!(format!("size {}", size), hash(&msg));
+            print_hash_throughput(*size, count);
+            let permutations = keccak_permutation_count(*size, rate) as u64;
+            println!("{:30}: {}", "permutations", permutations);
+            println!(
+                "{:30}: {}",
+                "overhead",
+                count as i64 - (permutations * permutation_count) as i64
+            );
+        }
+    }
+}
+
+fn cli_measure_merkle_build(cmd: &MerkleBuild) {
+    println!("merkle_build arity {}", cmd.arity);
+    let mut points = vec![];
+    for cnt in &cmd.leaf_cnts {
+        let leaves: Vec<Vec<u8>> = (0..*cnt)
+            .map(|i| vec![(i % u8::MAX as usize) as u8; cmd.leaf_size])
+            .collect();
+        let (tree, count) = perf!(
+            format!("leaves {}", cnt),
+            MerkleTree::build(&leaves, cmd.arity)
+        );
+        let tree = tree.unwrap();
+        let calculated = calc::calc_merkle_build_instructions(*cnt, cmd.leaf_size, cmd.arity);
+        println!("{:30}: {}", "depth", tree.depth());
+        println!(
+            "{:30}: {}",
+            "hashes",
+            cnt + merkle_internal_node_count(*cnt, cmd.arity)
+        );
+        print_calc_instructions(calculated, count);
+        points.push(SweepPoint {
+            x: *cnt as u64,
+            measured: count,
+            calculated: Some(calculated as u64),
+        });
+    }
+    report_marginal_cost("merkle_build", "leaf", &points);
+}
+
+fn cli_measure_merkle_verify(cmd: &MerkleVerify) {
+    println!("merkle_verify");
+    let mut points = vec![];
+    for depth in &cmd.depths {
+        let leaves: Vec<[u8; 32]> = (0..1usize << depth)
+            .map(|i| keccak256_hash(i.to_le_bytes()).0)
+            .collect();
+        let tree = MerkleTree::build(&leaves, 2).unwrap();
+        let proof = tree.prove(leaves.len() - 1).unwrap();
+
+        let (result, count) = perf!(
+            format!("depth {}", depth),
+            proof.verify(&tree.root(), &leaves[leaves.len() - 1])
+        );
+        assert!(result);
+        let calculated = calc::calc_merkle_verify_instructions(*depth);
+        print_calc_instructions(calculated, count);
+        points.push(SweepPoint {
+            x: *depth as u64,
+            measured: count,
+            calculated: Some(calculated as u64),
+        });
+    }
+    report_marginal_cost("merkle_verify", "level", &points);
+}
+
+fn cli_measure_hmac(cmd: &HashSizes) {
+    let key = [0x0bu8; 32];
+
+    println!("hmac_keccak256");
+    for size in &cmd.msg_sizes {
+        let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+        let (_, hash_count) = perf!(format!("keccak256 size {}", size), keccak256_hash(&msg));
+        let (_, count) = perf!(format!("hmac size {}", size), hmac_keccak256(&key, &msg));
+        print_calc_instructions(calc::calc_hmac_keccak256_instructions(*size), count);
+        print_hash_throughput(*size, count);
+        println!("{:30}: {}", "overhead", count as i64 - hash_count as i64);
+    }
+
+    println!("hmac_sha256");
+    for size in &cmd.msg_sizes {
+        let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+        let (_, hash_count) = perf!(format!("sha256 size {}", size), sha256_hash(&msg));
+        let (_, count) = perf!(format!("hmac size {}", size), hmac_sha256(&key, &msg));
+        print_hash_throughput(*size, count);
+        println!("{:30}: {}", "overhead", count as i64 - hash_count as i64);
+    }
+}
+
+fn cli_measure_hash(name: &str, hash: fn(&[u8]) -> Hash, cmd: &HashSizes) {
+    println!("{}", name);
+    let mut points = vec![];
+    for size in &cmd.msg_sizes {
+        let msg: Vec<u8> = vec![(size % u8::MAX as usize) as u8; *size];
+        let (_, count) = perf!(format!("size {}", size), hash(&msg));
+        print_hash_throughput(*size, count);
+        points.push(SweepPoint {
+            x: *size as u64,
+            measured: count,
+            calculated: None,
+        });
+    }
+    report_marginal_cost(name, "byte", &points);
 }
 
-fn cli_measure_keccak256(cmd: &Verify) {
+fn cli_measure_keccak256_chunked(cmd: &Keccak256Chunked) {
     let msg: Vec<u8> = vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size];
 
-    println!("keccak25");
-    perf!("total_instructions", keccak256_hash(&msg));
+    println!("keccak256_chunked");
+    let (hash, count) = perf!("one_shot", keccak256_hash(&msg));
+    print_calc_instructions(calc::calc_keccak256_instructions(cmd.msg_size), count);
+
+    for chunk_size in &cmd.chunk_sizes {
+        let (result, chunked_count) = perf!(
+            format!("chunk {}", chunk_size),
+            keccak256_hash_iter(msg.chunks(*chunk_size))
+        );
+        assert_eq!(result, hash);
+        println!("{:30}: {}", "overhead", chunked_count as i64 - count as i64);
+    }
+}
+
+fn cli_measure_keccak256_sign_verify<V: Bls12381Variant>(cmd: &Verify) {
+    let msg: Vec<u8> = vec![(cmd.msg_size % u8::MAX as usize) as u8; cmd.msg_size];
+    let sk = V::private_key_from_u64(1);
+    let pk = V::public_key(&sk);
+
+    println!("keccak256_sign");
+    let (sig, count) = perf!("total_instructions", V::sign(&sk, &keccak256_hash(&msg).0));
+    print_variant_calc_instructions::<V>(
+        calc::calc_keccak256_sign_instructions(cmd.msg_size),
+        count,
+    );
+
+    println!("keccak256_verify");
+    let (result, count) = perf!(
+        "total_instructions",
+        V::verify(&keccak256_hash(&msg).0, &pk, &sig)
+    );
+    assert!(result);
+    print_variant_calc_instructions::<V>(
+        calc::calc_keccak256_verify_instructions(cmd.msg_size),
+        count,
+    );
+}
+
+#[cfg(feature = "test-vectors")]
+fn cli_run_test_vectors(cmd: &TestVectors) {
+    let (args, operations) = match cmd {
+        TestVectors::Ietf(args) => {
+            println!("test_vectors ietf");
+            (args, TestVectorOperation::IETF.as_slice())
+        }
+        TestVectors::Eth(args) => {
+            println!("test_vectors eth");
+            (args, TestVectorOperation::ETH.as_slice())
+        }
+        TestVectors::HashToG2(args) => {
+            println!("test_vectors hash_to_g2");
+            let report = run_hash_to_g2_test_vectors(&args.file).unwrap();
+            println!(
+                "{:30}: passed {} failed {}",
+                "hash_to_g2",
+                report.passed,
+                report.failed.len()
+            );
+            for name in &report.failed {
+                println!("{:30}: {}", "failed", name);
+            }
+            if !report.failed.is_empty() {
+                std::process::exit(1);
+            }
+            return;
+        }
+    };
+
+    let mut failed = 0;
+    for operation in operations {
+        let vectors = load_test_vectors(&args.dir, *operation).unwrap();
+        let mut report = TestVectorReport::default();
+        for vector in vectors {
+            let output = if args.measure {
+                perf!(
+                    format!("{}/{}", operation.name(), vector.name),
+                    vector.run()
+                )
+                .0
+            } else {
+                vector.run()
+            };
+            if output == vector.expected {
+                report.passed += 1;
+            } else {
+                report.failed.push(vector.name);
+            }
+        }
+        println!(
+            "{:30}: passed {} failed {}",
+            operation.name(),
+            report.passed,
+            report.failed.len()
+        );
+        for name in &report.failed {
+            println!("{:30}: {}", "failed", name);
+        }
+        failed += report.failed.len();
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
 }
 
 pub fn run() {
@@ -198,32 +3604,270 @@ pub fn run() {
     *MEASURE_METHOD
         .get_or_init(|| Mutex::new(String::new()))
         .lock()
-        .unwrap() = cli.measure_method;
+        .unwrap() = cli.measure_method.clone();
+
+    ENCODING.get_or_init(|| cli.encoding);
+    if let Some(seed) = cli.seed {
+        set_test_data_seed(seed);
+    }
+    set_random_test_messages(cli.random_messages);
+    if let Some(dir) = &cli.test_data_cache_dir {
+        set_test_data_cache_dir(dir.clone());
+    }
+    #[cfg(feature = "fixtures")]
+    if let Some(path) = &cli.fixtures {
+        set_replayed_fixtures(TestFixtures::load(path).unwrap());
+    }
+
+    if matches!(cli.variant, Variant::MinSig) && cli.command.min_pk_only() {
+        input_error("the command supports min-pk variant only");
+    }
+    match cli.variant {
+        Variant::MinPk => run_variant::<MinPk>(&cli),
+        Variant::MinSig => run_variant::<MinSig>(&cli),
+    }
+}
+
+fn run_variant<V: Bls12381Variant>(cli: &Cli) {
+    #[cfg(feature = "fixtures")]
+    if cli.export_fixtures.is_some() {
+        record_fixtures::<V>();
+    }
+
+    run_command::<V>(&cli.command);
+
+    #[cfg(feature = "fixtures")]
+    if let (Some(path), Some(fixtures)) = (&cli.export_fixtures, recorded_fixtures()) {
+        fixtures.save(path).unwrap();
+        println!("{:30}: {}", "exported_fixture_sets", fixtures.sets.len());
+    }
+}
 
-    match &cli.command {
+fn run_command<V: Bls12381Variant>(command: &Commands) {
+    match command {
         Commands::Verify(args) => {
-            cli_measure_verify(args);
+            cli_measure_verify::<V>(args);
         }
         Commands::AggregateVerify(args) => {
-            cli_cmd_measure_aggregate_verify(false, args);
+            cli_cmd_measure_aggregate_verify::<V>(false, args);
         }
         Commands::AggregateVerifySizes(args) => {
-            cli_measure_aggregate_verify_sizes(false, args);
+            cli_measure_aggregate_verify_sizes::<V>(false, args);
         }
         Commands::AggregateVerifyThreaded(args) => {
-            cli_cmd_measure_aggregate_verify(true, args);
+            cli_cmd_measure_aggregate_verify::<V>(true, args);
+        }
+        Commands::AggregateVerifyBorrowed(args) => {
+            cli_measure_aggregate_verify_borrowed(args);
+        }
+        Commands::AggregateVerifyChunked(args) => {
+            cli_measure_aggregate_verify_chunked(args);
         }
         Commands::FastAggregateVerify(args) => {
-            cli_measure_fast_aggregate_verify(args);
+            cli_measure_fast_aggregate_verify::<V>(args);
+        }
+        Commands::VerifyFlags(args) => {
+            cli_measure_verify_flags(args);
+        }
+        Commands::VerifyAug(args) => {
+            cli_measure_verify_aug(args);
+        }
+        Commands::AggregateVerifyAug(args) => {
+            cli_measure_aggregate_verify_aug(args);
+        }
+        Commands::AggregateVerifyBasic(args) => {
+            cli_measure_aggregate_verify_basic(args);
+        }
+        Commands::MessagesDistinct(args) => {
+            cli_measure_messages_distinct(args);
+        }
+        Commands::PrecomputedVerify(args) => {
+            cli_measure_precomputed_verify(args);
+        }
+        Commands::DecodedVerify(args) => {
+            cli_measure_decoded_verify(args);
+        }
+        Commands::PairingPool(args) => {
+            cli_measure_pairing_pool(args);
+        }
+        Commands::HashToG2Batch(args) => {
+            cli_measure_hash_to_g2_batch(args);
+        }
+        Commands::Kzg(args) => {
+            cli_measure_kzg(args);
+        }
+        #[cfg(feature = "attestation-dump")]
+        Commands::VerifyAttestationDump(args) => {
+            cli_measure_verify_attestation_dump(args);
+        }
+        Commands::BatchVerify(args) => {
+            cli_measure_batch_verify::<V>(args);
+        }
+        Commands::BatchVerifyMsm(args) => {
+            cli_measure_batch_verify_msm::<V>(args);
+        }
+        Commands::VerifyMultipleAggregateSignatures(args) => {
+            cli_measure_verify_multiple_aggregate_signatures::<V>(args);
+        }
+        Commands::VerifyNegative(args) => {
+            cli_measure_verify_negative::<V>(args);
+        }
+        #[cfg(feature = "cross-check")]
+        Commands::CrossCheck(args) => {
+            cli_cross_check::<V>(args);
         }
         Commands::SignatureAggregate(args) => {
-            cli_measure_signature_aggregate(args);
+            cli_measure_signature_aggregate::<V>(args);
+        }
+        Commands::SignatureAggregateParallel(args) => {
+            cli_measure_signature_aggregate_parallel(args);
+        }
+        Commands::PublicKeyAggregate(args) => {
+            cli_measure_public_key_aggregate::<V>(args);
+        }
+        Commands::PublicKeyAggregateIncremental(args) => {
+            cli_measure_public_key_aggregate_incremental(args);
+        }
+        #[cfg(feature = "keystore")]
+        Commands::KeystoreDecrypt(args) => {
+            cli_measure_keystore_decrypt(args);
+        }
+        #[cfg(feature = "mnemonic")]
+        Commands::MnemonicDerive => {
+            cli_measure_mnemonic_derive();
+        }
+        Commands::CommitteeVerify(args) => {
+            cli_measure_committee_verify(args);
+        }
+        Commands::ThresholdSplit(args) => {
+            cli_measure_threshold_split(args);
+        }
+        Commands::ThresholdCombine(args) => {
+            cli_measure_threshold_combine(args);
+        }
+        Commands::DkgSimulate(args) => {
+            cli_measure_dkg_simulate(args);
+        }
+        Commands::PopRegistryPopulate(args) => {
+            cli_measure_pop_registry_populate(args);
+        }
+        #[cfg(feature = "mnemonic")]
+        Commands::Derive(args) => {
+            cli_derive::<V>(args);
         }
         Commands::HashToPoint(args) => {
             cli_measure_hash_to_point(args);
         }
+        Commands::HashToG2Phases(args) => {
+            cli_measure_hash_to_g2_phases(args);
+        }
         Commands::Keccak256(args) => {
             cli_measure_keccak256(args);
         }
+        Commands::Keccak256Chunked(args) => {
+            cli_measure_keccak256_chunked(args);
+        }
+        Commands::HashChain(args) => {
+            cli_measure_hash_chain(args);
+        }
+        Commands::Keccak256Parallel(args) => {
+            cli_measure_keccak256_parallel(args);
+        }
+        Commands::KeccakF(args) => {
+            cli_measure_keccak_f(args);
+        }
+        Commands::MerkleBuild(args) => {
+            cli_measure_merkle_build(args);
+        }
+        Commands::MerkleVerify(args) => {
+            cli_measure_merkle_verify(args);
+        }
+        Commands::Hmac(args) => {
+            cli_measure_hmac(args);
+        }
+        Commands::Sha256(args) => {
+            cli_measure_hash("sha256", |msg| sha256_hash(msg), args);
+        }
+        Commands::Sha3_256(args) => {
+            cli_measure_hash("sha3_256", |msg| sha3_256_hash(msg), args);
+        }
+        Commands::Blake2b256(args) => {
+            cli_measure_hash("blake2b_256", |msg| blake2b_256_hash(msg), args);
+        }
+        Commands::Keccak256SignVerify(args) => {
+            cli_measure_keccak256_sign_verify::<V>(args);
+        }
+        Commands::Sign(args) => {
+            cli_measure_sign::<V>(args);
+        }
+        Commands::SignBatch(args) => {
+            cli_sign_batch::<V>(args);
+        }
+        Commands::VerifySig(args) => {
+            cli_verify_sig(args);
+        }
+        Commands::Aggregate(cmd) => {
+            cli_aggregate(cmd);
+        }
+        Commands::Keygen(args) => {
+            cli_keygen::<V>(args);
+        }
+        Commands::Deposit(args) => {
+            cli_measure_deposit(args);
+        }
+        Commands::PopProve => {
+            cli_measure_pop_prove::<V>();
+        }
+        Commands::PopVerify(args) => match (&args.pk, &args.proof) {
+            (Some(pk), Some(proof)) => cli_pop_verify::<V>(pk, proof),
+            _ => cli_measure_pop_verify::<V>(),
+        },
+        Commands::Pop(args) => {
+            cli_pop::<V>(args);
+        }
+        Commands::SignatureValidate(args) => {
+            cli_measure_signature_validate::<V>(args);
+        }
+        Commands::PublicKeyValidate(args) => {
+            cli_measure_public_key_validate::<V>(args);
+        }
+        Commands::Serialization(args) => {
+            cli_measure_serialization::<V>(args);
+        }
+        Commands::G2Decompress(args) => {
+            cli_measure_g2_decompress(args);
+        }
+        Commands::Pairing(args) => {
+            cli_measure_pairing(args);
+        }
+        Commands::Gt(args) => {
+            cli_measure_gt(args);
+        }
+        Commands::PointOps(args) => {
+            cli_measure_point_ops(args);
+        }
+        Commands::Sensitivity(args) => {
+            cli_measure_sensitivity::<V>(args);
+        }
+        Commands::Ed25519Verify(args) => {
+            cli_measure_ed25519_verify(args);
+        }
+        Commands::Secp256k1Verify(args) => {
+            cli_measure_secp256k1_verify(args);
+        }
+        Commands::CompareVerify(args) => {
+            cli_measure_compare_verify::<V>(args);
+        }
+        Commands::Precompile(cmd) => {
+            cli_measure_precompile(cmd);
+        }
+        #[cfg(feature = "test-vectors")]
+        Commands::TestVectors(cmd) => {
+            cli_run_test_vectors(cmd);
+        }
+        #[cfg(feature = "serve")]
+        Commands::Serve(args) => {
+            crate::server::serve(&args.listen).unwrap_or_else(|err| input_error(err));
+        }
     }
 }
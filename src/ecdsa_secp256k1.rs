@@ -0,0 +1,41 @@
+use crate::keccak256_hash;
+use once_cell::sync::Lazy;
+use secp256k1::{ecdsa::Signature, All, Message, PublicKey, Secp256k1, SecretKey};
+
+/// Context is expensive to create, so it is shared by all the calls
+static SECP256K1_CTX: Lazy<Secp256k1<All>> = Lazy::new(Secp256k1::new);
+
+/// Returns public key, message and ECDSA signature of its keccak256 hash
+pub fn get_ecdsa_secp256k1_verify_test_data(msg_size: usize) -> (PublicKey, Vec<u8>, Signature) {
+    let sk = SecretKey::from_slice(&[1u8; secp256k1::constants::SECRET_KEY_SIZE]).unwrap();
+    let msg: Vec<u8> = vec![(msg_size % u8::MAX as usize) as u8; msg_size];
+    let digest = Message::from_digest(keccak256_hash(&msg).0);
+    let sig = SECP256K1_CTX.sign_ecdsa(&digest, &sk);
+
+    (PublicKey::from_secret_key(&SECP256K1_CTX, &sk), msg, sig)
+}
+
+/// Performs ECDSA secp256k1 signature verification of the keccak256 hash of the message
+pub fn verify_ecdsa_secp256k1(
+    message: &[u8],
+    public_key: &PublicKey,
+    signature: &Signature,
+) -> bool {
+    let digest = Message::from_digest(keccak256_hash(message).0);
+    SECP256K1_CTX
+        .verify_ecdsa(&digest, signature, public_key)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let (pk, msg, sig) = get_ecdsa_secp256k1_verify_test_data(100);
+
+        assert!(verify_ecdsa_secp256k1(&msg, &pk, &sig));
+        assert!(!verify_ecdsa_secp256k1(&msg[1..], &pk, &sig));
+    }
+}
@@ -0,0 +1,28 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+/// Returns public key, message and its Ed25519 signature
+pub fn get_ed25519_verify_test_data(msg_size: usize) -> (VerifyingKey, Vec<u8>, Signature) {
+    let sk = SigningKey::from_bytes(&[1u8; ed25519_dalek::SECRET_KEY_LENGTH]);
+    let msg: Vec<u8> = vec![(msg_size % u8::MAX as usize) as u8; msg_size];
+    let sig = sk.sign(&msg);
+
+    (sk.verifying_key(), msg, sig)
+}
+
+/// Performs Ed25519 signature verification (SHA-512 of the message is included)
+pub fn verify_ed25519(message: &[u8], public_key: &VerifyingKey, signature: &Signature) -> bool {
+    public_key.verify(message, signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let (pk, msg, sig) = get_ed25519_verify_test_data(100);
+
+        assert!(verify_ed25519(&msg, &pk, &sig));
+        assert!(!verify_ed25519(&msg[1..], &pk, &sig));
+    }
+}
@@ -1,4 +1,17 @@
-use sha3::{Digest, Keccak256};
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256, Keccak512};
+use std::fmt;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+/// Size of the chunks data is read in, when hashing incrementally
+pub const HASH_READ_CHUNK_SIZE: usize = 1 << 20;
+
+/// Bytes absorbed per keccak-f[1600] permutation by keccak256 (1600 - 2 * 256 bits)
+pub const KECCAK256_RATE: usize = 136;
+
+/// Bytes absorbed per keccak-f[1600] permutation by keccak512 (1600 - 2 * 512 bits)
+pub const KECCAK512_RATE: usize = 72;
 
 /// Represents a 32-byte hash digest.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -56,26 +69,222 @@ impl AsRef<[u8]> for Hash {
     }
 }
 
+//======
+// text
+//======
+
+/// Represents an error when parsing hash from hex string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHashError {
+    InvalidHex(String),
+    InvalidLength(usize),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseHashError {}
+
+impl fmt::Display for ParseHashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for Hash {
+    type Err = ParseHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| ParseHashError::InvalidHex(s.to_owned()))?;
+        bytes
+            .try_into()
+            .map(Hash)
+            .map_err(|bytes: Vec<u8>| ParseHashError::InvalidLength(bytes.len()))
+    }
+}
+
+impl fmt::LowerHex for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:x}", self)
+    }
+}
+
+impl fmt::Debug for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:x}", self)
+    }
+}
+
+/// Incremental hasher (sha3 streaming interface), so that chunked payloads
+/// can be hashed without concatenating them
+#[derive(Clone, Default)]
+pub struct Keccak256Hasher(Keccak256);
+
+impl Keccak256Hasher {
+    pub fn new() -> Self {
+        Self(Keccak256::new())
+    }
+
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        Digest::update(&mut self.0, data);
+    }
+
+    pub fn finalize(self) -> Hash {
+        Hash(self.0.finalize().into())
+    }
+}
+
 pub fn keccak256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
-    let mut hasher = Keccak256::new();
+    let mut hasher = Keccak256Hasher::new();
     hasher.update(data);
-    let hash = hasher.finalize();
-    Hash(hash.into())
+    hasher.finalize()
+}
+
+/// Hashes concatenation of given chunks
+pub fn keccak256_hash_iter<T: AsRef<[u8]>, I: IntoIterator<Item = T>>(chunks: I) -> Hash {
+    let mut hasher = Keccak256Hasher::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finalize()
+}
+
+/// Hash chain: keccak256 applied n times, each time to the previous digest
+pub fn keccak256_hash_chain(start: Hash, n: usize) -> Hash {
+    (0..n).fold(start, |hash, _| keccak256_hash(hash))
+}
+
+/// Tree hash: chunks of given size are hashed in parallel, then digests of the chunks
+/// are hashed together (so result differs from keccak256_hash of the data)
+pub fn keccak256_hash_parallel(data: &[u8], chunk_size: usize) -> Hash {
+    let digests: Vec<Hash> = data.par_chunks(chunk_size).map(keccak256_hash).collect();
+    keccak256_hash_iter(digests)
+}
+
+/// Hashes data read in chunks, eg. message too big to fit in memory
+pub fn keccak256_hash_reader<R: Read>(mut reader: R) -> io::Result<Hash> {
+    let mut hasher = Keccak256Hasher::new();
+    let mut buf = vec![0u8; HASH_READ_CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buf)? {
+            0 => break,
+            len => hasher.update(&buf[..len]),
+        }
+    }
+    Ok(hasher.finalize())
+}
+
+/// Raw keccak-f[1600] permutation of the 25-lane state
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    keccak::f1600(state);
+}
+
+/// Number of permutations needed to hash message of given size with given rate,
+/// padding adds at least one byte, so full last block takes an extra permutation
+pub fn keccak_permutation_count(msg_size: usize, rate: usize) -> usize {
+    msg_size / rate + 1
+}
+
+pub fn keccak512_hash<T: AsRef<[u8]>>(data: T) -> [u8; 64] {
+    Keccak512::digest(data).into()
+}
+
+/// Hashes data read in chunks with given 32-byte digest (eg. SHA-256)
+pub(crate) fn digest_reader<D: Digest, R: Read>(mut reader: R) -> io::Result<Hash> {
+    let mut hasher = D::new();
+    let mut buf = vec![0u8; HASH_READ_CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buf)? {
+            0 => break,
+            len => hasher.update(&buf[..len]),
+        }
+    }
+    Ok(Hash(hasher.finalize().as_slice().try_into().unwrap()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sbor::rust::str::FromStr;
 
     #[test]
     fn test_keccak256_hash() {
         let data = "Hello Radix";
         let hash = keccak256_hash(data);
         assert_eq!(
-            hash,
-            Hash::from_str("415942230ddb029416a4612818536de230d827cbac9646a0b26d9855a4c45587")
+            hash.0.to_vec(),
+            hex::decode("415942230ddb029416a4612818536de230d827cbac9646a0b26d9855a4c45587")
                 .unwrap()
         );
+        assert_eq!(keccak256_hash_reader(data.as_bytes()).unwrap(), hash);
+        assert_eq!(keccak256_hash_iter(["Hello", " ", "Radix"]), hash);
+        assert_eq!(keccak256_hash_iter(data.as_bytes().chunks(3)), hash);
+
+        let mut hasher = Keccak256Hasher::new();
+        hasher.update("Hello ");
+        hasher.update(b"Radix");
+        assert_eq!(hasher.finalize(), hash);
+    }
+
+    #[test]
+    fn test_keccak256_hash_chain() {
+        let start = keccak256_hash("seed");
+        assert_eq!(keccak256_hash_chain(start, 0), start);
+        assert_eq!(
+            keccak256_hash_chain(start, 2),
+            keccak256_hash(keccak256_hash(start))
+        );
+    }
+
+    #[test]
+    fn test_keccak256_hash_parallel() {
+        let data = vec![5u8; 1000];
+        let digests: Vec<Hash> = data.chunks(300).map(keccak256_hash).collect();
+        assert_eq!(digests.len(), 4);
+        assert_eq!(
+            keccak256_hash_parallel(&data, 300),
+            keccak256_hash_iter(digests)
+        );
+    }
+
+    #[test]
+    fn test_hash_text() {
+        let text = "415942230ddb029416a4612818536de230d827cbac9646a0b26d9855a4c45587";
+        let hash = Hash::from_str(text).unwrap();
+        assert_eq!(hash.to_string(), text);
+        assert_eq!(format!("{:x}", hash), text);
+        assert_eq!(format!("{:#x}", hash), format!("0x{}", text));
+
+        assert_eq!(
+            Hash::from_str("zz"),
+            Err(ParseHashError::InvalidHex("zz".to_string()))
+        );
+        assert_eq!(
+            Hash::from_str("00ff"),
+            Err(ParseHashError::InvalidLength(2))
+        );
+    }
+
+    #[test]
+    fn test_keccak_f1600() {
+        let mut state = [0u64; 25];
+        keccak_f1600(&mut state);
+        assert_eq!(state[0], 0xF1258F7940E1DDE7);
+
+        assert_eq!(
+            hex::encode(keccak512_hash("")),
+            "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304\
+             c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e"
+        );
+
+        assert_eq!(keccak_permutation_count(0, KECCAK256_RATE), 1);
+        assert_eq!(keccak_permutation_count(135, KECCAK256_RATE), 1);
+        assert_eq!(keccak_permutation_count(136, KECCAK256_RATE), 2);
     }
 }
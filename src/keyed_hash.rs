@@ -0,0 +1,50 @@
+use crate::keccak256::Hash;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sha3::Keccak256;
+
+/// HMAC (RFC 2104) with keccak256, ie. keccak256((K ^ opad) || keccak256((K ^ ipad) || data))
+/// with 136-byte blocks
+pub fn hmac_keccak256<T: AsRef<[u8]>>(key: &[u8], data: T) -> Hash {
+    let mut mac = Hmac::<Keccak256>::new_from_slice(key).unwrap();
+    mac.update(data.as_ref());
+    Hash(mac.finalize().into_bytes().into())
+}
+
+/// HMAC (RFC 2104) with SHA-256 (64-byte blocks)
+pub fn hmac_sha256<T: AsRef<[u8]>>(key: &[u8], data: T) -> Hash {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(data.as_ref());
+    Hash(mac.finalize().into_bytes().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keccak256::{keccak256_hash_iter, KECCAK256_RATE};
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", "what do ya want for nothing?").0),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_hmac_keccak256() {
+        let key = b"key";
+        let data = "The quick brown fox jumps over the lazy dog";
+
+        let mut padded_key = [0u8; KECCAK256_RATE];
+        padded_key[..key.len()].copy_from_slice(key);
+        let ipad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x36).collect();
+        let opad: Vec<u8> = padded_key.iter().map(|b| b ^ 0x5c).collect();
+        let inner = keccak256_hash_iter([ipad.as_slice(), data.as_bytes()]);
+        let outer = keccak256_hash_iter([opad.as_slice(), inner.0.as_slice()]);
+
+        assert_eq!(hmac_keccak256(key, data), outer);
+        assert_ne!(hmac_keccak256(b"other key", data), outer);
+    }
+}
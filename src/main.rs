@@ -1,12 +1,29 @@
+mod blake2b;
 mod bls12381;
 mod cli;
 #[macro_use]
 mod utils;
 mod calc;
+mod ecdsa_secp256k1;
+mod ed25519;
 mod keccak256;
+mod keyed_hash;
+mod merkle;
+mod sensitivity;
+#[cfg(feature = "serve")]
+mod server;
+mod sha256;
+mod sha3_256;
 
+pub use blake2b::*;
 pub use bls12381::*;
+pub use ecdsa_secp256k1::*;
+pub use ed25519::*;
 pub use keccak256::*;
+pub use keyed_hash::*;
+pub use merkle::*;
+pub use sha256::*;
+pub use sha3_256::*;
 pub use utils::*;
 
 fn main() {
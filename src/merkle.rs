@@ -0,0 +1,204 @@
+use crate::keccak256::{keccak256_hash, Hash, Keccak256Hasher};
+use std::fmt;
+
+/// Merkle tree over keccak256. Leaves are hashed, each node is the hash of the
+/// concatenation of its (up to arity) children. Last node of a level may have fewer
+/// children, which are hashed as they are (no padding).
+pub struct MerkleTree {
+    /// Hashes of the nodes, from the leaves up to the root
+    levels: Vec<Vec<Hash>>,
+    arity: usize,
+}
+
+impl MerkleTree {
+    pub fn build<T: AsRef<[u8]>>(leaves: &[T], arity: usize) -> Result<Self, MerkleError> {
+        if arity < 2 {
+            return Err(MerkleError::InvalidArity(arity));
+        }
+        if leaves.is_empty() {
+            return Err(MerkleError::NoLeaves);
+        }
+
+        let mut levels = vec![leaves.iter().map(keccak256_hash).collect::<Vec<Hash>>()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(arity)
+                .map(|children| {
+                    let mut hasher = Keccak256Hasher::new();
+                    for child in children {
+                        hasher.update(child);
+                    }
+                    hasher.finalize()
+                })
+                .collect();
+            levels.push(level);
+        }
+        Ok(Self { levels, arity })
+    }
+
+    pub fn root(&self) -> Hash {
+        self.levels.last().unwrap()[0]
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Number of levels above the leaves
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Hashes of the nodes of given level (0 for the leaves)
+    pub fn level(&self, level: usize) -> Option<&[Hash]> {
+        self.levels.get(level).map(Vec::as_slice)
+    }
+
+    /// Proof of inclusion of the leaf of given index
+    pub fn prove(&self, leaf_index: usize) -> Option<MerkleProof> {
+        if leaf_index >= self.leaf_count() {
+            return None;
+        }
+        let mut index = leaf_index;
+        let steps = self.levels[..self.depth()]
+            .iter()
+            .map(|level| {
+                let first = index - index % self.arity;
+                let last = (first + self.arity).min(level.len());
+                let step = MerkleProofStep {
+                    position: index - first,
+                    siblings: [&level[first..index], &level[index + 1..last]].concat(),
+                };
+                index /= self.arity;
+                step
+            })
+            .collect();
+        Some(MerkleProof { leaf_index, steps })
+    }
+}
+
+/// Siblings of the node at one level, the node is hashed at given position among them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProofStep {
+    pub position: usize,
+    pub siblings: Vec<Hash>,
+}
+
+/// Proof of inclusion of a leaf, steps go from the leaf level up to the root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub steps: Vec<MerkleProofStep>,
+}
+
+impl MerkleProof {
+    pub fn verify(&self, root: &Hash, leaf: &[u8]) -> bool {
+        let mut hash = keccak256_hash(leaf);
+        for step in &self.steps {
+            if step.position > step.siblings.len() {
+                return false;
+            }
+            let mut hasher = Keccak256Hasher::new();
+            for sibling in &step.siblings[..step.position] {
+                hasher.update(sibling);
+            }
+            hasher.update(hash);
+            for sibling in &step.siblings[step.position..] {
+                hasher.update(sibling);
+            }
+            hash = hasher.finalize();
+        }
+        hash == *root
+    }
+}
+
+/// Number of nodes above the leaves (each costing a hash of its children)
+pub fn merkle_internal_node_count(leaf_cnt: usize, arity: usize) -> usize {
+    let mut cnt = 0;
+    let mut level = leaf_cnt;
+    while level > 1 {
+        level = level.div_ceil(arity);
+        cnt += level;
+    }
+    cnt
+}
+
+/// Represents an error when building Merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    // Arity less than 2
+    InvalidArity(usize),
+    NoLeaves,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for MerkleError {}
+
+impl fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merkle_tree_build() {
+        let leaves: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 10]).collect();
+        let hashes: Vec<Hash> = leaves.iter().map(keccak256_hash).collect();
+        let hash_of = |children: &[Hash]| {
+            let bytes: Vec<u8> = children.iter().flat_map(|hash| hash.0).collect();
+            keccak256_hash(bytes)
+        };
+
+        let tree = MerkleTree::build(&leaves, 2).unwrap();
+        let left = hash_of(&[
+            hash_of(&[hashes[0], hashes[1]]),
+            hash_of(&[hashes[2], hashes[3]]),
+        ]);
+        let right = hash_of(&[hash_of(&[hashes[4]])]);
+        assert_eq!(tree.root(), hash_of(&[left, right]));
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(tree.leaf_count(), 5);
+        assert_eq!(merkle_internal_node_count(5, 2), 3 + 2 + 1);
+
+        let tree = MerkleTree::build(&leaves, 4).unwrap();
+        let root = hash_of(&[hash_of(&hashes[..4]), hash_of(&hashes[4..])]);
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(merkle_internal_node_count(5, 4), 2 + 1);
+
+        // Single leaf is the root
+        let tree = MerkleTree::build(&leaves[..1], 2).unwrap();
+        assert_eq!(tree.root(), hashes[0]);
+        assert_eq!(merkle_internal_node_count(1, 2), 0);
+
+        for arity in [2, 3, 4] {
+            let tree = MerkleTree::build(&leaves, arity).unwrap();
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.prove(i).unwrap();
+                assert_eq!(proof.steps.len(), tree.depth());
+                assert!(proof.verify(&tree.root(), leaf));
+                assert!(!proof.verify(&tree.root(), &leaves[(i + 1) % leaves.len()]));
+            }
+            assert!(tree.prove(leaves.len()).is_none());
+        }
+
+        assert_eq!(
+            MerkleTree::build(&leaves, 1).err(),
+            Some(MerkleError::InvalidArity(1))
+        );
+        assert_eq!(
+            MerkleTree::build::<Vec<u8>>(&[], 2).err(),
+            Some(MerkleError::NoLeaves)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+/// Single point of a sweep: value of the swept parameter (bytes, messages, signers...)
+/// together with measured and (if model exists) calculated instructions.
+pub struct SweepPoint {
+    pub x: u64,
+    pub measured: u64,
+    pub calculated: Option<u64>,
+}
+
+/// Least-squares slope of instructions over the swept parameter,
+/// ie. marginal cost of one extra unit of the parameter.
+/// Returns None if there are not enough distinct points to fit a line.
+pub fn marginal_cost(points: &[(u64, u64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| *x as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| *y as f64).sum::<f64>() / n;
+
+    let mut num = 0.0;
+    let mut den = 0.0;
+    for (x, y) in points {
+        let dx = *x as f64 - mean_x;
+        num += dx * (*y as f64 - mean_y);
+        den += dx * dx;
+    }
+
+    if den == 0.0 {
+        None
+    } else {
+        Some(num / den)
+    }
+}
+
+fn format_marginal_cost(cost: Option<f64>) -> String {
+    match cost {
+        Some(cost) => format!("{:.2}", cost),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Prints marginal cost per extra `unit` of given operation,
+/// numerically (from measured sweep) and analytically (from calc model).
+/// Calc models are skipped when wall time is measured.
+pub fn report_marginal_cost(op: &str, unit: &str, points: &[SweepPoint]) {
+    let measured: Vec<(u64, u64)> = points.iter().map(|p| (p.x, p.measured)).collect();
+    let calculated: Option<Vec<(u64, u64)>> = match crate::measures_time() {
+        true => None,
+        false => points
+            .iter()
+            .map(|p| p.calculated.map(|c| (p.x, c)))
+            .collect(),
+    };
+
+    let measured_cost = marginal_cost(&measured);
+    let calculated_cost = calculated.and_then(|c| marginal_cost(&c));
+
+    println!(
+        "{:30}: measured: {} calc: {}",
+        format!("{} per {}", op, unit),
+        format_marginal_cost(measured_cost),
+        format_marginal_cost(calculated_cost)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marginal_cost_of_linear_points() {
+        let points = vec![(100, 1100), (200, 2100), (400, 4100)];
+        assert_eq!(marginal_cost(&points), Some(10.0));
+    }
+
+    #[test]
+    fn marginal_cost_needs_distinct_points() {
+        assert_eq!(marginal_cost(&[]), None);
+        assert_eq!(marginal_cost(&[(1, 10)]), None);
+        assert_eq!(marginal_cost(&[(1, 10), (1, 20)]), None);
+    }
+}
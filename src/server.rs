@@ -0,0 +1,358 @@
+use crate::bls12381::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::process::Command;
+use tiny_http::{Header, Method, Response, Server};
+
+/// Measurement request, the command line of this tool is built from its fields,
+/// eg. {"command": "verify", "method": "count", "options": {"msg-size": "1024"}}
+#[derive(Debug, Deserialize)]
+pub struct MeasureRequest {
+    /// One of MEASURE_COMMANDS
+    pub command: String,
+    /// Measurement method (perf, count, time, none), the tool's default if not given
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Signature variant (min-pk, min-sig)
+    #[serde(default)]
+    pub variant: Option<String>,
+    /// Seed of the test data
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Options of the command by long name, one of MEASURE_OPTIONS
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+/// Commands, which can be measured remotely. None of them reads or writes files
+/// given by the options below.
+pub const MEASURE_COMMANDS: &[&str] = &[
+    "verify",
+    "aggregate-verify",
+    "aggregate-verify-threaded",
+    "fast-aggregate-verify",
+    "verify-flags",
+    "verify-aug",
+    "aggregate-verify-aug",
+    "aggregate-verify-basic",
+    "batch-verify",
+    "verify-multiple-aggregate-signatures",
+    "signature-aggregate",
+    "public-key-aggregate",
+    "hash-to-point",
+    "pairing",
+    "point-ops",
+    "keccak256",
+    "sha256",
+    "sha3-256",
+    "blake2b-256",
+    "hmac",
+];
+
+/// Options of the measured commands, none of them takes a path
+pub const MEASURE_OPTIONS: &[&str] = &[
+    "msg-size",
+    "msg-sizes",
+    "msg-cnt",
+    "signers-per-msg",
+    "batch-sizes",
+    "sig-cnt",
+    "pair-cnt",
+    "cnt",
+    "group",
+];
+
+/// Upper bound of numbers given in the option values and of the length of their lists,
+/// so that a single request cannot keep the server measuring for hours
+const MAX_OPTION_NUMBER: u64 = 1 << 20;
+const MAX_OPTION_LIST_LENGTH: usize = 16;
+
+/// Requests of larger bodies are rejected without reading them whole
+const MAX_REQUEST_BODY_SIZE: u64 = 64 * 1024;
+
+const MEASURE_METHODS: &[&str] = &["perf", "count", "time", "none"];
+const MEASURE_VARIANTS: &[&str] = &["min-pk", "min-sig"];
+
+/// Output of the measurement, "name: value" lines (eg. instruction counts) are also
+/// returned as separate values
+#[derive(Debug, Serialize)]
+pub struct MeasureResponse {
+    pub success: bool,
+    pub output: Vec<String>,
+    pub values: Vec<MeasuredValue>,
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MeasuredValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// Verification request of min-pk signature, message is hex encoded
+#[derive(Debug, Deserialize)]
+pub struct VerifyRequest {
+    pub public_key: Bls12381G1PublicKey,
+    pub message: String,
+    pub signature: Bls12381G2Signature,
+}
+
+/// Machine details, so that results collected from the fleet can be told apart
+#[derive(Debug, Serialize)]
+pub struct InfoResponse {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+}
+
+/// Serves the HTTP/JSON API:
+///   GET /info - machine details
+///   POST /measure - runs allowed measurement command of this tool (MeasureRequest)
+///   POST /verify - verifies signature (VerifyRequest)
+/// Requests are handled one at a time, so that measurements do not disturb each other.
+/// Returns only if the server cannot listen on the address.
+pub fn serve(addr: &str) -> Result<(), String> {
+    let server = Server::http(addr).map_err(|err| format!("cannot listen on {}: {}", addr, err))?;
+    println!("{:30}: {}", "listening", addr);
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let (status, json) = match request
+            .as_reader()
+            .take(MAX_REQUEST_BODY_SIZE + 1)
+            .read_to_string(&mut body)
+        {
+            Ok(len) if len as u64 > MAX_REQUEST_BODY_SIZE => error_response(
+                413,
+                format!("request body exceeds {} bytes", MAX_REQUEST_BODY_SIZE),
+            ),
+            Ok(_) => handle(request.method(), request.url(), &body),
+            Err(err) => error_response(400, err.to_string()),
+        };
+        println!("{:30}: {} {}", request.url(), request.method(), status);
+
+        let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+        let response = Response::from_string(json)
+            .with_status_code(status)
+            .with_header(header);
+        if let Err(err) = request.respond(response) {
+            eprintln!("failed to respond: {}", err);
+        }
+    }
+    Ok(())
+}
+
+/// Returns status code and JSON body of the response
+fn handle(method: &Method, url: &str, body: &str) -> (u16, String) {
+    match (method, url) {
+        (Method::Get, "/info") => json_response(&InfoResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }),
+        (Method::Post, "/measure") => match serde_json::from_str::<MeasureRequest>(body) {
+            Ok(request) => match measure(&request) {
+                Ok(response) => json_response(&response),
+                Err(err) => error_response(400, err),
+            },
+            Err(err) => error_response(400, err.to_string()),
+        },
+        (Method::Post, "/verify") => match serde_json::from_str::<VerifyRequest>(body) {
+            Ok(request) => match hex::decode(request.message.trim_start_matches("0x")) {
+                Ok(message) => json_response(&serde_json::json!({
+                    "valid": verify_bls12381_v1(&message, &request.public_key, &request.signature)
+                })),
+                Err(_) => error_response(400, format!("invalid message {}", request.message)),
+            },
+            Err(err) => error_response(400, err.to_string()),
+        },
+        _ => error_response(404, format!("{} {} not found", method, url)),
+    }
+}
+
+/// Builds command line arguments of the measurement, rejecting commands and options,
+/// which are not allowed
+fn measure_args(request: &MeasureRequest) -> Result<Vec<String>, String> {
+    let allowed = |list: &[&str], value: &str| list.contains(&value);
+    if !allowed(MEASURE_COMMANDS, &request.command) {
+        return Err(format!("command {} cannot be measured", request.command));
+    }
+
+    let mut args = vec![];
+    if let Some(method) = &request.method {
+        if !allowed(MEASURE_METHODS, method) {
+            return Err(format!("invalid method {}", method));
+        }
+        args.push(format!("--measure-method={}", method));
+    }
+    if let Some(variant) = &request.variant {
+        if !allowed(MEASURE_VARIANTS, variant) {
+            return Err(format!("invalid variant {}", variant));
+        }
+        args.push(format!("--variant={}", variant));
+    }
+    if let Some(seed) = request.seed {
+        args.push(format!("--seed={}", seed));
+    }
+    args.push(request.command.clone());
+    for (name, value) in &request.options {
+        if !allowed(MEASURE_OPTIONS, name) {
+            return Err(format!("option {} is not allowed", name));
+        }
+        // Numbers, lists of them and enum values only
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == ',' || c == '-')
+        {
+            return Err(format!("invalid value {} of option {}", value, name));
+        }
+        let items: Vec<&str> = value.split(',').collect();
+        if items.len() > MAX_OPTION_LIST_LENGTH
+            || items.iter().any(|item| {
+                item.starts_with(|c: char| c.is_ascii_digit())
+                    && item
+                        .parse::<u64>()
+                        .map_or(true, |number| number > MAX_OPTION_NUMBER)
+            })
+        {
+            return Err(format!("value {} of option {} is too large", value, name));
+        }
+        // Joined with the name, so that the value cannot be taken for another option
+        args.push(format!("--{}={}", name, value));
+    }
+    Ok(args)
+}
+
+// Command runs in a separate process, so that its measurement is not affected by the server
+fn measure(request: &MeasureRequest) -> Result<MeasureResponse, String> {
+    let args = measure_args(request)?;
+    let exe = std::env::current_exe().map_err(|err| err.to_string())?;
+    let output = Command::new(exe)
+        .args(&args)
+        .output()
+        .map_err(|err| err.to_string())?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(MeasureResponse {
+        success: output.status.success(),
+        output: stdout.lines().map(str::to_string).collect(),
+        values: parse_output_values(&stdout),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    })
+}
+
+/// Splits "name: value" lines of the output (as printed by perf! and the commands)
+fn parse_output_values(output: &str) -> Vec<MeasuredValue> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(name, value)| MeasuredValue {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+        .collect()
+}
+
+fn json_response<T: Serialize>(value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(json) => (200, json),
+        Err(err) => error_response(500, err.to_string()),
+    }
+}
+
+fn error_response(status: u16, error: String) -> (u16, String) {
+    (status, serde_json::json!({ "error": error }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_handles_verify_and_info() {
+        let (_sks, pks, msgs, sigs) = get_aggregate_verify_test_data(2, 2, 32);
+        let body = |msg: &[u8]| {
+            serde_json::json!({
+                "public_key": pks[0],
+                "message": hex::encode(msg),
+                "signature": sigs[0],
+            })
+            .to_string()
+        };
+
+        assert_eq!(
+            handle(&Method::Post, "/verify", &body(&msgs[0])),
+            (200, r#"{"valid":true}"#.to_string())
+        );
+        assert_eq!(
+            handle(&Method::Post, "/verify", &body(&msgs[1])),
+            (200, r#"{"valid":false}"#.to_string())
+        );
+        assert_eq!(handle(&Method::Post, "/verify", "{}").0, 400);
+
+        let (status, json) = handle(&Method::Get, "/info", "");
+        assert_eq!(status, 200);
+        assert!(json.contains(env!("CARGO_PKG_VERSION")));
+
+        assert_eq!(handle(&Method::Get, "/unknown", "").0, 404);
+        assert_eq!(
+            handle(&Method::Post, "/measure", r#"{"command": "serve"}"#).0,
+            400
+        );
+    }
+
+    #[test]
+    fn measure_args_allowlisted() {
+        let request = |json: &str| serde_json::from_str::<MeasureRequest>(json).unwrap();
+
+        assert_eq!(
+            measure_args(&request(
+                r#"{"command": "verify", "method": "count", "variant": "min-sig", "seed": 7,
+                    "options": {"msg-size": "1024"}}"#
+            )),
+            Ok(vec![
+                "--measure-method=count".to_string(),
+                "--variant=min-sig".to_string(),
+                "--seed=7".to_string(),
+                "verify".to_string(),
+                "--msg-size=1024".to_string(),
+            ])
+        );
+        for json in [
+            r#"{"command": "sign"}"#,
+            r#"{"command": "keygen"}"#,
+            r#"{"command": "verify", "method": "--export-fixtures=x"}"#,
+            r#"{"command": "verify", "variant": "min-pk --keystore"}"#,
+            r#"{"command": "verify", "options": {"msg-file": "/etc/passwd"}}"#,
+            r#"{"command": "verify", "options": {"test-data-cache-dir": "/tmp"}}"#,
+            r#"{"command": "verify", "options": {"msg-size": "1 --keystore=k"}}"#,
+            r#"{"command": "hash-to-point", "options": {"msg": "@/etc/passwd"}}"#,
+            r#"{"command": "verify", "options": {"msg-size": "4294967296"}}"#,
+            r#"{"command": "verify", "options": {"msg-size": "99999999999999999999"}}"#,
+            r#"{"command": "batch-verify", "options": {"batch-sizes": "1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17"}}"#,
+        ] {
+            assert!(measure_args(&request(json)).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn output_values_parsed() {
+        let output = "verify\ntotal_instructions            : 123\ncalc_instructions             : 120 diff: -3\n";
+        assert_eq!(
+            parse_output_values(output),
+            vec![
+                MeasuredValue {
+                    name: "total_instructions".to_string(),
+                    value: "123".to_string(),
+                },
+                MeasuredValue {
+                    name: "calc_instructions".to_string(),
+                    value: "120 diff: -3".to_string(),
+                },
+            ]
+        );
+    }
+}
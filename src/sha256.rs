@@ -0,0 +1,27 @@
+use crate::keccak256::{digest_reader, Hash};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+pub fn sha256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+    Hash(Sha256::digest(data).into())
+}
+
+/// Hashes data read in chunks, eg. message too big to fit in memory
+pub fn sha256_hash_reader<R: Read>(reader: R) -> io::Result<Hash> {
+    digest_reader::<Sha256, R>(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hash() {
+        let hash = sha256_hash("abc");
+        assert_eq!(
+            hex::encode(hash.0),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(sha256_hash_reader("abc".as_bytes()).unwrap() == hash);
+    }
+}
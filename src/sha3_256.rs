@@ -0,0 +1,28 @@
+use crate::keccak256::{digest_reader, Hash};
+use sha3::{Digest, Sha3_256};
+use std::io::{self, Read};
+
+/// SHA3-256 (FIPS 202), differs from keccak256 in the padding only
+pub fn sha3_256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+    Hash(Sha3_256::digest(data).into())
+}
+
+/// Hashes data read in chunks, eg. message too big to fit in memory
+pub fn sha3_256_hash_reader<R: Read>(reader: R) -> io::Result<Hash> {
+    digest_reader::<Sha3_256, R>(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha3_256_hash() {
+        let hash = sha3_256_hash("abc");
+        assert_eq!(
+            hex::encode(hash.0),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert!(sha3_256_hash_reader("abc".as_bytes()).unwrap() == hash);
+    }
+}
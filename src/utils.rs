@@ -63,8 +63,59 @@ macro_rules! perf {
                 println!("{:30}: {:?}", $desc, counts);
                 (result, counts)
             }
-            "none" => ($closure, 0),
+            "time" => {
+                let start = std::time::Instant::now();
+                let result = $closure;
+                let nanos = start.elapsed().as_nanos() as u64;
+                println!("{:30}: {} ns", $desc, nanos);
+                (result, nanos)
+            }
+            // Method is not set when not run from the CLI (eg. in tests)
+            "none" | "" => ($closure, 0),
             _ => panic!("measure method {:?} not supported", method),
         }
     }};
 }
+
+/// Whether perf! returns wall time (ns) instead of instruction count,
+/// so that its results cannot be compared with calc models
+pub fn measures_time() -> bool {
+    crate::cli::MEASURE_METHOD
+        .get()
+        .is_some_and(|method| *method.lock().unwrap() == "time")
+}
+
+#[cfg(test)]
+pub use alloc_counter::allocation_count;
+
+#[cfg(test)]
+mod alloc_counter {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// System allocator counting allocations made by each thread
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|cnt| cnt.set(cnt.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Number of heap allocations made by the current thread so far
+    pub fn allocation_count() -> usize {
+        ALLOCATIONS.with(|cnt| cnt.get())
+    }
+}